@@ -2,7 +2,7 @@ use foldhash::{HashMap, HashMapExt};
 use std::sync::Arc;
 use vulkano::device::physical::PhysicalDeviceType;
 use vulkano::device::{Device, DeviceCreateInfo, DeviceExtensions, DeviceFeatures, Queue, QueueCreateInfo, QueueFlags};
-use vulkano::format::Format;
+use vulkano::format::{Format, FormatFeatures};
 use vulkano::instance::Instance;
 use vulkano::memory::MemoryPropertyFlags;
 use vulkano::swapchain::{PresentMode, Surface, SurfaceCapabilities, SurfaceInfo};
@@ -223,6 +223,11 @@ impl BuilderDevice
 				..DeviceFeatures::empty()
 			};
 		}
+		// compressed texture formats when available (see textureLoader_fromKTX2)
+		let supportedFeatures = physical_device.supported_features();
+		feature.texture_compression_bc = supportedFeatures.texture_compression_bc;
+		feature.texture_compression_etc2 = supportedFeatures.texture_compression_etc2;
+		feature.texture_compression_astc_ldr = supportedFeatures.texture_compression_astc_ldr;
 
 		// Now initializing the device. This is probably the most important object of Vulkan.
 		//
@@ -316,8 +321,25 @@ impl BuilderDevice
 		return tmp;
 	}
 
+	/// true if textures of this format can be sampled (BCn, ETC2 and ASTC depend of the gpu)
+	pub fn formatSupported(&self, format: Format) -> bool
+	{
+		return self.formatFeatures(format).contains(FormatFeatures::SAMPLED_IMAGE);
+	}
+
+	/// true if mip levels of this format can be generated on the gpu (linear blit)
+	pub fn formatBlitable(&self, format: Format) -> bool
+	{
+		return self.formatFeatures(format).contains(FormatFeatures::BLIT_SRC | FormatFeatures::BLIT_DST | FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR);
+	}
+
 	/////// PRIVATE
 
+	fn formatFeatures(&self, format: Format) -> FormatFeatures
+	{
+		return self.device.physical_device().format_properties(format).map(|x| x.optimal_tiling_features).unwrap_or(FormatFeatures::empty());
+	}
+
 	fn FindQueueForX(&self, ElementToFind: usize) -> Arc<Queue>
 	{
 		let mut SecondaryElement = 1;
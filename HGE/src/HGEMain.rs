@@ -17,6 +17,7 @@ use crate::ManagerAnimation::{AnimationHolder, ManagerAnimation};
 use crate::ManagerMemoryAllocator::ManagerMemoryAllocator;
 use crate::Models3D::ManagerModels::ManagerModels;
 use crate::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dline_holder, HGE_shader_2Dsimple, HGE_shader_2Dsimple_holder};
+use crate::Shaders::HGE_shader_2Dtext::{HGE_shader_2Dtext, HGE_shader_2Dtext_holder, HGE_SHADER_2DTEXT_SET, HGE_SHADER_2DTEXT_TEXTURE};
 use crate::Shaders::HGE_shader_3Dinstance::{HGE_shader_3Dinstance, HGE_shader_3Dinstance_holder};
 use crate::Shaders::HGE_shader_3Dsimple::{HGE_shader_3Dsimple, HGE_shader_3Dsimple_holder};
 use crate::Shaders::HGE_shader_screen::HGE_shader_screen;
@@ -98,6 +99,12 @@ impl HGEMain
 		return SINGLETON.get().unwrap_or_else(|| panic!("HGE have not been initialized"));
 	}
 
+	/// return true if "initialize" have been successfully called
+	pub fn isInitialized() -> bool
+	{
+		return SINGLETON.get().is_some();
+	}
+
 	pub fn preInitialize() -> anyhow::Result<HGEMain_preinitState<Initial>>
 	{
 		if (SINGLETON.get().is_some())
@@ -491,12 +498,15 @@ impl HGEMain
 
 		ShaderDrawer_Manager::singleton().register::<HGE_shader_2Dsimple_holder>(HGEsubpassName::UI);
 		ShaderDrawer_Manager::singleton().register::<HGE_shader_2Dline_holder>(HGEsubpassName::UI);
+		ShaderDrawer_Manager::singleton().register::<HGE_shader_2Dtext_holder>(HGEsubpassName::UI);
 		ShaderDrawer_Manager::singleton().register::<HGE_shader_3Dsimple_holder>(HGEsubpassName::WORLDSOLID);
 		ShaderDrawer_Manager::singleton().register::<HGE_shader_3Dinstance_holder>(HGEsubpassName::WORLDSOLID);
 
 		HGE_shader_3Dinstance::createPipeline()?;
 		HGE_shader_3Dsimple::createPipeline()?;
 		HGE_shader_2Dsimple::createPipeline()?;
+		// optional, needed only for ManagerFont sdf mode
+		let haveTextShader = HGE_shader_2Dtext::createPipeline().is_ok();
 		HGE_shader_screen::createPipeline()?;
 
 		self._rendering.write().window_size_dependent_setup();
@@ -520,11 +530,12 @@ impl HGEMain
 				"default",
 			),
 		);
+		// resized arrays : compressed textures are decompressed to fit in (with a warning)
 		ManagerTexture::singleton().descriptorSet_create(
 			"HGE_set1",
 			TextureDescriptor::new(
 				TextureDescriptor_type::SIZE_DEPENDENT(0..512, TextureDescriptor_process::RESIZE(texturesize, texturesize)),
-				TextureDescriptor_exclude::ARRAY(vec!["font".to_string()]),
+				TextureDescriptor_exclude::ARRAY(vec!["font".to_string(), HGE_SHADER_2DTEXT_TEXTURE.to_string()]),
 				HGE_shader_2Dsimple_holder::pipelineName(),
 				1,
 				"default",
@@ -534,13 +545,27 @@ impl HGEMain
 			"HGE_set2",
 			TextureDescriptor::new(
 				TextureDescriptor_type::SIZE_MIN(512.., TextureDescriptor_process::RESIZE(texturesizebig, texturesizebig)),
-				TextureDescriptor_exclude::ARRAY(vec!["font".to_string()]),
+				TextureDescriptor_exclude::ARRAY(vec!["font".to_string(), HGE_SHADER_2DTEXT_TEXTURE.to_string()]),
 				HGE_shader_2Dsimple_holder::pipelineName(),
 				2,
 				"default",
 			),
 		);
 
+		if (haveTextShader)
+		{
+			ManagerTexture::singleton().descriptorSet_create(
+				HGE_SHADER_2DTEXT_SET,
+				TextureDescriptor::new(
+					TextureDescriptor_type::<Range<u16>>::ONE(HGE_SHADER_2DTEXT_TEXTURE.to_string()),
+					TextureDescriptor_exclude::NONE,
+					HGE_shader_2Dtext_holder::pipelineName(),
+					0,
+					"default",
+				),
+			);
+		}
+
 		let tmp = HGEconfig::singleton().general_get();
 		HTraceError!(ManagerFont::singleton().FontLoad(tmp.fonts.path_fileUser.clone(), tmp.fonts.path_fileUniversel.clone(), tmp.fonts.path_fileBold.clone()));
 
@@ -40,6 +40,7 @@ pub struct Bar
 	_position: [interfacePosition; 2],
 	_textureSize: [f32; 2],
 	_orientation: Bar_orientation,
+	_clip: Option<[f32; 4]>,
 	_events: event<Bar>,
 	_hitbox: UiHitbox,
 	_cacheinfos: cacheInfos,
@@ -60,6 +61,7 @@ impl Bar
 			_position: [interfacePosition::default(), interfacePosition::default()],
 			_textureSize: [1.0, 1.0],
 			_orientation: Bar_orientation::HORIZONTAL,
+			_clip: None,
 			_events: event::new(),
 			_hitbox: UiHitbox::new(),
 			_cacheinfos: cacheInfos::default(),
@@ -271,6 +273,7 @@ impl ShaderDrawerImpl for Bar
 		for x in newplanes.iter_mut()
 		{
 			*x.components_mut() = self._components.clone();
+			x.layout_clip(self._clip);
 			if let Some(mut cache) = x.cache_get()
 			{
 				datas.combine(&mut cache);
@@ -314,6 +317,20 @@ impl UiPageContent for Bar
 	{
 		self._hitbox.clone()
 	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		if (self._clip != clip)
+		{
+			self._clip = clip;
+			self._cacheinfos.setNeedUpdate(true);
+		}
+	}
 }
 
 impl entities_utils for Bar
@@ -327,6 +344,7 @@ impl entities_utils for Bar
 			_position: self._position.clone(),
 			_textureSize: self._textureSize.clone(),
 			_orientation: self._orientation.clone(),
+			_clip: self._clip,
 			_events: self._events.clone(),
 			_hitbox: self._hitbox.clone(),
 			_cacheinfos: Default::default(),
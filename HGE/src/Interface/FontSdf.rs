@@ -0,0 +1,204 @@
+use crate::Textures::Order::Order;
+use crate::Textures::Orders::Order_partialTextureUpdate::Order_partialTextureUpdate;
+use ab_glyph::{Font, FontArc, GlyphId, PxScale};
+use foldhash::{HashMap, HashMapExt};
+use glyph_brush_layout::FontId;
+use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+/// reference size and distance spread of signed distance field glyphs, see ManagerFont::setRenderMode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontSdf_config
+{
+	/// size in pixel at which glyphs are generated, bigger is sharper on thin fonts but use more texture
+	pub size: f32,
+	/// distance in pixel (at the reference size) stored around each glyph, limit outline width and shadow offset
+	pub spread: f32,
+}
+
+impl Default for FontSdf_config
+{
+	fn default() -> Self
+	{
+		return FontSdf_config {
+			size: 48.0,
+			spread: 6.0,
+		};
+	}
+}
+
+/// place of a glyph in the sdf texture
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct FontSdf_glyph
+{
+	/// left, top, width, height in the texture, in pixel
+	pub(crate) rect: [u32; 4],
+	/// left, top, right, bottom from the glyph origin at the reference size, spread included
+	pub(crate) bounds: [f32; 4],
+}
+
+/// signed distance field generator and its texture packing (shelves), growing when full
+pub struct FontSdf
+{
+	_config: FontSdf_config,
+	_size: [u32; 2],
+	_cursor: [u32; 2],
+	_shelfHeight: u32,
+	_glyphs: HashMap<(usize, GlyphId), Option<FontSdf_glyph>>,
+}
+
+// space between glyphs in the texture, avoid bleeding with the linear sampler
+const FONTSDF_PADDING: u32 = 1;
+
+impl FontSdf
+{
+	pub fn new(config: FontSdf_config, size: [u32; 2]) -> Self
+	{
+		return FontSdf {
+			_config: config,
+			_size: size,
+			_cursor: [FONTSDF_PADDING, FONTSDF_PADDING],
+			_shelfHeight: 0,
+			_glyphs: HashMap::new(),
+		};
+	}
+
+	pub fn config(&self) -> FontSdf_config
+	{
+		return self._config;
+	}
+
+	/// texture size, the texture must be resized (keeping its content) when this change
+	pub fn size(&self) -> [u32; 2]
+	{
+		return self._size;
+	}
+
+	/// signed distance field of a coverage image : 0.5 on the edge, 1.0 at "spread" pixel inside, 0.0 at "spread" pixel outside
+	pub fn generate(coverage: &GrayImage, spread: f32) -> GrayImage
+	{
+		let (width, height) = coverage.dimensions();
+		let radius = spread.ceil() as i64;
+		let isInside = |x: i64, y: i64| -> Option<bool> {
+			if (x < 0 || y < 0 || x >= width as i64 || y >= height as i64)
+			{
+				return Some(false);
+			}
+			return match coverage.get_pixel(x as u32, y as u32).0[0]
+			{
+				0 => Some(false),
+				255 => Some(true),
+				_ => None,
+			};
+		};
+
+		let mut returning = GrayImage::new(width, height);
+		for (x, y, pixel) in returning.enumerate_pixels_mut()
+		{
+			let value = coverage.get_pixel(x, y).0[0] as f32 / 255.0;
+			// the edge cross this pixel, the coverage give the distance to it
+			let distance = match isInside(x as i64, y as i64)
+			{
+				None => value - 0.5,
+				Some(inside) =>
+				{
+					let mut nearest = spread;
+					for dy in -radius..=radius
+					{
+						for dx in -radius..=radius
+						{
+							if (isInside(x as i64 + dx, y as i64 + dy) != Some(inside))
+							{
+								nearest = nearest.min(((dx * dx + dy * dy) as f32).sqrt() - 0.5);
+							}
+						}
+					}
+					if (inside)
+					{
+						nearest
+					}
+					else
+					{
+						-nearest
+					}
+				}
+			};
+			let normalized = (0.5 + distance / (2.0 * spread)).clamp(0.0, 1.0);
+			*pixel = Luma([(normalized * 255.0).round() as u8]);
+		}
+		return returning;
+	}
+
+	/// glyph in the texture, generated and queued in "orders" if missing, None for glyphs without outline (space)
+	pub(crate) fn glyph(&mut self, fonts: &[FontArc], fontId: FontId, glyphId: GlyphId, orders: &mut Vec<Box<dyn Order + Send + Sync>>) -> Option<FontSdf_glyph>
+	{
+		if let Some(found) = self._glyphs.get(&(fontId.0, glyphId))
+		{
+			return *found;
+		}
+
+		let generated = fonts.get(fontId.0).and_then(|font| self.rasterize(font, glyphId));
+		let result = generated.map(|(image, bounds)| {
+			let position = self.place(image.width(), image.height());
+			orders.push(Box::new(Order_partialTextureUpdate {
+				raw: image.clone(),
+				offset: position,
+				sameThread: true,
+			}));
+			FontSdf_glyph {
+				rect: [position[0], position[1], image.width(), image.height()],
+				bounds,
+			}
+		});
+		self._glyphs.insert((fontId.0, glyphId), result);
+		return result;
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// sdf of the glyph at the reference size, white with the distance in alpha
+	fn rasterize(&self, font: &FontArc, glyphId: GlyphId) -> Option<(RgbaImage, [f32; 4])>
+	{
+		let glyph = glyphId.with_scale(PxScale::from(self._config.size));
+		let outlined = font.outline_glyph(glyph)?;
+		let pixelBounds = outlined.px_bounds();
+		let spread = self._config.spread.ceil() as u32;
+		let width = pixelBounds.width() as u32 + spread * 2;
+		let height = pixelBounds.height() as u32 + spread * 2;
+
+		let mut coverage = GrayImage::new(width, height);
+		outlined.draw(|x, y, value| {
+			coverage.put_pixel(x + spread, y + spread, Luma([(value.clamp(0.0, 1.0) * 255.0).round() as u8]));
+		});
+
+		let distance = Self::generate(&coverage, self._config.spread);
+		let image = RgbaImage::from_fn(width, height, |x, y| Rgba([255, 255, 255, distance.get_pixel(x, y).0[0]]));
+		let spread = spread as f32;
+		let bounds = [pixelBounds.min.x - spread, pixelBounds.min.y - spread, pixelBounds.max.x + spread, pixelBounds.max.y + spread];
+		return Some((image, bounds));
+	}
+
+	// next free place on the shelves, double the texture size if full
+	fn place(&mut self, width: u32, height: u32) -> [u32; 2]
+	{
+		loop
+		{
+			if (self._cursor[0] + width + FONTSDF_PADDING > self._size[0])
+			{
+				self._cursor = [FONTSDF_PADDING, self._cursor[1] + self._shelfHeight + FONTSDF_PADDING];
+				self._shelfHeight = 0;
+			}
+			if (self._cursor[0] + width + FONTSDF_PADDING <= self._size[0] && self._cursor[1] + height + FONTSDF_PADDING <= self._size[1])
+			{
+				let position = self._cursor;
+				self._cursor[0] += width + FONTSDF_PADDING;
+				self._shelfHeight = self._shelfHeight.max(height);
+				return position;
+			}
+
+			// both sides grow to keep the content (Order_resize), new glyphs go to the new space below
+			self._size = [self._size[0] * 2, self._size[1] * 2];
+			self._cursor = [FONTSDF_PADDING, self._cursor[1] + self._shelfHeight + FONTSDF_PADDING];
+			self._shelfHeight = 0;
+		}
+	}
+}
@@ -13,6 +13,7 @@ pub struct Line
 {
 	_pos: [interfacePosition; 2],
 	_color: [[f32; 4]; 2],
+	_clip: Option<[f32; 4]>,
 	_events: event<Line>,
 	_cacheinfos: cacheInfos
 }
@@ -47,6 +48,7 @@ impl Default for Line
 		{
 			_pos: [interfacePosition::new_percent(0.0, 0.0), interfacePosition::new_percent(0.0, 0.0)],
 			_color: [[0.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 0.0]],
+			_clip: None,
 			_events: event,
 			_cacheinfos: cacheInfos::default(),
 		};
@@ -120,12 +122,14 @@ impl ShaderDrawerImplReturn<HGE_shader_2Dsimple_def> for Line
 			position: self._pos[0].convertToVertex(),
 			ispixel: self._pos[0].getTypeInt(),
 			color: self._color[0],
+			clip: self._clip.unwrap_or([0.0; 4]),
 			..HGE_shader_2Dsimple_def::default()
 		});
 		vecstruct.push(HGE_shader_2Dsimple_def {
 			position: self._pos[1].convertToVertex(),
 			ispixel: self._pos[1].getTypeInt(),
 			color: self._color[1],
+			clip: self._clip.unwrap_or([0.0; 4]),
 			..HGE_shader_2Dsimple_def::default()
 		});
 		
@@ -138,4 +142,9 @@ impl UiPageContent for Line
 	fn getHitbox(&self) -> UiHitbox {
 		UiHitbox::new()
 	}
+	
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>) {
+		self._clip = clip;
+		self._cacheinfos.setNeedUpdate(true);
+	}
 }
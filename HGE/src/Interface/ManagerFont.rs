@@ -1,6 +1,10 @@
 use crate::assetStreamReader::assetManager;
+use crate::Interface::FontSdf::{FontSdf, FontSdf_config};
 use crate::Interface::Text::{Extra, TextCacheUpdater};
+use crate::Shaders::names;
 use crate::Shaders::HGE_shader_2Dsimple::HGE_shader_2Dsimple_def;
+use crate::Shaders::HGE_shader_2Dtext::{HGE_SHADER_2DTEXT_SET, HGE_SHADER_2DTEXT_TEXTURE};
+use crate::Shaders::Manager::ManagerShaders;
 use crate::Textures::generate::emptyTexture;
 use crate::Textures::textureLoader::textureLoader_fromRaw;
 use crate::Textures::Manager::ManagerTexture;
@@ -8,12 +12,12 @@ use crate::Textures::Order::Order;
 use crate::Textures::Orders::Order_load::Order_load;
 use crate::Textures::Orders::Order_partialTextureUpdate::Order_partialTextureUpdate;
 use crate::Textures::Orders::Order_resize::Order_resize;
-use ab_glyph::FontArc;
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
 use anyhow::anyhow;
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use foldhash::{HashMap, HashMapExt};
-use glyph_brush::{BrushAction, BrushError, GlyphBrush, GlyphBrushBuilder, GlyphVertex, OwnedSection, Rectangle};
+use glyph_brush::{BrushAction, BrushError, GlyphBrush, GlyphBrushBuilder, GlyphCruncher, GlyphVertex, OwnedSection, OwnedText, Rectangle, SectionGlyph};
 use glyph_brush_layout::FontId;
 use image::{GrayImage, Rgba, RgbaImage};
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
@@ -40,6 +44,18 @@ impl PartialEq for ManagerFont_verticestmp
 }
 
 static DEFAULTTEXTURESIZE: u32 = 32;
+static DEFAULTSDFTEXTURESIZE: u32 = 256;
+/// shrinkToFit never scale a text below this size (pixel), the text overflow instead
+pub const MANAGERFONT_SHRINK_MINSCALE: f32 = 6.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ManagerFont_renderMode
+{
+	/// glyphs rasterized at each size in the texture "font", drawn by the simple2D shader
+	BITMAP,
+	/// glyphs generated once as signed distance field in the texture "font_sdf", drawn at any size by the text2D shader
+	SDF(FontSdf_config),
+}
 
 pub struct ManagerFont
 {
@@ -47,8 +63,12 @@ pub struct ManagerFont
 	_storeText: Arc<DashMap<u128, OwnedSection<Extra>>>,
 	_storeCallBack: Arc<DashMap<u128, Arc<dyn Fn(TextCacheUpdater) + Send + Sync>>>,
 	_storeFontId: Arc<DashMap<String, FontId>>,
+	_registered: RwLock<Vec<(FontId, FontArc)>>,
+	_fallbacks: DashMap<String, Vec<String>>,
 	_fontEngine: RwLock<Option<GlyphBrush<ManagerFont_verticestmp, Extra>>>,
 	_fontEngineTextureSize: ArcSwap<[u32; 2]>,
+	_renderMode: ArcSwap<ManagerFont_renderMode>,
+	_sdf: Mutex<FontSdf>,
 	_threadLoading: Mutex<SingletonThread>,
 	_updateNeed: RwLock<bool>,
 }
@@ -84,6 +104,29 @@ impl ManagerFont
 			Some("fontsampler"),
 		);
 
+		// distance must be interpolated, mipmaps would mix glyphs
+		ManagerTexture::singleton().addSampler(
+			"fontsdfsampler",
+			SamplerCreateInfo {
+				mag_filter: Filter::Linear,
+				min_filter: Filter::Linear,
+				address_mode: [SamplerAddressMode::ClampToEdge; 3],
+				..Default::default()
+			},
+		);
+
+		let texture = emptyTexture(DEFAULTSDFTEXTURESIZE, DEFAULTSDFTEXTURESIZE);
+		ManagerTexture::singleton().texture_load(
+			HGE_SHADER_2DTEXT_TEXTURE,
+			Order_load::newPrioritize(textureLoader_fromRaw {
+				raw: texture.to_vec(),
+				width: texture.width(),
+				height: texture.height(),
+				canReload: false,
+			}),
+			Some("fontsdfsampler"),
+		);
+
 		let mut thread = SingletonThread::newFiltered(
 			|| {
 				HTracer::threadSetName("ManagerFont");
@@ -100,8 +143,12 @@ impl ManagerFont
 			_storeText: Default::default(),
 			_storeCallBack: Default::default(),
 			_storeFontId: Default::default(),
+			_registered: Default::default(),
+			_fallbacks: Default::default(),
 			_fontEngine: Default::default(),
 			_fontEngineTextureSize: ArcSwap::new(Arc::new([DEFAULTTEXTURESIZE, DEFAULTTEXTURESIZE])),
+			_renderMode: ArcSwap::new(Arc::new(ManagerFont_renderMode::BITMAP)),
+			_sdf: Mutex::new(FontSdf::new(FontSdf_config::default(), [DEFAULTSDFTEXTURESIZE, DEFAULTSDFTEXTURESIZE])),
 			_threadLoading: Mutex::new(thread),
 			_updateNeed: RwLock::new(false),
 		};
@@ -114,6 +161,7 @@ impl ManagerFont
 
 	pub fn Text_add(&self, newtext: OwnedSection<Extra>, callback: impl Fn(TextCacheUpdater) + Send + Sync + 'static, id: u128)
 	{
+		// fallbacks are applied at layout, fonts can be loaded or registered later
 		self._storeText.insert(id, newtext);
 		self._storeCallBack.insert(id, Arc::new(callback));
 		*self._updateNeed.write() = true;
@@ -125,31 +173,130 @@ impl ManagerFont
 		self._storeCallBack.remove(&id);
 	}
 
+	/// replace the fonts "user" (user language), "normal" (universal) and "bold", falling back to each other by default
+	/// fonts added with register, aliases and fallbacks already set are kept
 	pub fn FontLoad(&self, fileUser: String, fileUniversel: String, fileBold: String) -> anyhow::Result<()>
 	{
-		let fontUser = self.loadFond(fileUser)?;
-		let fontUser = FontArc::try_from_vec(fontUser)?;
-		let fontUniversel = self.loadFond(fileUniversel)?;
-		let fontUniversel = FontArc::try_from_vec(fontUniversel)?;
-		let fontBold = self.loadFond(fileBold)?;
-		let fontBold = FontArc::try_from_vec(fontBold)?;
+		let mut fonts = vec![self.loadFont(fileUser)?, self.loadFont(fileUniversel)?, self.loadFont(fileBold)?];
 
-		let mut glyph_brush = GlyphBrushBuilder::using_fonts([fontUser, fontUniversel, fontBold].into())
-			.cache_redraws(false)
-			.draw_cache_position_tolerance(2.0) // ignore subpixel differences totally
-			.draw_cache_scale_tolerance(3.0) // ignore scale differences
-			.build();
+		// the engine is rebuilt : registered fonts follow the 3 loaded ones, old ids are moved to the new ones
+		let mut newIds = HashMap::new();
+		for (fontId, font) in self._registered.write().iter_mut()
+		{
+			newIds.insert(*fontId, FontId(fonts.len()));
+			*fontId = FontId(fonts.len());
+			fonts.push(font.clone());
+		}
+		for (index, name) in ["user", "normal", "bold"].iter().enumerate()
+		{
+			if let Some(oldId) = self.FontIdGet(name)
+			{
+				newIds.insert(oldId, FontId(index));
+			}
+		}
+
+		*self._fontEngine.write() = Some(self.FontEngine_build(fonts));
+		// font ids are reused by the new fonts
+		{
+			let mut sdf = self._sdf.lock();
+			*sdf = FontSdf::new(sdf.config(), sdf.size());
+		}
+		let names: Vec<(String, FontId)> = self._storeFontId.iter().map(|x| (x.key().clone(), *x.value())).collect();
+		self._storeFontId.clear();
+		for (name, oldId) in names
+		{
+			if let Some(newId) = newIds.get(&oldId)
+			{
+				self._storeFontId.insert(name, *newId);
+			}
+		}
 		self._storeFontId.insert("user".to_string(), FontId(0));
 		self._storeFontId.insert("normal".to_string(), FontId(1));
 		self._storeFontId.insert("bold".to_string(), FontId(2));
-		let tmp = *self._fontEngineTextureSize.load_full();
-		glyph_brush.resize_texture(tmp[0], tmp[1]);
+		for (name, fallbacks) in [("user", vec!["normal"]), ("normal", vec!["user"]), ("bold", vec!["normal", "user"])]
+		{
+			if (!self._fallbacks.contains_key(name))
+			{
+				self.fallbackSet(name, fallbacks);
+			}
+		}
+		*self._updateNeed.write() = true;
+
+		return Ok(());
+	}
+
+	/// load a font file (relative to static) under "name", usable with FontIdGet, return its id
+	/// registering an existing name use the new font for texts added after, registered fonts are kept by FontLoad
+	pub fn register(&self, name: impl Into<String>, path: impl Into<String>) -> anyhow::Result<FontId>
+	{
+		let font = self.loadFont(path)?;
+		let fontId = {
+			let mut binding = self._fontEngine.write();
+			match binding.as_mut()
+			{
+				Some(fontEngine) => fontEngine.add_font(font.clone()),
+				None =>
+				{
+					*binding = Some(self.FontEngine_build(vec![font.clone()]));
+					FontId(0)
+				}
+			}
+		};
+		self._registered.write().push((fontId, font));
+		self._storeFontId.insert(name.into(), fontId);
+		*self._updateNeed.write() = true;
+		return Ok(fontId);
+	}
+
+	/// make "name" use the font registered as "target", return false if target is missing
+	pub fn alias(&self, name: impl Into<String>, target: &str) -> bool
+	{
+		let Some(fontId) = self.FontIdGet(target)
+		else
+		{
+			return false;
+		};
+		self._storeFontId.insert(name.into(), fontId);
+		return true;
+	}
+
+	/// fonts used, in this order, to draw the chars missing in the font "name" (fonts can be registered later)
+	pub fn fallbackSet(&self, name: impl Into<String>, fallbacks: impl IntoIterator<Item = impl Into<String>>)
+	{
+		self._fallbacks.insert(name.into(), fallbacks.into_iter().map(|x| x.into()).collect());
+	}
 
-		*self._fontEngine.write() = Some(glyph_brush);
+	pub fn fallbackGet(&self, name: &str) -> Vec<String>
+	{
+		return self._fallbacks.get(name).map(|x| x.value().clone()).unwrap_or_default();
+	}
 
+	/// switch all texts between bitmap and signed distance field rendering
+	/// SDF need the "HGE_2Dtext" shader (names::text2D), changing its config regenerate all glyphs
+	pub fn setRenderMode(&self, mode: ManagerFont_renderMode) -> anyhow::Result<()>
+	{
+		if let ManagerFont_renderMode::SDF(config) = mode
+		{
+			if (ManagerShaders::singleton().get(names::text2D).is_none())
+			{
+				return Err(anyhow!("sdf text need the shader \"{}\"", names::text2D));
+			}
+			let mut sdf = self._sdf.lock();
+			if (sdf.config() != config)
+			{
+				*sdf = FontSdf::new(config, sdf.size());
+			}
+		}
+		self._renderMode.store(Arc::new(mode));
+		*self._updateNeed.write() = true;
 		return Ok(());
 	}
 
+	pub fn getRenderMode(&self) -> ManagerFont_renderMode
+	{
+		return **self._renderMode.load();
+	}
+
 	pub fn FontIdGet(&self, name: &str) -> Option<FontId>
 	{
 		return match self._storeFontId.get(name)
@@ -159,6 +306,176 @@ impl ManagerFont
 		};
 	}
 
+	/// horizontal position (in pixel) of each char boundary of a single line text, first one is 0.0
+	/// return only [0.0] if font are not loaded
+	pub fn Text_advances(&self, text: &str, scale: f32, font: FontId) -> Vec<f32>
+	{
+		let mut returning = vec![0.0];
+		let binding = self._fontEngine.read();
+		let Some(fontEngine) = binding.as_ref()
+		else
+		{
+			return returning;
+		};
+		if (fontEngine.fonts().get(font.0).is_none())
+		{
+			return returning;
+		}
+
+		let chain = self.fallbackChain(font);
+		let mut cursor = 0.0;
+		let mut lastGlyph = None;
+		for char in text.chars()
+		{
+			let fontId = Self::fallbackFont(fontEngine.fonts(), &chain, char);
+			let font = fontEngine.fonts()[fontId.0].as_scaled(scale);
+			let glyph = font.glyph_id(char);
+			// no kerning between two fonts
+			if let Some((lastFont, last)) = lastGlyph
+			{
+				if (lastFont == fontId)
+				{
+					cursor += font.kern(last, glyph);
+				}
+			}
+			cursor += font.h_advance(glyph);
+			returning.push(cursor);
+			lastGlyph = Some((fontId, glyph));
+		}
+
+		return returning;
+	}
+
+	/// baseline position (section pixel) of the first glyph of each text of a section, with the ascent and descent of its font
+	pub fn Text_firstGlyphs(&self, section: &OwnedSection<Extra>) -> HashMap<usize, ([f32; 2], f32, f32)>
+	{
+		let mut returning = HashMap::new();
+		let mut binding = self._fontEngine.write();
+		let Some(fontEngine) = binding.as_mut()
+		else
+		{
+			return returning;
+		};
+
+		let glyphs: Vec<_> = fontEngine.glyphs(section.to_borrowed()).map(|x| (x.section_index, x.font_id, x.glyph.position, x.glyph.scale)).collect();
+		for (index, fontId, position, scale) in glyphs
+		{
+			if (returning.contains_key(&index))
+			{
+				continue;
+			}
+			let font = fontEngine.fonts()[fontId.0].as_scaled(scale);
+			returning.insert(index, ([position.x, position.y], font.ascent(), font.descent()));
+		}
+		return returning;
+	}
+
+	/// size in pixel (width, height) of a section once laid out, [0.0, 0.0] if empty or if fonts are not loaded
+	pub fn measure(&self, section: &OwnedSection<Extra>) -> [f32; 2]
+	{
+		let section = self.fallbackApply(section.clone());
+		let mut binding = self._fontEngine.write();
+		let Some(fontEngine) = binding.as_mut()
+		else
+		{
+			return [0.0, 0.0];
+		};
+
+		return match fontEngine.glyph_bounds(section.to_borrowed())
+		{
+			None => [0.0, 0.0],
+			Some(bounds) => [bounds.width(), bounds.height()],
+		};
+	}
+
+	/// section with all its texts scaled down (never up) to fit in "size" (width, height in pixel), and the factor used
+	/// texts are not scaled below MANAGERFONT_SHRINK_MINSCALE
+	pub fn shrinkToFit(&self, section: &OwnedSection<Extra>, size: [f32; 2]) -> (OwnedSection<Extra>, f32)
+	{
+		return Self::shrinkToFit_with(section, size, |x| self.measure(x));
+	}
+
+	/// shrinkToFit with another measure of the section size
+	pub fn shrinkToFit_with(section: &OwnedSection<Extra>, size: [f32; 2], measure: impl Fn(&OwnedSection<Extra>) -> [f32; 2]) -> (OwnedSection<Extra>, f32)
+	{
+		let mut factor = 1.0;
+		let mut returning = section.clone();
+		// wrapping can change with the scale, retry a few times from the new measure
+		for _ in 0..4
+		{
+			let [width, height] = measure(&returning);
+			if (width <= size[0] && height <= size[1])
+			{
+				break;
+			}
+			let smallest = returning.text.iter().map(|x| x.scale.y).fold(f32::MAX, f32::min);
+			let ratio = (size[0] / width.max(1.0)).min(size[1] / height.max(1.0)).min(0.95).max(MANAGERFONT_SHRINK_MINSCALE / smallest.max(1.0));
+			if (ratio >= 1.0)
+			{
+				break;
+			}
+			factor *= ratio;
+			for text in returning.text.iter_mut()
+			{
+				text.scale = PxScale {
+					x: text.scale.x * ratio,
+					y: text.scale.y * ratio,
+				};
+			}
+		}
+		return (returning, factor);
+	}
+
+	/// section cut at the end with an ellipsis to be at most "maxWidth" pixel wide, made for single line texts
+	pub fn ellipsis(&self, section: OwnedSection<Extra>, maxWidth: f32) -> OwnedSection<Extra>
+	{
+		let ellipsis = self.ellipsisGet(&section);
+		return Self::ellipsis_with(section, maxWidth, ellipsis, |x| self.measure(x));
+	}
+
+	/// ellipsis with a given ellipsis string and another measure of the section size
+	pub fn ellipsis_with(section: OwnedSection<Extra>, maxWidth: f32, ellipsis: &str, measure: impl Fn(&OwnedSection<Extra>) -> [f32; 2]) -> OwnedSection<Extra>
+	{
+		if (measure(&section)[0] <= maxWidth)
+		{
+			return section;
+		}
+
+		// most chars kept with the ellipsis still fitting, at least the ellipsis alone
+		let total: usize = section.text.iter().map(|x| x.text.chars().count()).sum();
+		let (mut low, mut high) = (0, total);
+		while (low < high)
+		{
+			let middle = (low + high + 1) / 2;
+			if (measure(&Self::ellipsisCut(&section, middle, ellipsis))[0] <= maxWidth)
+			{
+				low = middle;
+			}
+			else
+			{
+				high = middle - 1;
+			}
+		}
+		return Self::ellipsisCut(&section, low, ellipsis);
+	}
+
+	/// "text" cut in runs, each one drawn by the first font of "chain" having its chars (fonts missing from "fonts" are skipped)
+	/// chars missing in every font use the first loaded font of the chain, drawn as its missing glyph
+	pub fn fallbackRuns(fonts: &[FontArc], chain: &[FontId], text: &str) -> Vec<(FontId, String)>
+	{
+		let mut runs: Vec<(FontId, String)> = Vec::new();
+		for char in text.chars()
+		{
+			let fontId = Self::fallbackFont(fonts, chain, char);
+			match runs.last_mut()
+			{
+				Some((current, run)) if *current == fontId => run.push(char),
+				_ => runs.push((fontId, char.to_string())),
+			}
+		}
+		return runs;
+	}
+
 	pub fn FontEngineGet(&self) -> RwLockReadGuard<'_, Option<GlyphBrush<ManagerFont_verticestmp, Extra>>>
 	{
 		self._fontEngine.read()
@@ -182,30 +499,153 @@ impl ManagerFont
 
 	//////////// PRIVATE //////////////
 
-	fn loadFond(&self, file: impl Into<String>) -> anyhow::Result<Vec<u8>>
+	fn loadFont(&self, file: impl Into<String>) -> anyhow::Result<FontArc>
 	{
 		let file = file.into();
-		let returning = match assetManager::singleton().readFile(file.clone())
+		let Some(result) = assetManager::singleton().readFile(file.clone())
+		else
 		{
-			None => Err(anyhow!(format!("Cannot read font file {}", file))),
-			Some(result) => Ok(result.into_inner()),
+			return Err(anyhow!(format!("Cannot read font file {}", file)));
 		};
 
+		return FontArc::try_from_vec(result.into_inner()).map_err(|err| anyhow!("font file {} : {}", file, err));
+	}
+
+	fn FontEngine_build(&self, fonts: Vec<FontArc>) -> GlyphBrush<ManagerFont_verticestmp, Extra>
+	{
+		let mut glyph_brush = GlyphBrushBuilder::using_fonts(fonts)
+			.cache_redraws(false)
+			.draw_cache_position_tolerance(2.0) // ignore subpixel differences totally
+			.draw_cache_scale_tolerance(3.0) // ignore scale differences
+			.build();
+		let tmp = *self._fontEngineTextureSize.load_full();
+		glyph_brush.resize_texture(tmp[0], tmp[1]);
+		return glyph_brush;
+	}
+
+	// the font then its fallbacks, fallbacks not registered are ignored
+	fn fallbackChain(&self, font: FontId) -> Vec<FontId>
+	{
+		let mut chain = vec![font];
+		// a font can have many names (alias)
+		let names: Vec<String> = self._storeFontId.iter().filter(|x| *x.value() == font).map(|x| x.key().clone()).collect();
+		for fallback in names.iter().flat_map(|name| self.fallbackGet(name))
+		{
+			if let Some(id) = self.FontIdGet(&fallback)
+			{
+				if (!chain.contains(&id))
+				{
+					chain.push(id);
+				}
+			}
+		}
+		return chain;
+	}
+
+	// first font of the chain having a glyph for this char, the first one if none
+	// "…" if a font of the last text can draw it
+	fn ellipsisGet(&self, section: &OwnedSection<Extra>) -> &'static str
+	{
+		let binding = self._fontEngine.read();
+		let Some(fontEngine) = binding.as_ref()
+		else
+		{
+			return "...";
+		};
+		let fontId = section.text.last().map(|x| x.font_id).unwrap_or_default();
+		let chain = self.fallbackChain(fontId);
+		let found = Self::fallbackFont(fontEngine.fonts(), &chain, '…');
+		return match fontEngine.fonts().get(found.0).is_some_and(|font| font.glyph_id('…').0 != 0)
+		{
+			true => "…",
+			false => "...",
+		};
+	}
+
+	// keep the "count" first chars of the section and add the ellipsis to the last kept text
+	fn ellipsisCut(section: &OwnedSection<Extra>, count: usize, ellipsis: &str) -> OwnedSection<Extra>
+	{
+		let mut returning = section.clone();
+		let mut remaining = count;
+		let mut lastKept = 0;
+		for (index, text) in returning.text.iter_mut().enumerate()
+		{
+			let chars = text.text.chars().count();
+			if (remaining > 0)
+			{
+				lastKept = index;
+			}
+			text.text = text.text.chars().take(remaining).collect();
+			remaining -= remaining.min(chars);
+		}
+		if let Some(text) = returning.text.get_mut(lastKept)
+		{
+			text.text = format!("{}{}", text.text.trim_end(), ellipsis);
+		}
 		return returning;
 	}
 
+	fn fallbackFont(fonts: &[FontArc], chain: &[FontId], char: char) -> FontId
+	{
+		let loaded: Vec<FontId> = chain.iter().copied().filter(|id| fonts.get(id.0).is_some()).collect();
+		return loaded
+			.iter()
+			.find(|id| fonts[id.0].glyph_id(char).0 != 0)
+			.or(loaded.first())
+			.copied()
+			.unwrap_or(chain.first().copied().unwrap_or_default());
+	}
+
+	fn fallbackApply(&self, section: OwnedSection<Extra>) -> OwnedSection<Extra>
+	{
+		let binding = self._fontEngine.read();
+		let Some(fontEngine) = binding.as_ref()
+		else
+		{
+			return section;
+		};
+		return self.fallbackSplit(fontEngine.fonts(), section);
+	}
+
+	// split each text of the section in runs, each one drawn by the font having its chars
+	fn fallbackSplit(&self, fonts: &[FontArc], mut section: OwnedSection<Extra>) -> OwnedSection<Extra>
+	{
+		let mut texts: Vec<OwnedText<Extra>> = Vec::new();
+		for text in section.text.drain(..)
+		{
+			let chain = self.fallbackChain(text.font_id);
+			if (chain.len() == 1)
+			{
+				texts.push(text);
+				continue;
+			}
+
+			for (fontId, run) in Self::fallbackRuns(fonts, &chain, &text.text)
+			{
+				texts.push(OwnedText::<Extra>::new(run).with_scale(text.scale).with_font_id(fontId).with_extra(text.extra.clone()));
+			}
+		}
+		section.text = texts;
+		return section;
+	}
+
 	fn FontEngine_internalCacheUpdate(&self)
 	{
 		HTracer::threadSetName("FontEngine");
 		if let Some(FontEngine) = self._fontEngine.write().as_mut()
 		{
+			if (matches!(self.getRenderMode(), ManagerFont_renderMode::SDF(_)))
+			{
+				self.sdfCacheUpdate(FontEngine);
+				return;
+			}
 			if ManagerTexture::singleton().descriptorSet_getIdTexture(["HGE_set0"], "font").is_none()
 			{
 				return;
 			}
 
 			self._storeText.iter().for_each(|item| {
-				let tmp = item.value().clone();
+				let tmp = self.fallbackSplit(FontEngine.fonts(), item.value().clone());
 				FontEngine.queue(tmp.to_borrowed());
 			});
 
@@ -236,6 +676,8 @@ impl ManagerFont
 								vertex: x.vertex.drain(0..).collect(),
 								indices: x.indices.drain(0..).collect(),
 								isUpdated: true,
+								isSdf: false,
+								sdfScales: vec![],
 							});
 						});
 
@@ -246,6 +688,8 @@ impl ManagerFont
 								vertex: vec![],
 								indices: vec![],
 								isUpdated: true,
+								isSdf: false,
+								sdfScales: vec![],
 							};
 
 							cache.into_iter().for_each(|mut x| {
@@ -291,6 +735,106 @@ impl ManagerFont
 		}
 	}
 
+	// layout with glyph_brush, glyphs from the sdf texture scaled to the text size
+	fn sdfCacheUpdate(&self, FontEngine: &mut GlyphBrush<ManagerFont_verticestmp, Extra>)
+	{
+		if ManagerTexture::singleton().descriptorSet_getIdTexture([HGE_SHADER_2DTEXT_SET], HGE_SHADER_2DTEXT_TEXTURE).is_none()
+		{
+			return;
+		}
+
+		let mut sdf = self._sdf.lock();
+		let oldSize = sdf.size();
+		let mut textureUpdate: Vec<Box<dyn Order + Send + Sync>> = Vec::new();
+		let mut texts = Vec::new();
+		for item in self._storeText.iter()
+		{
+			let section = self.fallbackSplit(FontEngine.fonts(), item.value().clone());
+			let glyphs: Vec<SectionGlyph> = FontEngine.glyphs(section.to_borrowed()).cloned().collect();
+			let mut placed = Vec::new();
+			for glyph in glyphs
+			{
+				if let Some(sdfGlyph) = sdf.glyph(FontEngine.fonts(), glyph.font_id, glyph.glyph.id, &mut textureUpdate)
+				{
+					let extra = section.text.get(glyph.section_index).map(|x| x.extra.clone()).unwrap_or_default();
+					placed.push((glyph.glyph.position, glyph.glyph.scale, sdfGlyph, extra));
+				}
+			}
+			texts.push((*item.key(), placed));
+		}
+
+		// uv are computed after all new glyphs are placed, the texture can have grown
+		let size = sdf.size();
+		if (size != oldSize)
+		{
+			HTrace!("Resizing sdf font texture {:?}", size);
+			textureUpdate.insert(
+				0,
+				Box::new(Order_resize {
+					newWidth: size[0],
+					newHeight: size[1],
+					sameThread: true,
+				}),
+			);
+		}
+		let config = sdf.config();
+		drop(sdf);
+
+		if (!textureUpdate.is_empty())
+		{
+			ManagerTexture::singleton().texture_update(HGE_SHADER_2DTEXT_TEXTURE, textureUpdate);
+		}
+		for (textId, placed) in texts
+		{
+			let mut cache = TextCacheUpdater {
+				vertex: vec![],
+				indices: vec![],
+				isUpdated: true,
+				isSdf: true,
+				sdfScales: vec![],
+			};
+			for (position, scale, sdfGlyph, extra) in placed
+			{
+				let first = cache.vertex.len() as u32;
+				cache.vertex.append(&mut Self::sdfQuad([position.x, position.y], scale, sdfGlyph.rect, sdfGlyph.bounds, size, config.size, &extra));
+				cache.indices.extend([0, 1, 2, 1, 3, 2].iter().map(|x| x + first));
+				cache.sdfScales.push(scale.y / config.size);
+			}
+
+			if let Some(callback) = self._storeCallBack.get(&textId).map(|x| x.value().clone())
+			{
+				callback(cache);
+			}
+		}
+		*self._updateNeed.write() = false;
+	}
+
+	fn sdfQuad(origin: [f32; 2], scale: PxScale, rect: [u32; 4], bounds: [f32; 4], textureSize: [u32; 2], referenceSize: f32, extra: &Extra) -> Vec<HGE_shader_2Dsimple_def>
+	{
+		let factor = [scale.x / referenceSize, scale.y / referenceSize];
+		let left = origin[0] + bounds[0] * factor[0];
+		let top = origin[1] + bounds[1] * factor[1];
+		let right = origin[0] + bounds[2] * factor[0];
+		let bottom = origin[1] + bounds[3] * factor[1];
+		let uvLeft = rect[0] as f32 / textureSize[0] as f32;
+		let uvTop = rect[1] as f32 / textureSize[1] as f32;
+		let uvRight = (rect[0] + rect[2]) as f32 / textureSize[0] as f32;
+		let uvBottom = (rect[1] + rect[3]) as f32 / textureSize[1] as f32;
+
+		return [([left, top], [uvLeft, uvTop]), ([right, top], [uvRight, uvTop]), ([left, bottom], [uvLeft, uvBottom]), ([right, bottom], [uvRight, uvBottom])]
+			.into_iter()
+			.map(|(position, uvcoord)| HGE_shader_2Dsimple_def {
+				position: [position[0], position[1], 0.0],
+				ispixel: 1,
+				texture: Some(HGE_SHADER_2DTEXT_TEXTURE.to_string()),
+				uvcoord,
+				color: extra.color,
+				color_blend_type: 0,
+				clip: [0.0; 4],
+			})
+			.collect();
+	}
+
 	fn processInternal_textureUpdate(&self, rect: Rectangle<u32>, tex_data: &[u8]) -> Order_partialTextureUpdate
 	{
 		let gray = GrayImage::from_raw(rect.width(), rect.height(), tex_data.to_vec()).unwrap();
@@ -354,6 +898,7 @@ impl ManagerFont
 				uvcoord: [tex_coords.min.x, tex_coords.min.y],
 				color: vertex_data.extra.color,
 				color_blend_type: 0,
+				clip: [0.0; 4],
 			},
 			HGE_shader_2Dsimple_def {
 				position: [gl_rect.max.x, gl_rect.min.y, 0.0],
@@ -362,6 +907,7 @@ impl ManagerFont
 				uvcoord: [tex_coords.max.x, tex_coords.min.y],
 				color: vertex_data.extra.color,
 				color_blend_type: 0,
+				clip: [0.0; 4],
 			},
 			HGE_shader_2Dsimple_def {
 				position: [gl_rect.min.x, gl_rect.max.y, 0.0],
@@ -370,6 +916,7 @@ impl ManagerFont
 				uvcoord: [tex_coords.min.x, tex_coords.max.y],
 				color: vertex_data.extra.color,
 				color_blend_type: 0,
+				clip: [0.0; 4],
 			},
 			HGE_shader_2Dsimple_def {
 				position: [gl_rect.max.x, gl_rect.max.y, 0.0],
@@ -378,6 +925,7 @@ impl ManagerFont
 				uvcoord: [tex_coords.max.x, tex_coords.max.y],
 				color: vertex_data.extra.color,
 				color_blend_type: 0,
+				clip: [0.0; 4],
 			},
 		];
 
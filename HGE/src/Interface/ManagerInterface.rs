@@ -1,21 +1,40 @@
 use crate::components::event::{event_trait, event_type};
-use crate::Interface::UiPage::UiPage;
-use arc_swap::ArcSwap;
+use crate::Animation::Animation;
+use crate::Interface::UiMarkup::{UiMarkup, UiMarkup_handler};
+use crate::Interface::UiOverlay::{UiOverlay_entry, UiOverlay_options, UiToast, UITOAST_STYLE_CLASS};
+use crate::Interface::UiPage::{UiFocus_direction, UiPage, UiPageContent, UiScrollEvent, UiTextEvent};
+use crate::Interface::UiTheme::UiTheme;
+use crate::Interface::UiTransition::UiTransition;
+use crate::ManagerAnimation::ManagerAnimation;
+use crate::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dline_holder, HGE_shader_2Dsimple_holder, HGE_shader_2Dsimple_transform};
+use crate::Shaders::HGE_shader_2Dtext::HGE_shader_2Dtext_holder;
+use crate::Shaders::ShaderDrawer::ShaderDrawer_Manager;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use dashmap::mapref::one::Ref;
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use singletonThread::SingletonThread;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
 use std::thread::sleep;
 use std::time::Duration;
+use HArcMut::HArcMut;
 use Htrace::namedThread;
 use Htrace::HTracer::HTracer;
 
 pub struct ManagerInterface
 {
 	_pageArray: DashMap<String, UiPage>,
+	_handlers: DashMap<String, UiMarkup_handler>,
 	_activePage: ArcSwap<String>,
+	_transition: ArcSwapOption<UiTransition>,
+	_overlays: RwLock<Vec<UiOverlay_entry>>,
+	_toasts: Mutex<Vec<UiToast>>,
+	_toastsDirty: AtomicBool,
+	_mousePosition: ArcSwap<[u16; 2]>,
+	_dragPressed: AtomicBool,
+	_textInputWanted: AtomicBool,
+	_clipboard: ArcSwap<String>,
 	_threadEachTickUpdate: RwLock<SingletonThread>,
 	_threadEachSecondUpdate: RwLock<SingletonThread>,
 	_threadRefreshwindows: RwLock<SingletonThread>,
@@ -60,11 +79,26 @@ impl ManagerInterface
 					page.eventWinRefresh();
 				}
 			}
+
+			for overlay in Self::singleton()._overlays.write().iter_mut()
+			{
+				overlay.winRefresh();
+			}
+			Self::singleton()._toastsDirty.store(true, Ordering::Release);
 		});
 
 		return ManagerInterface {
 			_pageArray: Default::default(),
+			_handlers: Default::default(),
 			_activePage: ArcSwap::new(Arc::new("default".to_string())),
+			_transition: ArcSwapOption::empty(),
+			_overlays: RwLock::new(Vec::new()),
+			_toasts: Mutex::new(Vec::new()),
+			_toastsDirty: AtomicBool::new(false),
+			_mousePosition: ArcSwap::new(Arc::new([0, 0])),
+			_dragPressed: AtomicBool::new(false),
+			_textInputWanted: AtomicBool::new(false),
+			_clipboard: ArcSwap::new(Arc::new(String::new())),
 			_threadEachTickUpdate: RwLock::new(threadEachTick),
 			_threadEachSecondUpdate: RwLock::new(threadEachSecond),
 			_threadRefreshwindows: RwLock::new(threadRefreshwindows),
@@ -78,6 +112,20 @@ impl ManagerInterface
 	}
 
 	pub fn changeActivePage(&self, name: impl Into<String>)
+	{
+		let transition = self._transition.load().as_deref().copied();
+		self.changeActivePage_transition(name, transition);
+	}
+
+	/// transition used by changeActivePage, None to switch immediately (default)
+	pub fn setTransition(&self, transition: Option<UiTransition>)
+	{
+		self._transition.store(transition.map(Arc::new));
+	}
+
+	/// change the active page with an animation, both pages are shown until the end of the transition
+	/// changing page again before the end is ignored
+	pub fn changeActivePage_transition(&self, name: impl Into<String>, transition: Option<UiTransition>)
 	{
 		if (self._canChangePage.compare_exchange(true, false, Ordering::Release, Ordering::Acquire).is_err())
 		{
@@ -85,7 +133,7 @@ impl ManagerInterface
 		}
 
 		let name = name.into();
-		let _ = namedThread!(|| {
+		let _ = namedThread!(move || {
 			let oldpage = (&*Self::singleton()._activePage.swap(Arc::new(name.clone()))).clone();
 
 			// si on change pas de page, on refresh juste
@@ -102,17 +150,41 @@ impl ManagerInterface
 
 			if let Some(mut page) = Self::singleton()._pageArray.get_mut(&oldpage)
 			{
+				// a focused text content close the IME on BLUR
+				page.focus_clear();
 				page.event_trigger(event_type::EXIT);
-				page.cache_remove();
+				if (transition.is_none())
+				{
+					page.cache_remove();
+				}
 			};
 
+			// new page transformed before its first submit
+			if let Some(transition) = &transition
+			{
+				Self::singleton().transitionTransforms(&oldpage, &name, transition.transforms(0.0));
+			}
+
 			if let Some(mut page) = Self::singleton()._pageArray.get_mut(&name)
 			{
 				page.event_trigger(event_type::ENTER);
 				page.cache_checkupdate();
 			}
 
-			Self::singleton()._canChangePage.store(true, Ordering::Release);
+			let Some(transition) = transition
+			else
+			{
+				Self::singleton()._canChangePage.store(true, Ordering::Release);
+				return;
+			};
+
+			let mut animation = Animation::new(transition.duration, HArcMut::new(()), oldpage.clone(), name.clone(), move |animation, progress| {
+				Self::singleton().transitionTransforms(&animation.startState, &animation.endState, transition.transforms(progress));
+			});
+			animation.setModeNoRepeat(Some(move || {
+				Self::singleton().transitionEnd(&oldpage, &name);
+			}));
+			ManagerAnimation::singleton().append(animation);
 		});
 	}
 
@@ -129,12 +201,180 @@ impl ManagerInterface
 
 	pub fn mouseUpdate(&self, x: u16, y: u16, mouseClick: bool) -> bool
 	{
-		let Some(page) = self._pageArray.get_mut(self.getActivePage().as_str())
+		self._mousePosition.store(Arc::new([x, y]));
+		let mut clicked = mouseClick;
+		let mut returning = false;
+		for name in self.inputPages()
+		{
+			let Some(mut page) = self._pageArray.get_mut(&name)
+			else
+			{
+				continue;
+			};
+			returning |= page.eventMouse(x, y, clicked);
+			if (clicked)
+			{
+				page.focus_atPosition(x, y);
+			}
+			// a click used by an upper page is only a move for the pages below
+			clicked &= !returning;
+		}
+		return returning;
+	}
+
+	/// mouse wheel in pixel, sent to the content under the mouse (positive values show what is below/right)
+	pub fn scrollUpdate(&self, x: f32, y: f32) -> bool
+	{
+		let [mousex, mousey] = self.getMousePosition();
+		return self.inputFirst(|page| page.eventScroll(mousex, mousey, &UiScrollEvent::WHEEL([x, y])));
+	}
+
+	/// pointer state (mouse button or touch) used for drag scrolling, in window pixel
+	pub fn dragUpdate(&self, x: u16, y: u16, pressed: bool) -> bool
+	{
+		let position = [x as f32, y as f32];
+		let event = match (self._dragPressed.swap(pressed, Ordering::AcqRel), pressed)
+		{
+			(false, true) => UiScrollEvent::DRAG_START(position),
+			(true, true) => UiScrollEvent::DRAG_MOVE(position),
+			(true, false) => UiScrollEvent::DRAG_END,
+			(false, false) => return false,
+		};
+
+		return self.inputFirst(|page| page.eventScroll(x, y, &event));
+	}
+
+	/// move the focus of the active page, or of the topmost overlay (arrow keys, d-pad, tab)
+	pub fn navigate(&self, direction: UiFocus_direction) -> bool
+	{
+		return self.inputFirst(|page| page.eventNavigate(direction));
+	}
+
+	/// activate the focused content of the active page or overlays (enter, gamepad A)
+	pub fn activate(&self) -> bool
+	{
+		for name in self.inputPages()
+		{
+			if (self._pageArray.get(&name).is_some_and(|page| page.eventActivate()))
+			{
+				return true;
+			}
+		}
+		return false;
+	}
+
+	/// send keyboard text to the focused content of the active page or overlays, return true if used
+	pub fn textInput(&self, event: UiTextEvent) -> bool
+	{
+		for name in self.inputPages()
+		{
+			if (self._pageArray.get(&name).is_some_and(|page| page.eventText(&event)))
+			{
+				return true;
+			}
+		}
+		return false;
+	}
+
+	/// show a page above the active page and the older overlays, a page already shown as overlay is moved on top
+	/// stacked overlays should use a growing dimZ (and contents above it) to dim the overlays below
+	pub fn pushOverlay(&self, name: impl Into<String>, options: UiOverlay_options)
+	{
+		let name = name.into();
+		{
+			let mut overlays = self._overlays.write();
+			if let Some(index) = overlays.iter().position(|x| x.name == name)
+			{
+				overlays.remove(index).remove();
+			}
+			overlays.push(UiOverlay_entry::new(name.clone(), options));
+		}
+
+		// page events can use the manager, so not in the caller thread
+		let _ = namedThread!(move || {
+			if let Some(mut page) = Self::singleton()._pageArray.get_mut(&name)
+			{
+				page.event_trigger(event_type::ENTER);
+				page.cache_checkupdate();
+			}
+		});
+	}
+
+	/// hide the topmost overlay, return its name
+	pub fn popOverlay(&self) -> Option<String>
+	{
+		let mut overlay = self._overlays.write().pop()?;
+		overlay.remove();
+		Self::overlayExit(overlay.name.clone());
+		return Some(overlay.name);
+	}
+
+	/// hide an overlay wherever it is in the stack, return false if not shown
+	pub fn removeOverlay(&self, name: &str) -> bool
+	{
+		let mut overlays = self._overlays.write();
+		let Some(index) = overlays.iter().position(|x| x.name == name)
 		else
 		{
 			return false;
 		};
-		return page.eventMouse(x, y, mouseClick);
+		let mut overlay = overlays.remove(index);
+		drop(overlays);
+		overlay.remove();
+		Self::overlayExit(overlay.name);
+		return true;
+	}
+
+	/// overlays names, from the bottom to the top
+	pub fn getOverlays(&self) -> Vec<String>
+	{
+		return self._overlays.read().iter().map(|x| x.name.clone()).collect();
+	}
+
+	/// show a short message at the bottom of the window (UiTheme class "toast"), removed after "duration"
+	pub fn toast(&self, message: impl Into<String>, duration: Duration)
+	{
+		self._toasts.lock().push(UiToast::new(message.into(), duration));
+		self._toastsDirty.store(true, Ordering::Release);
+	}
+
+	/// called by text contents on focus/blur, the front open IME / soft keyboard when wanted
+	pub fn textInput_want(&self, wanted: bool)
+	{
+		self._textInputWanted.store(wanted, Ordering::Release);
+	}
+
+	pub fn textInput_isWanted(&self) -> bool
+	{
+		return self._textInputWanted.load(Ordering::Acquire);
+	}
+
+	/// set system clipboard (internal only on android)
+	pub fn clipboard_set(&self, text: impl Into<String>)
+	{
+		let text = text.into();
+		#[cfg(not(target_os = "android"))]
+		if let Ok(mut clipboard) = arboard::Clipboard::new()
+		{
+			let _ = clipboard.set_text(text.clone());
+		}
+		self._clipboard.store(Arc::new(text));
+	}
+
+	pub fn clipboard_get(&self) -> String
+	{
+		#[cfg(not(target_os = "android"))]
+		if let Ok(text) = arboard::Clipboard::new().and_then(|mut x| x.get_text())
+		{
+			return text;
+		}
+		return self._clipboard.load().to_string();
+	}
+
+	/// last mouse position received by mouseUpdate, in window pixel
+	pub fn getMousePosition(&self) -> [u16; 2]
+	{
+		return **self._mousePosition.load();
 	}
 
 	pub fn WindowRefreshed(&self)
@@ -153,7 +393,7 @@ impl ManagerInterface
 			oldpage.cache_remove();
 		}
 
-		if (self.getActivePage() == name)
+		if (self.getActivePage() == name || self._overlays.read().iter().any(|x| x.name == name))
 		{
 			if let Some(mut page) = self._pageArray.get_mut(&name)
 			{
@@ -162,6 +402,25 @@ impl ManagerInterface
 		}
 	}
 
+	/// build a page from a json markup file (see UiMarkup) read with assetManager, and append it (replacing the old one)
+	pub fn UiPageLoad(&self, name: impl Into<String>, path: impl Into<String>) -> anyhow::Result<()>
+	{
+		let page = UiMarkup::loadFile(path)?;
+		self.UiPageAppend(name, page);
+		return Ok(());
+	}
+
+	/// register a function usable by name in markup events, must be done before UiPageLoad
+	pub fn UiHandlerAdd(&self, name: impl Into<String>, func: impl Fn(&mut dyn UiPageContent) -> bool + Send + Sync + 'static)
+	{
+		self._handlers.insert(name.into(), Arc::new(func));
+	}
+
+	pub fn UiHandlerGet(&self, name: &str) -> Option<UiMarkup_handler>
+	{
+		return self._handlers.get(name).map(|x| x.value().clone());
+	}
+
 	pub fn UiPageUpdate(&self, name: &str, func: impl Fn(&mut UiPage))
 	{
 		if let Some(mut page) = self._pageArray.get_mut(name)
@@ -185,24 +444,163 @@ impl ManagerInterface
 
 	///////////// PRIVATE //////////////////
 
+	// pages receiving inputs : overlays from the top down to the first modal one, then the active page if none is modal
+	fn inputPages(&self) -> Vec<String>
+	{
+		let mut pages = Vec::new();
+		for overlay in self._overlays.read().iter().rev()
+		{
+			pages.push(overlay.name.clone());
+			if (overlay.options.modal)
+			{
+				return pages;
+			}
+		}
+		pages.push(self.getActivePage());
+		return pages;
+	}
+
+	// send an input to the pages from the top, until one use it
+	fn inputFirst(&self, func: impl Fn(&mut UiPage) -> bool) -> bool
+	{
+		for name in self.inputPages()
+		{
+			if let Some(mut page) = self._pageArray.get_mut(&name)
+			{
+				if (func(page.value_mut()))
+				{
+					return true;
+				}
+			}
+		}
+		return false;
+	}
+
+	// active page and overlays
+	fn shownPages(&self) -> Vec<String>
+	{
+		let active = self.getActivePage();
+		let mut pages = vec![active.clone()];
+		pages.extend(self._overlays.read().iter().filter(|x| x.name != active).map(|x| x.name.clone()));
+		return pages;
+	}
+
+	// apply the transforms of the old and new page on all their contents
+	fn transitionTransforms(&self, oldpage: &str, newpage: &str, transforms: [HGE_shader_2Dsimple_transform; 2])
+	{
+		for (name, transform) in [oldpage, newpage].into_iter().zip(transforms)
+		{
+			let Some(uuids) = self._pageArray.get(name).map(|page| page.cache_uuids())
+			else
+			{
+				continue;
+			};
+			let lineUuids = uuids.clone();
+			let textUuids = uuids.clone();
+			ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
+				holder.transform_set(&uuids, transform);
+			});
+			ShaderDrawer_Manager::inspect::<HGE_shader_2Dline_holder>(move |holder| {
+				holder.transform_set(&lineUuids, transform);
+			});
+			ShaderDrawer_Manager::inspect::<HGE_shader_2Dtext_holder>(move |holder| {
+				holder.transform_set(&textUuids, transform);
+			});
+		}
+	}
+
+	fn transitionEnd(&self, oldpage: &str, newpage: &str)
+	{
+		let mut uuids = Vec::new();
+		if let Some(page) = self._pageArray.get(oldpage)
+		{
+			// the old page can be shown again as active page or overlay
+			if (self.getActivePage() != oldpage && !self._overlays.read().iter().any(|x| x.name == oldpage))
+			{
+				page.cache_remove();
+			}
+			uuids.extend(page.cache_uuids());
+		}
+		if let Some(page) = self._pageArray.get(newpage)
+		{
+			uuids.extend(page.cache_uuids());
+		}
+
+		let lineUuids = uuids.clone();
+		let textUuids = uuids.clone();
+		ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
+			holder.transform_remove(&uuids);
+		});
+		ShaderDrawer_Manager::inspect::<HGE_shader_2Dline_holder>(move |holder| {
+			holder.transform_remove(&lineUuids);
+		});
+		ShaderDrawer_Manager::inspect::<HGE_shader_2Dtext_holder>(move |holder| {
+			holder.transform_remove(&textUuids);
+		});
+		self._canChangePage.store(true, Ordering::Release);
+	}
+
+	fn overlayExit(name: String)
+	{
+		let _ = namedThread!(move || {
+			// still shown as the active page
+			if (Self::singleton().getActivePage() == name)
+			{
+				return;
+			}
+			if let Some(mut page) = Self::singleton()._pageArray.get_mut(&name)
+			{
+				page.focus_clear();
+				page.event_trigger(event_type::EXIT);
+				page.cache_remove();
+			}
+		});
+	}
+
+	fn toastsUpdate(&self)
+	{
+		let mut toasts = self._toasts.lock();
+		let count = toasts.len();
+		toasts.retain_mut(|toast| {
+			if (toast.isExpired())
+			{
+				toast.remove();
+				return false;
+			}
+			return true;
+		});
+
+		if (self._toastsDirty.swap(false, Ordering::AcqRel) || toasts.len() != count)
+		{
+			UiToast::placeAll(&mut toasts, &UiTheme::singleton().get(UITOAST_STYLE_CLASS));
+		}
+		for toast in toasts.iter_mut()
+		{
+			toast.submit();
+		}
+	}
+
 	fn EachTickUpdate(&self)
 	{
-		let Some(mut page) = self._pageArray.get_mut(self.getActivePage().as_str())
-		else
+		for name in self.shownPages()
 		{
-			return;
-		};
-		page.subevent_trigger(event_type::EACH_TICK);
-		page.cache_checkupdate();
+			if let Some(mut page) = self._pageArray.get_mut(&name)
+			{
+				page.subevent_trigger(event_type::EACH_TICK);
+				page.cache_checkupdate();
+			}
+		}
+		self.toastsUpdate();
 	}
 
 	fn EachSecondUpdate(&self)
 	{
-		let Some(page) = self._pageArray.get(self.getActivePage().as_str())
-		else
+		for name in self.shownPages()
 		{
-			return;
-		};
-		page.subevent_trigger(event_type::EACH_SECOND);
+			if let Some(page) = self._pageArray.get(&name)
+			{
+				page.subevent_trigger(event_type::EACH_SECOND);
+			}
+		}
 	}
 }
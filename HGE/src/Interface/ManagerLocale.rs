@@ -0,0 +1,266 @@
+use crate::assetStreamReader::assetManager;
+use crate::Interface::ManagerFont::ManagerFont;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use csv::ReaderBuilder;
+use dashmap::DashMap;
+use foldhash::{HashMap, HashMapExt};
+use std::io::Read;
+use std::sync::{Arc, OnceLock};
+use Htrace::HTraceError;
+
+/// font name used for the "user" font of a language registered with setFont
+const MANAGERLOCALE_FONT_PREFIX: &str = "user_";
+
+/// localized string tables, the active language can be changed at runtime
+/// Text bound with setLocale are refreshed on each language change
+pub struct ManagerLocale
+{
+	_tables: ArcSwap<HashMap<String, HashMap<String, String>>>,
+	_language: ArcSwap<String>,
+	_fallback: ArcSwap<String>,
+	_fonts: DashMap<String, String>,
+}
+
+static SINGLETON: OnceLock<ManagerLocale> = OnceLock::new();
+
+impl ManagerLocale
+{
+	fn new() -> ManagerLocale
+	{
+		return ManagerLocale {
+			_tables: ArcSwap::new(Arc::new(HashMap::new())),
+			_language: ArcSwap::new(Arc::new("en".to_string())),
+			_fallback: ArcSwap::new(Arc::new("en".to_string())),
+			_fonts: DashMap::new(),
+		};
+	}
+
+	pub fn singleton() -> &'static ManagerLocale
+	{
+		return SINGLETON.get_or_init(|| ManagerLocale::new());
+	}
+
+	/// add a csv string table read with assetManager, see loadCsv
+	pub fn loadFile(&self, path: impl Into<String>) -> anyhow::Result<()>
+	{
+		let path = path.into();
+		let Some(fileread) = assetManager::singleton().readFile(&path)
+		else
+		{
+			return Err(anyhow!("cannot load : {}", path));
+		};
+		return self.loadCsv(fileread).map_err(|err| anyhow!("{} : {}", path, err));
+	}
+
+	/// add a csv string table : header "key,en,fr,...", then one key per line, empty cells are ignored
+	/// plural forms are keys ending with ".zero", ".one", ".two", ".few", ".many" and ".other"
+	/// loaded keys replace the existing ones, on error nothing is loaded
+	pub fn loadCsv(&self, reader: impl Read) -> anyhow::Result<()>
+	{
+		let mut csv = ReaderBuilder::new().from_reader(reader);
+		let languages: Vec<String> = csv.headers()?.iter().skip(1).map(|x| x.trim().to_string()).collect();
+		if (languages.is_empty())
+		{
+			return Err(anyhow!("string table need a header \"key,language,...\""));
+		}
+
+		let mut loaded: Vec<(String, String, String)> = Vec::new();
+		for result in csv.records()
+		{
+			let cols = result?;
+			let Some(key) = cols.get(0).map(|x| x.trim()).filter(|x| !x.is_empty())
+			else
+			{
+				continue;
+			};
+			for (language, text) in languages.iter().zip(cols.iter().skip(1))
+			{
+				if (!text.is_empty())
+				{
+					loaded.push((language.clone(), key.to_string(), text.to_string()));
+				}
+			}
+		}
+
+		self._tables.rcu(|old| {
+			let mut tables = HashMap::clone(old);
+			for (language, key, text) in loaded.iter()
+			{
+				tables.entry(language.clone()).or_insert_with(HashMap::new).insert(key.clone(), text.clone());
+			}
+			tables
+		});
+		ManagerInterface::singleton().WindowRefreshed();
+		return Ok(());
+	}
+
+	/// change the active language, switch the "user" font if one is set for it, and refresh all pages
+	pub fn setLanguage(&self, language: impl Into<String>)
+	{
+		let language = language.into();
+		self._language.store(Arc::new(language.clone()));
+
+		// keep the "user" font of FontLoad to come back to it
+		if (!self._fonts.is_empty() && ManagerFont::singleton().FontIdGet(&Self::fontName("default")).is_none())
+		{
+			ManagerFont::singleton().alias(Self::fontName("default"), "user");
+		}
+		if let Some(path) = self._fonts.get(&language).map(|x| x.value().clone())
+		{
+			let fontname = Self::fontName(&language);
+			if (ManagerFont::singleton().FontIdGet(&fontname).is_none())
+			{
+				HTraceError!(ManagerFont::singleton().register(&fontname, path));
+			}
+			ManagerFont::singleton().alias("user", &fontname);
+		}
+		else if (!self._fonts.is_empty())
+		{
+			ManagerFont::singleton().alias("user", &Self::fontName("default"));
+		}
+		ManagerInterface::singleton().WindowRefreshed();
+	}
+
+	pub fn getLanguage(&self) -> String
+	{
+		return self._language.load().to_string();
+	}
+
+	/// language used when a key is missing in the active language ("en" by default)
+	pub fn setFallbackLanguage(&self, language: impl Into<String>)
+	{
+		self._fallback.store(Arc::new(language.into()));
+	}
+
+	/// languages having at least one string
+	pub fn languages(&self) -> Vec<String>
+	{
+		return self._tables.load().keys().cloned().collect();
+	}
+
+	/// font file (relative to static) used as "user" font when this language is active
+	pub fn setFont(&self, language: impl Into<String>, path: impl Into<String>)
+	{
+		self._fonts.insert(language.into(), path.into());
+	}
+
+	/// localized string, the key itself if missing in the active and fallback languages
+	pub fn get(&self, key: &str) -> String
+	{
+		return self.getWith(key, &[]);
+	}
+
+	/// localized string with "{name}" replaced by parameters
+	pub fn getWith(&self, key: &str, params: &[(&str, &str)]) -> String
+	{
+		let text = self.find(key).unwrap_or_else(|| key.to_string());
+		return Self::replaceParams(text, params);
+	}
+
+	/// localized plural form "key.<category>" (see pluralCategory) for count, fallback to "key.other" then "key"
+	/// "{count}" is replaced by count
+	pub fn getPlural(&self, key: &str, count: i64, params: &[(&str, &str)]) -> String
+	{
+		let category = Self::pluralCategory(&self.getLanguage(), count);
+		let text = self
+			.find(&format!("{}.{}", key, category))
+			.or_else(|| self.find(&format!("{}.other", key)))
+			.unwrap_or_else(|| self.find(key).unwrap_or_else(|| key.to_string()));
+		let count = count.to_string();
+		let mut params = params.to_vec();
+		params.push(("count", &count));
+		return Self::replaceParams(text, &params);
+	}
+
+	/// plural category of a count (CLDR names : zero, one, two, few, many, other) for a language like "fr" or "pt-BR"
+	pub fn pluralCategory(language: &str, count: i64) -> &'static str
+	{
+		let n = count.unsigned_abs();
+		let (n10, n100) = (n % 10, n % 100);
+		let language = language.split(['-', '_']).next().unwrap_or_default();
+		return match language
+		{
+			"ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" | "tr" => "other",
+			"fr" | "pt" =>
+			{
+				if (n <= 1)
+				{
+					"one"
+				}
+				else
+				{
+					"other"
+				}
+			}
+			"ru" | "uk" | "be" | "sr" | "hr" | "bs" => match (n10, n100)
+			{
+				(1, x) if x != 11 => "one",
+				(2..=4, x) if !(12..=14).contains(&x) => "few",
+				_ => "many",
+			},
+			"pl" => match (n, n10, n100)
+			{
+				(1, _, _) => "one",
+				(_, 2..=4, x) if !(12..=14).contains(&x) => "few",
+				_ => "many",
+			},
+			"cs" | "sk" => match n
+			{
+				1 => "one",
+				2..=4 => "few",
+				_ => "other",
+			},
+			"ar" => match (n, n100)
+			{
+				(0, _) => "zero",
+				(1, _) => "one",
+				(2, _) => "two",
+				(_, 3..=10) => "few",
+				(_, 11..=99) => "many",
+				_ => "other",
+			},
+			_ =>
+			{
+				if (n == 1)
+				{
+					"one"
+				}
+				else
+				{
+					"other"
+				}
+			}
+		};
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn fontName(language: &str) -> String
+	{
+		return format!("{}{}", MANAGERLOCALE_FONT_PREFIX, language);
+	}
+
+	fn find(&self, key: &str) -> Option<String>
+	{
+		let tables = self._tables.load();
+		for language in [self._language.load(), self._fallback.load()]
+		{
+			if let Some(text) = tables.get(language.as_str()).and_then(|x| x.get(key))
+			{
+				return Some(text.clone());
+			}
+		}
+		return None;
+	}
+
+	fn replaceParams(mut text: String, params: &[(&str, &str)]) -> String
+	{
+		for (name, value) in params
+		{
+			text = text.replace(&format!("{{{}}}", name), value);
+		}
+		return text;
+	}
+}
@@ -7,14 +7,20 @@ use crate::components::scale::scale;
 use crate::components::{Components, HGEC_offset, HGEC_origin};
 use crate::entities::utils::entities_utils;
 use crate::HGEMain::HGEMain;
+use crate::components::color::colorBlend;
 use crate::Interface::ManagerFont::ManagerFont;
+use crate::Interface::ManagerLocale::ManagerLocale;
+use crate::Interface::TextEffect::{TextEffect, TextEffect_glyph, TextEffect_gradient, TextEffect_outline, TextEffect_shadow};
+use crate::Interface::TextMarkup::{TextMarkup, TextMarkup_segment, TextMarkup_size};
 use crate::Interface::UiButton::UiButton_content;
 use crate::Interface::UiHidable::UiHidable_content;
 use crate::Interface::UiHitbox::{UiHitbox, UiHitbox_raw};
 use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
 use crate::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dsimple_def, HGE_shader_2Dsimple_holder};
+use crate::Shaders::HGE_shader_2Dtext::{HGE_shader_2Dtext_def, HGE_shader_2Dtext_holder};
 use crate::Shaders::ShaderDrawer::ShaderDrawer_Manager;
 use crate::Shaders::ShaderDrawerImpl::{ShaderDrawerImpl, ShaderDrawerImplReturn, ShaderDrawerImplStruct};
+use crate::Textures::Manager::ManagerTexture;
 use glyph_brush::{OwnedSection, OwnedText};
 use glyph_brush_layout::{BuiltInLineBreaker, Layout};
 use parking_lot::RwLock;
@@ -90,6 +96,12 @@ impl TextSize
 {
 	pub fn getInt(&self) -> f32
 	{
+		// fixed size do not depend on the window
+		if let TextSize::FIX(u) = self
+		{
+			return u.abs().round();
+		}
+
 		let dim = HGEMain::singleton().getWindowInfos();
 		let size;
 		if (dim.isWide)
@@ -126,6 +138,106 @@ pub struct TextCacheUpdater
 	pub(crate) vertex: Vec<HGE_shader_2Dsimple_def>,
 	pub(crate) indices: Vec<u32>,
 	pub(crate) isUpdated: bool,
+	/// vertices use the sdf texture, drawn by the text2D shader
+	pub(crate) isSdf: bool,
+	/// pixel per sdf texture pixel of each glyph quad, empty in bitmap mode
+	pub(crate) sdfScales: Vec<f32>,
+}
+
+// key of ManagerLocale resolved on each submit
+#[derive(Clone)]
+struct TextLocale
+{
+	key: String,
+	count: Option<i64>,
+	params: Vec<(String, String)>,
+}
+
+// everything needed to turn the glyphs of ManagerFont into the final vertices, kept to replay animations without a new layout
+struct TextSubmit
+{
+	cacheinfos: cacheInfos,
+	components: Components<interfacePosition>,
+	clip: [f32; 4],
+	icons: ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>,
+	effects: TextEffect,
+	sharedHitbox: Arc<RwLock<UiHitbox>>,
+	sharedStruct: Arc<RwLock<ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>>>,
+	sharedCacheInfos: Arc<RwLock<cacheInfos>>,
+}
+
+impl TextSubmit
+{
+	fn apply(&self, mut x: TextCacheUpdater)
+	{
+		x.isUpdated = true;
+		let behind = self.effects.applyGlyphs(&mut x);
+		let mut icons = self.icons.clone();
+		let first = x.vertex.len() as u32;
+		let firstIndice = x.indices.len();
+		x.vertex.append(&mut icons.vertex);
+		x.indices.extend(icons.indices.iter().map(|indice| indice + first));
+
+		let mut hitboxvec = Vec::new();
+		let tint = self.components.texture().color();
+
+		for (index, vertex) in x.vertex.iter_mut().enumerate()
+		{
+			let mut vertexCorrected = interfacePosition::new_pixel(vertex.position[0] as i32, vertex.position[1] as i32);
+			self.components.computeVertex(&mut vertexCorrected);
+			vertex.position = vertexCorrected.convertToVertex();
+			vertex.ispixel = vertexCorrected.getTypeInt();
+			vertex.color[0] = vertex.color[0] * tint.r;
+			vertex.color[1] = vertex.color[1] * tint.g;
+			vertex.color[2] = vertex.color[2] * tint.b;
+			vertex.color[3] = vertex.color[3] * tint.a;
+			vertex.clip = self.clip;
+
+			// outline and shadow are drawn behind but don't grow the hitbox
+			if let Some(depth) = behind.get(index)
+			{
+				vertex.position[2] += depth;
+				continue;
+			}
+			hitboxvec.push(UiHitbox_raw {
+				position: vertex.position,
+				ispixel: vertex.ispixel == 1,
+			});
+		}
+
+		let tmpstruct = ShaderDrawerImplStruct {
+			vertex: x.vertex.drain(0..).collect(),
+			indices: x.indices.drain(0..).collect(),
+		};
+
+		*self.sharedHitbox.write() = UiHitbox::newFrom2D(&hitboxvec);
+		*self.sharedStruct.write() = tmpstruct.clone();
+
+		if (self.sharedCacheInfos.read().isPresent())
+		{
+			// sdf glyphs are drawn by the text2D shader, icons stay in simple2D
+			let (simpleStruct, textStruct) = match x.isSdf
+			{
+				true =>
+				{
+					let (icons, mut glyphs) = Text::splitSdf(tmpstruct, first, firstIndice);
+					self.effects.applySdf(&mut glyphs.vertex, &x.sdfScales);
+					(icons, Some(glyphs))
+				}
+				false => (tmpstruct, None),
+			};
+			let cacheinfos = self.cacheinfos;
+			let isInserted = ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
+				holder.insert(cacheinfos, simpleStruct);
+			});
+			ShaderDrawer_Manager::inspect::<HGE_shader_2Dtext_holder>(move |holder| match textStruct
+			{
+				Some(textStruct) => holder.insert(cacheinfos, textStruct),
+				None => holder.remove(cacheinfos),
+			});
+			self.sharedCacheInfos.write().setNeedUpdate(isInserted);
+		}
+	}
 }
 
 pub struct Text
@@ -133,14 +245,21 @@ pub struct Text
 	_components: Components<interfacePosition>,
 	_layout: Layout<BuiltInLineBreaker>,
 	_texts: Vec<OwnedText>,
+	_markup: Vec<TextMarkup_segment>,
+	_locale: Option<TextLocale>,
 	_textSize: Option<TextSize>,
+	_fit: Option<[f32; 2]>,
+	_ellipsis: Option<f32>,
 	_managerfont_textId: u128,
 	_isVisible: bool,
+	_clip: Option<[f32; 4]>,
 	_events: event<Text>,
 	_cacheinfos: cacheInfos,
 	_sharedStruct: Arc<RwLock<ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>>>,
 	_sharedHitbox: Arc<RwLock<UiHitbox>>,
 	_sharedCacheInfos: Arc<RwLock<cacheInfos>>,
+	_effects: TextEffect,
+	_sharedLayout: Arc<RwLock<Option<(Arc<TextSubmit>, TextCacheUpdater)>>>,
 }
 
 impl Text
@@ -151,14 +270,21 @@ impl Text
 			_components: Components::default(),
 			_layout: Layout::default(),
 			_texts: vec![],
+			_markup: vec![],
+			_locale: None,
 			_textSize: None,
+			_fit: None,
+			_ellipsis: None,
 			_managerfont_textId: ManagerFont::singleton().getUniqId(),
 			_isVisible: false,
+			_clip: None,
 			_events: Self::newWithWinRefreshEvent(),
 			_cacheinfos: cacheInfos::default(),
 			_sharedStruct: Arc::new(Default::default()),
 			_sharedHitbox: Arc::new(RwLock::new(UiHitbox::new())),
 			_sharedCacheInfos: Arc::new(Default::default()),
+			_effects: TextEffect::new(),
+			_sharedLayout: Arc::new(RwLock::new(None)),
 		}
 	}
 
@@ -174,6 +300,31 @@ impl Text
 		&mut self._texts
 	}
 
+	/// replace the content by a markup text (see TextMarkup), drawn before texts added with addText
+	/// sizes are relative to setTextDynamicSize (NORMAL if not set), icon textures must be loaded before
+	pub fn setMarkup(&mut self, markup: &str) -> anyhow::Result<()>
+	{
+		self._markup = TextMarkup::parse(markup)?;
+		self._locale = None;
+		self._texts = vec![];
+		self._isVisible = true;
+		self._cacheinfos.setNeedUpdate(true);
+		return Ok(());
+	}
+
+	/// replace the content by the string of a ManagerLocale key, "{name}" replaced by params
+	/// the string can contain markup, it's resolved again on each submit (language change, window refresh)
+	pub fn setLocale(&mut self, key: impl Into<String>, params: &[(&str, &str)])
+	{
+		self.setLocaleInner(key.into(), None, params);
+	}
+
+	/// like setLocale with the plural form of the key for count, see ManagerLocale::getPlural
+	pub fn setLocalePlural(&mut self, key: impl Into<String>, count: i64, params: &[(&str, &str)])
+	{
+		self.setLocaleInner(key.into(), Some(count), params);
+	}
+
 	pub fn setTextDynamicSize(&mut self, size: TextSize)
 	{
 		self._textSize = Some(size);
@@ -183,6 +334,8 @@ impl Text
 	pub fn emptyText(&mut self)
 	{
 		self._texts = vec![];
+		self._markup = vec![];
+		self._locale = None;
 		self._isVisible = false;
 		ManagerFont::singleton().Text_remove(self._managerfont_textId);
 		self._cacheinfos.setNeedUpdate(true);
@@ -236,6 +389,261 @@ impl Text
 		return self._isVisible;
 	}
 
+	/// line around each glyph, None to remove
+	pub fn setOutline(&mut self, outline: Option<TextEffect_outline>)
+	{
+		self._effects.setOutline(outline);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// copy of the text drawn behind it, None to remove
+	pub fn setShadow(&mut self, shadow: Option<TextEffect_shadow>)
+	{
+		self._effects.setShadow(shadow);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// color multiplied from the top to the bottom of the text, None to remove
+	pub fn setGradient(&mut self, gradient: Option<TextEffect_gradient>)
+	{
+		self._effects.setGradient(gradient);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// called each tick with the glyph index (spaces are not counted) and the seconds since this call
+	/// see TextEffect::typewriter, TextEffect::wave, TextEffect::shake
+	pub fn setAnimation(&mut self, func: impl Fn(usize, f32) -> TextEffect_glyph + Send + Sync + 'static)
+	{
+		self._effects.setAnimation(Some(Arc::new(func)));
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// scale the text down (never up) to fit in width, height (pixel), None to remove
+	pub fn setShrinkToFit(&mut self, size: Option<[f32; 2]>)
+	{
+		self._fit = size;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// cut the end of the text with an ellipsis to be at most "maxWidth" pixel wide (single line), None to remove
+	pub fn setEllipsis(&mut self, maxWidth: Option<f32>)
+	{
+		self._ellipsis = maxWidth;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// size in pixel (width, height) the text will have once drawn, shrink to fit and ellipsis included
+	/// synchronous, unlike the hitbox that is updated by the ManagerFont thread
+	pub fn measure(&mut self) -> [f32; 2]
+	{
+		let (section, _) = self.sectionBuild();
+		return ManagerFont::singleton().measure(&section);
+	}
+
+	pub fn removeAnimation(&mut self)
+	{
+		self._effects.setAnimation(None);
+		*self._sharedLayout.write() = None;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// final section sent to ManagerFont (locale, markup, shrink to fit and ellipsis applied) and its markup icons
+	fn sectionBuild(&mut self) -> (OwnedSection<Extra>, Vec<(usize, String, f32)>)
+	{
+		self.localeResolve();
+		let mut tmp = OwnedSection::default();
+		let mut icons = Vec::new();
+		for x in self.markupTexts(&mut icons)
+		{
+			tmp = tmp.add_text(x);
+		}
+		for x in self._texts.iter()
+		{
+			let mut newtext = x.clone().with_extra(Extra {
+				color: x.extra.color,
+				z: x.extra.z,
+				textId: self._managerfont_textId,
+			});
+
+			if let Some(textsize) = &self._textSize
+			{
+				newtext = newtext.with_scale(textsize.getInt());
+			}
+
+			tmp = tmp.add_text(newtext);
+		}
+		tmp = tmp.with_layout(self._layout);
+		if let Some(size) = self._fit
+		{
+			let (fitted, factor) = ManagerFont::singleton().shrinkToFit(&tmp, size);
+			tmp = fitted;
+			icons.iter_mut().for_each(|(_, _, iconSize)| *iconSize *= factor);
+		}
+		if let Some(maxWidth) = self._ellipsis
+		{
+			tmp = ManagerFont::singleton().ellipsis(tmp, maxWidth);
+		}
+		return (tmp, icons);
+	}
+
+	// replay the last layout with the animation at the current time
+	fn animationTick(&self)
+	{
+		let Some((submit, layout)) = self._sharedLayout.read().clone()
+		else
+		{
+			return;
+		};
+		submit.apply(layout);
+	}
+
+	fn setLocaleInner(&mut self, key: String, count: Option<i64>, params: &[(&str, &str)])
+	{
+		self._locale = Some(TextLocale {
+			key,
+			count,
+			params: params.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+		});
+		self._markup = vec![];
+		self._texts = vec![];
+		self._isVisible = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	// resolve the locale key into markup, a string with invalid markup is drawn as is
+	fn localeResolve(&mut self)
+	{
+		let Some(locale) = &self._locale
+		else
+		{
+			return;
+		};
+		let params: Vec<(&str, &str)> = locale.params.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+		let resolved = match locale.count
+		{
+			Some(count) => ManagerLocale::singleton().getPlural(&locale.key, count, &params),
+			None => ManagerLocale::singleton().getWith(&locale.key, &params),
+		};
+		self._markup = TextMarkup::parse(&resolved).unwrap_or_else(|_| {
+			vec![TextMarkup_segment::TEXT {
+				text: resolved,
+				color: None,
+				font: None,
+				size: None,
+			}]
+		});
+	}
+
+	// markup segments with their final scale, icons are spaces reserving their width : (section index, texture, size)
+	fn markupTexts(&self, icons: &mut Vec<(usize, String, f32)>) -> Vec<OwnedText<Extra>>
+	{
+		let baseScale = self._textSize.clone().unwrap_or(TextSize::NORMAL).getInt();
+		let extra = Extra {
+			textId: self._managerfont_textId,
+			..Extra::default()
+		};
+
+		let mut texts = Vec::new();
+		for segment in self._markup.iter()
+		{
+			match segment
+			{
+				TextMarkup_segment::TEXT {
+					text,
+					color,
+					font,
+					size,
+				} =>
+				{
+					let scale = match size
+					{
+						Some(TextMarkup_size::FACTOR(factor)) => baseScale * factor,
+						Some(TextMarkup_size::PIXEL(pixel)) => *pixel,
+						None => baseScale,
+					};
+					let fontId = ManagerFont::singleton().FontIdGet(font.as_deref().unwrap_or("user")).unwrap_or_default();
+					texts.push(OwnedText::<Extra>::new(text.clone()).with_scale(scale).with_font_id(fontId).with_extra(Extra {
+						color: color.map(|x| x.toArray()).unwrap_or(extra.color),
+						..extra.clone()
+					}));
+				}
+				TextMarkup_segment::ICON(texture) =>
+				{
+					let space = ManagerFont::singleton().Text_advances(" ", baseScale, Default::default()).last().copied().unwrap_or(0.0);
+					let count = if (space > 0.0) { (baseScale / space).ceil() as usize } else { 1 };
+					icons.push((texts.len(), texture.clone(), baseScale));
+					texts.push(OwnedText::<Extra>::new(" ".repeat(count.max(1))).with_scale(baseScale).with_extra(extra.clone()));
+				}
+			}
+		}
+		return texts;
+	}
+
+	// quads of the icons, in section pixel, centered on their reserved space and on the line
+	fn markupIcons(section: &OwnedSection<Extra>, icons: Vec<(usize, String, f32)>) -> ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>
+	{
+		let mut returning = ShaderDrawerImplStruct::default();
+		if (icons.is_empty())
+		{
+			return returning;
+		}
+
+		let positions = ManagerFont::singleton().Text_firstGlyphs(section);
+		for (index, texture, size) in icons
+		{
+			let Some(([x, baseline], ascent, descent)) = positions.get(&index)
+			else
+			{
+				continue;
+			};
+			let Some((texturename, part)) = ManagerTexture::singleton().getPartFromName(&texture)
+			else
+			{
+				continue;
+			};
+			let reserved = ManagerFont::singleton().Text_advances(&section.text[index].text, size, Default::default()).last().copied().unwrap_or(size);
+			let left = x + (reserved - size) / 2.0;
+			let top = baseline - (ascent + descent) / 2.0 - size / 2.0;
+			let [[uvLeft, uvTop], [uvRight, uvBottom]] = part.uvcoord;
+
+			let first = returning.vertex.len() as u32;
+			for (posx, posy, u, v) in [(left, top, uvLeft, uvTop), (left + size, top, uvRight, uvTop), (left, top + size, uvLeft, uvBottom), (left + size, top + size, uvRight, uvBottom)]
+			{
+				returning.vertex.push(HGE_shader_2Dsimple_def {
+					position: [posx, posy, 0.0],
+					ispixel: 1,
+					texture: Some(texturename.clone()),
+					uvcoord: [u, v],
+					color: [1.0, 1.0, 1.0, 1.0],
+					color_blend_type: colorBlend::MUL.toU32(),
+					clip: [0.0; 4],
+				});
+			}
+			returning.indices.extend([0, 1, 2, 1, 3, 2].map(|x| first + x));
+		}
+		return returning;
+	}
+
+	// glyphs (first vertices) converted for the text2D shader, and the icons after them
+	fn splitSdf(
+		mut structure: ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>,
+		firstIcon: u32,
+		firstIconIndice: usize,
+	) -> (ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>, ShaderDrawerImplStruct<HGE_shader_2Dtext_def>)
+	{
+		let icons = ShaderDrawerImplStruct {
+			vertex: structure.vertex.split_off(firstIcon as usize),
+			indices: structure.indices.split_off(firstIconIndice).iter().map(|x| x - firstIcon).collect(),
+		};
+		let glyphs = ShaderDrawerImplStruct {
+			vertex: structure.vertex.iter().map(HGE_shader_2Dtext_def::from).collect(),
+			indices: structure.indices,
+		};
+		return (icons, glyphs);
+	}
+
 	fn newWithWinRefreshEvent() -> event<Text>
 	{
 		let mut tmp = event::new();
@@ -248,6 +656,10 @@ impl event_trait for Text
 {
 	fn event_trigger(&mut self, eventtype: event_type) -> bool
 	{
+		if (eventtype == event_type::EACH_TICK && self._effects.isAnimated())
+		{
+			self.animationTick();
+		}
 		let update = self._events.clone().trigger(eventtype, self);
 		if (self._cacheinfos.isPresent() && update)
 		{
@@ -258,7 +670,7 @@ impl event_trait for Text
 
 	fn event_have(&self, eventtype: event_type) -> bool
 	{
-		self._events.have(eventtype)
+		self._events.have(eventtype) || (eventtype == event_type::EACH_TICK && self._effects.isAnimated())
 	}
 }
 
@@ -280,14 +692,21 @@ impl Clone for Text
 			_components: self._components.clone(),
 			_layout: self._layout.clone(),
 			_texts: tmpvec,
+			_markup: self._markup.clone(),
+			_locale: self._locale.clone(),
 			_textSize: self._textSize.clone(),
+			_fit: self._fit,
+			_ellipsis: self._ellipsis,
 			_managerfont_textId: self._managerfont_textId,
 			_isVisible: self._isVisible,
+			_clip: self._clip,
 			_events: self._events.clone(),
 			_cacheinfos: self._cacheinfos.clone(),
 			_sharedStruct: self._sharedStruct.clone(),
 			_sharedHitbox: self._sharedHitbox.clone(),
 			_sharedCacheInfos: self._sharedCacheInfos.clone(),
+			_effects: self._effects.clone(),
+			_sharedLayout: self._sharedLayout.clone(),
 		};
 
 		return tmpfinal;
@@ -320,70 +739,29 @@ impl ShaderDrawerImpl for Text
 			return;
 		}
 
-		let mut tmp = OwnedSection::default();
-		for x in self._texts.iter()
-		{
-			let mut newtext = x.clone().with_extra(Extra {
-				color: x.extra.color,
-				z: x.extra.z,
-				textId: self._managerfont_textId,
-			});
-
-			if let Some(textsize) = &self._textSize
-			{
-				newtext = newtext.with_scale(textsize.getInt());
-			}
-
-			tmp = tmp.add_text(newtext);
-		}
-		tmp = tmp.with_layout(self._layout);
-
-		let tmpcacheinfos = self._cacheinfos;
-		let components = self._components.clone();
-		let shareHitbox = self._sharedHitbox.clone();
-		let shareStruct = self._sharedStruct.clone();
-		let sharedCacheInfos = self._sharedCacheInfos.clone();
+		let (tmp, icons) = self.sectionBuild();
+		let iconStruct = Self::markupIcons(&tmp, icons);
+
+		let submit = Arc::new(TextSubmit {
+			cacheinfos: self._cacheinfos,
+			components: self._components.clone(),
+			clip: self._clip.unwrap_or([0.0; 4]),
+			icons: iconStruct,
+			effects: self._effects.clone(),
+			sharedHitbox: self._sharedHitbox.clone(),
+			sharedStruct: self._sharedStruct.clone(),
+			sharedCacheInfos: self._sharedCacheInfos.clone(),
+		});
+		let sharedLayout = self._sharedLayout.clone();
 		self._sharedCacheInfos.write().setPresent();
 		ManagerFont::singleton().Text_add(
 			tmp.to_owned(),
-			move |mut x| {
-				x.isUpdated = true;
-
-				let mut hitboxvec = Vec::new();
-
-				for vertex in x.vertex.iter_mut()
+			move |x| {
+				if (submit.effects.isAnimated())
 				{
-					let mut vertexCorrected = interfacePosition::new_pixel(vertex.position[0] as i32, vertex.position[1] as i32);
-					components.computeVertex(&mut vertexCorrected);
-					vertex.position = vertexCorrected.convertToVertex();
-					vertex.ispixel = vertexCorrected.getTypeInt();
-					vertex.color[0] = vertex.color[0] * components.texture().color().r;
-					vertex.color[1] = vertex.color[1] * components.texture().color().g;
-					vertex.color[2] = vertex.color[2] * components.texture().color().b;
-					vertex.color[3] = vertex.color[3] * components.texture().color().a;
-
-					hitboxvec.push(UiHitbox_raw {
-						position: vertex.position,
-						ispixel: vertex.ispixel == 1,
-					});
-				}
-
-				let tmpstruct = ShaderDrawerImplStruct {
-					vertex: x.vertex.drain(0..).collect(),
-					indices: x.indices.drain(0..).collect(),
-				};
-
-				*shareHitbox.write() = UiHitbox::newFrom2D(&hitboxvec);
-				*shareStruct.write() = tmpstruct.clone();
-
-				if (sharedCacheInfos.read().isPresent())
-				{
-					sharedCacheInfos
-						.write()
-						.setNeedUpdate(ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
-							holder.insert(tmpcacheinfos, tmpstruct);
-						}));
+					*sharedLayout.write() = Some((submit.clone(), x.clone()));
 				}
+				submit.apply(x);
 			},
 			self._managerfont_textId,
 		);
@@ -397,10 +775,14 @@ impl ShaderDrawerImpl for Text
 		ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
 			holder.remove(tmp);
 		});
+		ShaderDrawer_Manager::inspect::<HGE_shader_2Dtext_holder>(move |holder| {
+			holder.remove(tmp);
+		});
 		self._cacheinfos.setAbsent();
 		self._sharedCacheInfos.write().setAbsent();
 		*self._sharedStruct.write() = ShaderDrawerImplStruct::default();
 		*self._sharedHitbox.write() = UiHitbox::new();
+		*self._sharedLayout.write() = None;
 	}
 }
 
@@ -429,6 +811,20 @@ impl UiPageContent for Text
 	{
 		self._sharedHitbox.read().clone()
 	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, _bottomRight: interfacePosition)
+	{
+		self.setPos(leftTop);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		if (self._clip != clip)
+		{
+			self._clip = clip;
+			self._cacheinfos.setNeedUpdate(true);
+		}
+	}
 }
 
 impl UiButton_content for Text {}
@@ -444,14 +840,21 @@ impl entities_utils for Text
 			_components: self._components.clone(),
 			_layout: self._layout.clone(),
 			_texts: tmpvec,
+			_markup: self._markup.clone(),
+			_locale: self._locale.clone(),
 			_textSize: self._textSize.clone(),
+			_fit: self._fit,
+			_ellipsis: self._ellipsis,
 			_managerfont_textId: ManagerFont::singleton().getUniqId(),
 			_isVisible: self._isVisible,
+			_clip: self._clip,
 			_events: Self::newWithWinRefreshEvent(),
 			_cacheinfos: cacheInfos::default(),
 			_sharedStruct: Arc::new(Default::default()),
 			_sharedHitbox: Arc::new(RwLock::new(UiHitbox::new())),
 			_sharedCacheInfos: Arc::new(Default::default()),
+			_effects: self._effects.clone(),
+			_sharedLayout: Arc::new(RwLock::new(None)),
 		};
 	}
 }
@@ -0,0 +1,285 @@
+use crate::components::color::color;
+use crate::Interface::ManagerFont::{ManagerFont, ManagerFont_renderMode};
+use crate::Interface::Text::TextCacheUpdater;
+use crate::Shaders::HGE_shader_2Dsimple::HGE_shader_2Dsimple_def;
+use crate::Shaders::HGE_shader_2Dtext::HGE_shader_2Dtext_def;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// line drawn around each glyph, see Text::setOutline
+#[derive(Clone, Copy, Debug)]
+pub struct TextEffect_outline
+{
+	pub color: color,
+	/// in pixel, limited by the spread in sdf mode
+	pub width: f32,
+}
+
+/// copy of the text drawn behind it, see Text::setShadow
+#[derive(Clone, Copy, Debug)]
+pub struct TextEffect_shadow
+{
+	pub color: color,
+	/// in pixel, positive go right and down
+	pub offset: [f32; 2],
+}
+
+/// color multiplied over the whole text, from its top to its bottom, see Text::setGradient
+#[derive(Clone, Copy, Debug)]
+pub struct TextEffect_gradient
+{
+	pub top: color,
+	pub bottom: color,
+}
+
+/// change of one glyph returned by an animation, see Text::setAnimation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextEffect_glyph
+{
+	/// in pixel
+	pub offset: [f32; 2],
+	/// multiplied with the glyph color
+	pub color: [f32; 4],
+	/// around the glyph center
+	pub scale: f32,
+}
+
+impl Default for TextEffect_glyph
+{
+	fn default() -> Self
+	{
+		return TextEffect_glyph {
+			offset: [0.0, 0.0],
+			color: [1.0, 1.0, 1.0, 1.0],
+			scale: 1.0,
+		};
+	}
+}
+
+/// effects of a Text, applied on its glyph quads after each layout (and each tick when animated)
+#[derive(Clone)]
+pub struct TextEffect
+{
+	_outline: Option<TextEffect_outline>,
+	_shadow: Option<TextEffect_shadow>,
+	_gradient: Option<TextEffect_gradient>,
+	_animation: Option<Arc<dyn Fn(usize, f32) -> TextEffect_glyph + Send + Sync>>,
+	_start: Instant,
+}
+
+// depth added to outline and shadow quads to stay behind the glyphs but in the same layer (one layer = 0.0001)
+const TEXTEFFECT_DEPTH_OUTLINE: f32 = 0.00001;
+const TEXTEFFECT_DEPTH_SHADOW: f32 = 0.00002;
+
+impl TextEffect
+{
+	pub fn new() -> Self
+	{
+		return TextEffect {
+			_outline: None,
+			_shadow: None,
+			_gradient: None,
+			_animation: None,
+			_start: Instant::now(),
+		};
+	}
+
+	/// glyphs appear one by one, fading in over one char duration
+	pub fn typewriter(charsPerSecond: f32) -> impl Fn(usize, f32) -> TextEffect_glyph + Send + Sync + 'static
+	{
+		return move |index, time| {
+			let alpha = (time * charsPerSecond - index as f32).clamp(0.0, 1.0);
+			TextEffect_glyph {
+				color: [1.0, 1.0, 1.0, alpha],
+				..TextEffect_glyph::default()
+			}
+		};
+	}
+
+	/// glyphs move up and down on a sine, "speed" in radian per second
+	pub fn wave(amplitude: f32, speed: f32) -> impl Fn(usize, f32) -> TextEffect_glyph + Send + Sync + 'static
+	{
+		return move |index, time| TextEffect_glyph {
+			offset: [0.0, (time * speed + index as f32 * 0.5).sin() * amplitude],
+			..TextEffect_glyph::default()
+		};
+	}
+
+	/// glyphs jump randomly (but always the same way for the same index and time) up to "amplitude" pixel
+	pub fn shake(amplitude: f32) -> impl Fn(usize, f32) -> TextEffect_glyph + Send + Sync + 'static
+	{
+		return move |index, time| {
+			let frame = (time * 30.0) as u32;
+			let seed = (index as u32).wrapping_mul(2) ^ frame.wrapping_shl(16);
+			TextEffect_glyph {
+				offset: [Self::noise(seed) * amplitude, Self::noise(seed + 1) * amplitude],
+				..TextEffect_glyph::default()
+			}
+		};
+	}
+
+	pub(crate) fn setOutline(&mut self, outline: Option<TextEffect_outline>)
+	{
+		self._outline = outline;
+	}
+
+	pub(crate) fn setShadow(&mut self, shadow: Option<TextEffect_shadow>)
+	{
+		self._shadow = shadow;
+	}
+
+	pub(crate) fn setGradient(&mut self, gradient: Option<TextEffect_gradient>)
+	{
+		self._gradient = gradient;
+	}
+
+	/// the animation time restart at 0
+	pub(crate) fn setAnimation(&mut self, animation: Option<Arc<dyn Fn(usize, f32) -> TextEffect_glyph + Send + Sync>>)
+	{
+		self._animation = animation;
+		self._start = Instant::now();
+	}
+
+	pub(crate) fn isAnimated(&self) -> bool
+	{
+		return self._animation.is_some();
+	}
+
+	/// apply animation and gradient on the glyph quads (4 vertices each), in bitmap mode outline and shadow quads are added before them
+	/// return the depth to add to each added vertex
+	pub(crate) fn applyGlyphs(&self, cache: &mut TextCacheUpdater) -> Vec<f32>
+	{
+		let quads = cache.vertex.len() / 4;
+
+		if let Some(animation) = &self._animation
+		{
+			let time = self._start.elapsed().as_secs_f32();
+			for (index, quad) in cache.vertex.chunks_mut(4).enumerate()
+			{
+				let glyph = animation(index, time);
+				let center = quad.iter().fold([0.0, 0.0], |acc, x| [acc[0] + x.position[0] / 4.0, acc[1] + x.position[1] / 4.0]);
+				for vertex in quad.iter_mut()
+				{
+					vertex.position[0] = center[0] + (vertex.position[0] - center[0]) * glyph.scale + glyph.offset[0];
+					vertex.position[1] = center[1] + (vertex.position[1] - center[1]) * glyph.scale + glyph.offset[1];
+					for channel in 0..4
+					{
+						vertex.color[channel] *= glyph.color[channel];
+					}
+				}
+				if let Some(sdfScale) = cache.sdfScales.get_mut(index)
+				{
+					*sdfScale *= glyph.scale;
+				}
+			}
+		}
+
+		if let Some(gradient) = &self._gradient
+		{
+			let top = cache.vertex.iter().map(|x| x.position[1]).fold(f32::MAX, f32::min);
+			let bottom = cache.vertex.iter().map(|x| x.position[1]).fold(f32::MIN, f32::max);
+			let height = (bottom - top).max(1.0);
+			for vertex in cache.vertex.iter_mut()
+			{
+				let tint = gradient.top.interval(gradient.bottom, (vertex.position[1] - top) / height).toArray();
+				for channel in 0..4
+				{
+					vertex.color[channel] *= tint[channel];
+				}
+			}
+		}
+
+		// sdf outline and shadow are computed by the text2D shader, see applySdf
+		if (cache.isSdf || (self._outline.is_none() && self._shadow.is_none()))
+		{
+			return vec![];
+		}
+
+		let mut behind = Vec::new();
+		let mut depths = Vec::new();
+		if let Some(shadow) = &self._shadow
+		{
+			behind.extend(Self::copyQuads(&cache.vertex, shadow.offset, shadow.color));
+			depths.resize(behind.len(), TEXTEFFECT_DEPTH_SHADOW);
+		}
+		if let Some(outline) = &self._outline
+		{
+			let diagonal = outline.width * std::f32::consts::FRAC_1_SQRT_2;
+			for offset in [
+				[outline.width, 0.0],
+				[-outline.width, 0.0],
+				[0.0, outline.width],
+				[0.0, -outline.width],
+				[diagonal, diagonal],
+				[-diagonal, diagonal],
+				[diagonal, -diagonal],
+				[-diagonal, -diagonal],
+			]
+			{
+				behind.extend(Self::copyQuads(&cache.vertex, offset, outline.color));
+			}
+			depths.resize(behind.len(), TEXTEFFECT_DEPTH_OUTLINE);
+		}
+
+		behind.append(&mut cache.vertex);
+		cache.vertex = behind;
+		let quads = quads + depths.len() / 4;
+		cache.indices = (0..quads as u32).flat_map(|quad| [0, 1, 2, 1, 3, 2].map(|x| quad * 4 + x)).collect();
+		return depths;
+	}
+
+	/// outline and shadow of sdf glyphs, "sdfScales" being the pixel per sdf texture pixel of each quad
+	pub(crate) fn applySdf(&self, glyphs: &mut [HGE_shader_2Dtext_def], sdfScales: &[f32])
+	{
+		let ManagerFont_renderMode::SDF(config) = ManagerFont::singleton().getRenderMode()
+		else
+		{
+			return;
+		};
+
+		for (quad, sdfScale) in glyphs.chunks_mut(4).zip(sdfScales.iter())
+		{
+			for vertex in quad.iter_mut()
+			{
+				if let Some(outline) = &self._outline
+				{
+					vertex.outline_color = outline.color.toArray();
+					vertex.outline_color[3] *= vertex.color[3];
+					vertex.outline_width = (outline.width / (sdfScale * 2.0 * config.spread)).min(0.5);
+				}
+				if let Some(shadow) = &self._shadow
+				{
+					vertex.shadow_color = shadow.color.toArray();
+					vertex.shadow_color[3] *= vertex.color[3];
+					vertex.shadow_offset = [shadow.offset[0] / sdfScale, shadow.offset[1] / sdfScale];
+				}
+			}
+		}
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn copyQuads(vertex: &[HGE_shader_2Dsimple_def], offset: [f32; 2], tint: color) -> Vec<HGE_shader_2Dsimple_def>
+	{
+		return vertex
+			.iter()
+			.map(|x| {
+				let mut copy = x.clone();
+				copy.position[0] += offset[0];
+				copy.position[1] += offset[1];
+				copy.color = [tint.r, tint.g, tint.b, tint.a * x.color[3]];
+				copy
+			})
+			.collect();
+	}
+
+	// deterministic value between -1 and 1
+	fn noise(seed: u32) -> f32
+	{
+		let mut x = seed.wrapping_mul(0x9E3779B1);
+		x ^= x >> 15;
+		x = x.wrapping_mul(0x85EBCA77);
+		x ^= x >> 13;
+		return (x & 0xFFFF) as f32 / 65535.0 * 2.0 - 1.0;
+	}
+}
@@ -0,0 +1,156 @@
+use crate::components::color::color;
+use crate::Interface::UiTheme::UiTheme;
+use anyhow::anyhow;
+use Hconfig::serde_json::Value as JsonValue;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextMarkup_size
+{
+	/// multiply the text size
+	FACTOR(f32),
+	/// fixed size in pixel
+	PIXEL(f32),
+}
+
+#[derive(Clone, Debug)]
+pub enum TextMarkup_segment
+{
+	/// text with its style, None keep the style of the Text
+	TEXT
+	{
+		text: String,
+		color: Option<color>,
+		font: Option<String>,
+		size: Option<TextMarkup_size>,
+	},
+	/// texture name, or "texture#part", drawn as a square of the text size
+	ICON(String),
+}
+
+/// parser of inline markup for Text::setMarkup
+///
+/// [b]bold[/b], [i]italic[/i], [font=name]..[/font], [color=#rrggbb(aa)]..[/color],
+/// [size=1.5]..[/size] (factor) or [size=24px]..[/size], [icon=texture#part], "[[" for a literal "["
+/// "b" and "i" use the fonts registered as "bold" and "italic" in ManagerFont
+pub struct TextMarkup {}
+
+#[derive(Clone, Default)]
+struct TextMarkup_style
+{
+	tag: String,
+	color: Option<color>,
+	font: Option<String>,
+	size: Option<TextMarkup_size>,
+}
+
+impl TextMarkup
+{
+	pub fn parse(markup: &str) -> anyhow::Result<Vec<TextMarkup_segment>>
+	{
+		let mut segments = Vec::new();
+		let mut stack = vec![TextMarkup_style::default()];
+		let mut text = String::new();
+		let mut rest = markup;
+
+		while let Some(start) = rest.find('[')
+		{
+			text.push_str(&rest[..start]);
+			rest = &rest[start + 1..];
+			if let Some(escaped) = rest.strip_prefix('[')
+			{
+				text.push('[');
+				rest = escaped;
+				continue;
+			}
+			let Some(end) = rest.find(']')
+			else
+			{
+				return Err(anyhow!("markup tag not closed : \"[{}\"", rest));
+			};
+			let tag = &rest[..end];
+			rest = &rest[end + 1..];
+
+			Self::flush(&mut segments, &mut text, stack.last().unwrap());
+			if let Some(closing) = tag.strip_prefix('/')
+			{
+				if (stack.len() == 1 || stack.last().unwrap().tag != closing)
+				{
+					return Err(anyhow!("markup closing tag \"[/{}]\" without opening", closing));
+				}
+				stack.pop();
+				continue;
+			}
+
+			let (name, value) = match tag.split_once('=')
+			{
+				Some((name, value)) => (name, Some(value)),
+				None => (tag, None),
+			};
+			if (name == "icon")
+			{
+				let icon = value.filter(|x| !x.is_empty()).ok_or(anyhow!("markup icon need a texture : [icon=texture#part]"))?;
+				segments.push(TextMarkup_segment::ICON(icon.to_string()));
+				continue;
+			}
+
+			let mut style = stack.last().unwrap().clone();
+			style.tag = name.to_string();
+			match (name, value)
+			{
+				("b", None) => style.font = Some("bold".to_string()),
+				("i", None) => style.font = Some("italic".to_string()),
+				("font", Some(font)) => style.font = Some(font.to_string()),
+				("color", Some(value)) => style.color = Some(UiTheme::parseColor(&JsonValue::String(value.to_string())).map_err(|err| anyhow!("markup [color={}] : {}", value, err))?),
+				("size", Some(value)) => style.size = Some(Self::parseSize(value)?),
+				_ => return Err(anyhow!("markup unknown tag \"[{}]\"", tag)),
+			}
+			stack.push(style);
+		}
+
+		text.push_str(rest);
+		Self::flush(&mut segments, &mut text, stack.last().unwrap());
+		return Ok(segments);
+	}
+
+	/// text without tags and icons
+	pub fn plainText(segments: &[TextMarkup_segment]) -> String
+	{
+		return segments
+			.iter()
+			.filter_map(|x| match x
+			{
+				TextMarkup_segment::TEXT {
+					text,
+					..
+				} => Some(text.as_str()),
+				TextMarkup_segment::ICON(_) => None,
+			})
+			.collect();
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn flush(segments: &mut Vec<TextMarkup_segment>, text: &mut String, style: &TextMarkup_style)
+	{
+		if (text.is_empty())
+		{
+			return;
+		}
+		segments.push(TextMarkup_segment::TEXT {
+			text: std::mem::take(text),
+			color: style.color,
+			font: style.font.clone(),
+			size: style.size,
+		});
+	}
+
+	fn parseSize(value: &str) -> anyhow::Result<TextMarkup_size>
+	{
+		let size = match value.strip_suffix("px")
+		{
+			Some(pixel) => pixel.parse::<f32>().map(TextMarkup_size::PIXEL),
+			None => value.parse::<f32>().map(TextMarkup_size::FACTOR),
+		};
+		return size.map_err(|_| anyhow!("markup [size={}] must be a factor (1.5) or a size in pixel (24px)", value));
+	}
+}
@@ -1,12 +1,17 @@
 use crate::components::cacheInfos::cacheInfos;
 use crate::components::event::{event_trait, event_type};
 use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
 use crate::Interface::UiHitbox::UiHitbox;
 use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
+use crate::Interface::UiPanel9Slice::UiPanel9Slice;
+use crate::Interface::UiStyle::{UiStyle, UiStyle_themed};
 use crate::Shaders::HGE_shader_2Dsimple::HGE_shader_2Dsimple_def;
 use crate::Shaders::ShaderDrawerImpl::{ShaderDrawerImpl, ShaderDrawerImplReturn};
 use parking_lot::RwLock;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub trait UiButton_content: UiPageContent + ShaderDrawerImplReturn<HGE_shader_2Dsimple_def> {}
 dyn_clone::clone_trait_object!(UiButton_content);
@@ -26,6 +31,12 @@ pub struct UiButton
 	_content: Vec<Box<dyn UiButton_content + Send + Sync>>,
 	_pressedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiButton) + Send + Sync>>>>,
 	_state: UiButtonState,
+	_focused: bool,
+	_style: Option<UiStyle_themed>,
+	_z: u16,
+	_focusPlane: Plane<interfacePosition>,
+	_backgroundPlane: Plane<interfacePosition>,
+	_backgroundPanel: UiPanel9Slice,
 	_hide: bool,
 	_cacheinfos: cacheInfos,
 }
@@ -39,6 +50,12 @@ impl UiButton
 			_content: Vec::new(),
 			_pressedFn: Arc::new(RwLock::new(None)),
 			_state: UiButtonState::IDLE,
+			_focused: false,
+			_style: None,
+			_z: 0,
+			_focusPlane: Plane::new(),
+			_backgroundPlane: Plane::new(),
+			_backgroundPanel: UiPanel9Slice::new(interfacePosition::default(), interfacePosition::default()),
 			_hide: false,
 			_cacheinfos: cacheInfos::default(),
 		};
@@ -56,11 +73,42 @@ impl UiButton
 		*self._pressedFn.write() = Some(Box::new(func));
 	}
 
+	/// draw a background behind the contents (on their hitbox) with the idle/hover/pressed colors, and a focus outline
+	/// the style texture is drawn as a 9-slice tinted by the background color
+	/// the outline is at the z given by the layout (0 otherwise), the background one above : contents must be higher
+	pub fn setStyle(&mut self, style: UiStyle)
+	{
+		let mut themed = UiStyle_themed::new();
+		themed.set(style);
+		self._style = Some(themed);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// same as setStyle but follow a UiTheme class, the style is reloaded when the theme change
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		let mut themed = UiStyle_themed::new();
+		themed.setClass(class);
+		self._style = Some(themed);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getStyle(&self) -> Option<&UiStyle>
+	{
+		return self._style.as_deref();
+	}
+
 	pub fn getState(&self) -> UiButtonState
 	{
 		return self._state;
 	}
 
+	/// true if the button have the keyboard/gamepad focus
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
 	pub fn boxed(self) -> Box<UiButton>
 	{
 		return Box::new(self);
@@ -98,6 +146,55 @@ impl UiButton
 	{
 		self._content.iter().any(|x| x.cache_infos().isNotShow())
 	}
+
+	// styled background and focus outline around the hitbox, removed without style
+	fn backgroundSubmit(&mut self)
+	{
+		let Some(style) = self._style.as_deref().filter(|_| !self._hitbox.isEmpty())
+		else
+		{
+			self.backgroundRemove();
+			return;
+		};
+
+		let [[left, top], [right, bottom]] = self._hitbox.getRaw();
+		let border = style.border;
+		let background = style.backgroundFor(self._state);
+		UiStyle::square(&mut self._focusPlane, [left - border, top - border, right + border, bottom + border], self._z, style.focus);
+		if (style.texture.is_some())
+		{
+			self._backgroundPanel.setStyle(style);
+			self._backgroundPanel.setColor(background);
+			self._backgroundPanel.setSquare(
+				interfacePosition::new_pixel_z(left.round() as i32, top.round() as i32, self._z + 1),
+				interfacePosition::new_pixel_z(right.round() as i32, bottom.round() as i32, self._z + 1),
+			);
+			self._backgroundPlane.cache_remove();
+			self._backgroundPanel.cache_submit();
+		}
+		else
+		{
+			UiStyle::square(&mut self._backgroundPlane, [left, top, right, bottom], self._z + 1, background);
+			self._backgroundPanel.cache_remove();
+			self._backgroundPlane.cache_submit();
+		}
+
+		if (self._focused)
+		{
+			self._focusPlane.cache_submit();
+		}
+		else
+		{
+			self._focusPlane.cache_remove();
+		}
+	}
+
+	fn backgroundRemove(&mut self)
+	{
+		self._focusPlane.cache_remove();
+		self._backgroundPlane.cache_remove();
+		self._backgroundPanel.cache_remove();
+	}
 }
 
 impl event_trait for UiButton
@@ -116,6 +213,26 @@ impl event_trait for UiButton
 					});
 					self.setCacheToIdle();
 					returning = true;
+					// focused visual come back when the mouse leave
+					if (self._focused)
+					{
+						self._content.iter_mut().filter(|x| x.event_have(event_type::FOCUS)).for_each(|item| {
+							item.event_trigger(event_type::FOCUS);
+						});
+					}
+				}
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					self._content.iter_mut().filter(|x| x.event_have(eventtype)).for_each(|item| {
+						item.event_trigger(eventtype);
+					});
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
 				}
 			}
 			event_type::HOVER =>
@@ -157,6 +274,15 @@ impl event_trait for UiButton
 					}
 				}
 
+				if (eventtype == event_type::WINREFRESH)
+				{
+					if let Some(style) = self._style.as_mut()
+					{
+						style.refresh();
+						update = true;
+					}
+				}
+
 				if (update)
 				{
 					returning = true;
@@ -184,6 +310,8 @@ impl event_trait for UiButton
 			event_type::HOVER => true,
 			event_type::CLICKED => true,
 			event_type::WINREFRESH => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
 			_ => false,
 		}
 	}
@@ -269,6 +397,7 @@ impl ShaderDrawerImpl for UiButton
 			}
 			self._hitbox = newHitbox;
 		}
+		self.backgroundSubmit();
 
 		if (!haveOneNotCommit)
 		{
@@ -290,6 +419,7 @@ impl ShaderDrawerImpl for UiButton
 		{
 			x.cache_remove();
 		}
+		self.backgroundRemove();
 		self._cacheinfos.setAbsent();
 	}
 }
@@ -305,4 +435,43 @@ impl UiPageContent for UiButton
 	{
 		self._hitbox.clone()
 	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		for x in self._content.iter_mut()
+		{
+			x.layout_place(leftTop.clone(), bottomRight.clone());
+		}
+		self._z = leftTop.getZ();
+		self._hitbox = UiHitbox::new();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		for x in self._content.iter_mut()
+		{
+			x.layout_clip(clip);
+		}
+		self._focusPlane.layout_clip(clip);
+		self._backgroundPlane.layout_clip(clip);
+		self._backgroundPanel.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		let mut uuids: Vec<Uuid> = self._content.iter().flat_map(|x| x.cache_uuids()).collect();
+		uuids.extend([
+			self._focusPlane.cache_infos().getUuid(),
+			self._backgroundPlane.cache_infos().getUuid(),
+			self._backgroundPanel.cache_infos().getUuid(),
+		]);
+		return uuids;
+	}
 }
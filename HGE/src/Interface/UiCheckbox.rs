@@ -0,0 +1,416 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::Text::Text;
+use crate::Interface::UiButton::UiButtonState;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
+use crate::Interface::UiStyle::{UiStyle, UiStyle_themed};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UiCheckbox_type
+{
+	/// square box with a mark
+	CHECKBOX,
+	/// switch with a knob moving left/right
+	TOGGLE,
+}
+
+/// checkbox or toggle with a label on its right
+#[derive(Clone)]
+pub struct UiCheckbox
+{
+	_type: UiCheckbox_type,
+	_position: [interfacePosition; 2],
+	_label: String,
+	_checked: bool,
+	_state: UiButtonState,
+	_focused: bool,
+	_style: UiStyle_themed,
+	_focusPlane: Plane<interfacePosition>,
+	_boxPlane: Plane<interfacePosition>,
+	_markPlane: Plane<interfacePosition>,
+	_haveMark: bool,
+	_text: Text,
+	_changedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiCheckbox) + Send + Sync>>>>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_events: event<UiCheckbox>,
+}
+
+impl UiCheckbox
+{
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition, label: impl Into<String>) -> Self
+	{
+		return UiCheckbox {
+			_type: UiCheckbox_type::CHECKBOX,
+			_position: [leftTop, bottomRight],
+			_label: label.into(),
+			_checked: false,
+			_state: UiButtonState::IDLE,
+			_focused: false,
+			_style: UiStyle_themed::new(),
+			_focusPlane: Plane::new(),
+			_boxPlane: Plane::new(),
+			_markPlane: Plane::new(),
+			_haveMark: false,
+			_text: Text::new(),
+			_changedFn: Arc::new(RwLock::new(None)),
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_events: event::new(),
+		};
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setType(&mut self, checkboxtype: UiCheckbox_type)
+	{
+		self._type = checkboxtype;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setLabel(&mut self, label: impl Into<String>)
+	{
+		self._label = label.into();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// fixed style, stop following a UiTheme class
+	pub fn setStyle(&mut self, style: UiStyle)
+	{
+		self._style.set(style);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getStyle(&self) -> &UiStyle
+	{
+		return &self._style;
+	}
+
+	/// follow a UiTheme class, the style is reloaded when the theme change
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		self._style.setClass(class);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// change the value without calling the changed function
+	pub fn setChecked(&mut self, checked: bool)
+	{
+		self._checked = checked;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn isChecked(&self) -> bool
+	{
+		return self._checked;
+	}
+
+	/// called when the user change the value
+	pub fn setChangedFn(&mut self, func: impl FnMut(&mut UiCheckbox) + Send + Sync + 'static)
+	{
+		*self._changedFn.write() = Some(Box::new(func));
+	}
+
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
+	pub fn boxed(self) -> Box<UiCheckbox>
+	{
+		return Box::new(self);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn callChanged(&mut self)
+	{
+		let selfbinding = self._changedFn.clone();
+		let mut binding = selfbinding.write();
+		if let Some(func) = binding.as_mut()
+		{
+			func(self);
+		}
+	}
+
+	fn refreshDisplay(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+		let height = bottomRight[1] - leftTop[1];
+		let padding = self._style.padding;
+		let border = self._style.border;
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+
+		let boxWidth = match self._type
+		{
+			UiCheckbox_type::CHECKBOX => height,
+			UiCheckbox_type::TOGGLE => height * 2.0,
+		};
+		let boxRect = [leftTop[0], leftTop[1], leftTop[0] + boxWidth, bottomRight[1]];
+		UiStyle::square(&mut self._focusPlane, [boxRect[0] - border, boxRect[1] - border, boxRect[2] + border, boxRect[3] + border], z, self._style.focus);
+
+		let mut boxColor = self._style.backgroundFor(self._state);
+		if (self._type == UiCheckbox_type::TOGGLE && self._checked)
+		{
+			boxColor = self._style.foreground;
+		}
+		UiStyle::square(&mut self._boxPlane, boxRect, z + 1, boxColor);
+
+		self._haveMark = true;
+		match self._type
+		{
+			UiCheckbox_type::CHECKBOX =>
+			{
+				self._haveMark = self._checked;
+				UiStyle::square(
+					&mut self._markPlane,
+					[boxRect[0] + padding, boxRect[1] + padding, boxRect[2] - padding, boxRect[3] - padding],
+					z + 2,
+					self._style.foreground,
+				);
+			}
+			UiCheckbox_type::TOGGLE =>
+			{
+				let knob = height - padding * 2.0;
+				let mut knobLeft = boxRect[0] + padding;
+				if (self._checked)
+				{
+					knobLeft = boxRect[2] - padding - knob;
+				}
+				UiStyle::square(&mut self._markPlane, [knobLeft, boxRect[1] + padding, knobLeft + knob, boxRect[3] - padding], z + 2, self._style.text);
+			}
+		}
+
+		let label = self._label.clone();
+		self._style.label(&mut self._text, &label, boxRect[2] + padding * 2.0, leftTop[1], bottomRight[1], z + 1);
+	}
+}
+
+impl event_trait for UiCheckbox
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returning = self._events.clone().trigger(eventtype, self);
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER =>
+			{
+				let newstate = match eventtype
+				{
+					event_type::HOVER => UiButtonState::HOVER,
+					_ => UiButtonState::IDLE,
+				};
+				if (self._state != newstate)
+				{
+					self._state = newstate;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::CLICKED =>
+			{
+				if (self._state != UiButtonState::PRESSED)
+				{
+					self._state = UiButtonState::PRESSED;
+					self._checked = !self._checked;
+					self.callChanged();
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self._style.refresh();
+				self._text.event_trigger(eventtype);
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			_ =>
+			{}
+		}
+
+		if (self._cacheinfos.isPresent() && returning)
+		{
+			self.cache_submit();
+		}
+		return returning;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::IDLE => true,
+			event_type::HOVER => true,
+			event_type::CLICKED => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
+			event_type::WINREFRESH => true,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiCheckbox> for UiCheckbox
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiCheckbox) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl hideable for UiCheckbox
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiCheckbox
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self._text.cache_mustUpdate()
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		self.refreshDisplay();
+		if (self._focused)
+		{
+			self._focusPlane.cache_submit();
+		}
+		else
+		{
+			self._focusPlane.cache_remove();
+		}
+		self._boxPlane.cache_submit();
+		if (self._haveMark)
+		{
+			self._markPlane.cache_submit();
+		}
+		else
+		{
+			self._markPlane.cache_remove();
+		}
+		self._text.cache_submit();
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._focusPlane.cache_remove();
+		self._boxPlane.cache_remove();
+		self._markPlane.cache_remove();
+		self._text.cache_remove();
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiCheckbox
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		self._focusPlane.layout_clip(clip);
+		self._boxPlane.layout_clip(clip);
+		self._markPlane.layout_clip(clip);
+		self._text.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return vec![
+			self._focusPlane.cache_infos().getUuid(),
+			self._boxPlane.cache_infos().getUuid(),
+			self._markPlane.cache_infos().getUuid(),
+			self._text.cache_infos().getUuid(),
+		];
+	}
+}
@@ -0,0 +1,526 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::Text::Text;
+use crate::Interface::UiButton::UiButtonState;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
+use crate::Interface::UiStyle::{UiStyle, UiStyle_themed};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// z added to the popup, to be drawn over the next contents
+const POPUP_Z: u16 = 10;
+
+/// select field showing the selected option, opening a popup list below it
+#[derive(Clone)]
+pub struct UiDropdown
+{
+	_position: [interfacePosition; 2],
+	_options: Vec<String>,
+	_selected: Option<usize>,
+	_placeholder: String,
+	_open: bool,
+	_hovered: Option<usize>,
+	_state: UiButtonState,
+	_focused: bool,
+	_style: UiStyle_themed,
+	_focusPlane: Plane<interfacePosition>,
+	_fieldPlane: Plane<interfacePosition>,
+	_arrowPlane: Plane<interfacePosition>,
+	_text: Text,
+	_popupPlane: Plane<interfacePosition>,
+	_hoverPlane: Plane<interfacePosition>,
+	_popupTexts: Vec<Text>,
+	_changedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiDropdown) + Send + Sync>>>>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_events: event<UiDropdown>,
+}
+
+impl UiDropdown
+{
+	/// square is the closed field, each option of the popup have the same height
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition, options: Vec<String>) -> Self
+	{
+		let mut tmp = UiDropdown {
+			_position: [leftTop, bottomRight],
+			_options: Vec::new(),
+			_selected: None,
+			_placeholder: String::new(),
+			_open: false,
+			_hovered: None,
+			_state: UiButtonState::IDLE,
+			_focused: false,
+			_style: UiStyle_themed::new(),
+			_focusPlane: Plane::new(),
+			_fieldPlane: Plane::new(),
+			_arrowPlane: Plane::new(),
+			_text: Text::new(),
+			_popupPlane: Plane::new(),
+			_hoverPlane: Plane::new(),
+			_popupTexts: Vec::new(),
+			_changedFn: Arc::new(RwLock::new(None)),
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_events: event::new(),
+		};
+		tmp.setOptions(options);
+		return tmp;
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// replace all options, the selection is kept if still in range
+	pub fn setOptions(&mut self, options: Vec<String>)
+	{
+		self._popupTexts.iter_mut().for_each(|x| x.cache_remove());
+		self._popupTexts = options.iter().map(|_| Text::new()).collect();
+		self._options = options;
+		self._selected = self._selected.filter(|x| *x < self._options.len());
+		self._hovered = None;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// text shown when nothing is selected
+	pub fn setPlaceholder(&mut self, placeholder: impl Into<String>)
+	{
+		self._placeholder = placeholder.into();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// fixed style, stop following a UiTheme class
+	pub fn setStyle(&mut self, style: UiStyle)
+	{
+		self._style.set(style);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getStyle(&self) -> &UiStyle
+	{
+		return &self._style;
+	}
+
+	/// follow a UiTheme class, the style is reloaded when the theme change
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		self._style.setClass(class);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// change the selection without calling the changed function
+	pub fn setSelected(&mut self, index: Option<usize>)
+	{
+		self._selected = index.filter(|x| *x < self._options.len());
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getSelected(&self) -> Option<usize>
+	{
+		return self._selected;
+	}
+
+	pub fn getSelectedLabel(&self) -> Option<&String>
+	{
+		return self._selected.map(|x| &self._options[x]);
+	}
+
+	/// called when the user change the selection
+	pub fn setChangedFn(&mut self, func: impl FnMut(&mut UiDropdown) + Send + Sync + 'static)
+	{
+		*self._changedFn.write() = Some(Box::new(func));
+	}
+
+	pub fn isOpen(&self) -> bool
+	{
+		return self._open;
+	}
+
+	pub fn close(&mut self)
+	{
+		self._open = false;
+		self._hovered = None;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
+	pub fn boxed(self) -> Box<UiDropdown>
+	{
+		return Box::new(self);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn callChanged(&mut self)
+	{
+		let selfbinding = self._changedFn.clone();
+		let mut binding = selfbinding.write();
+		if let Some(func) = binding.as_mut()
+		{
+			func(self);
+		}
+	}
+
+	fn select(&mut self, index: usize)
+	{
+		if (self._selected != Some(index))
+		{
+			self._selected = Some(index);
+			self.callChanged();
+		}
+	}
+
+	// None if the mouse is on the field, Some(None) outside, Some(Some(i)) on a popup option
+	fn mouseTarget(&self) -> Option<Option<usize>>
+	{
+		let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let (x, y) = (mousex as f32, mousey as f32);
+		if (x < leftTop[0] || x > bottomRight[0] || y < leftTop[1])
+		{
+			return Some(None);
+		}
+		if (y <= bottomRight[1])
+		{
+			return None;
+		}
+		if (!self._open)
+		{
+			return Some(None);
+		}
+
+		let rowHeight = (bottomRight[1] - leftTop[1]).max(1.0);
+		let index = ((y - bottomRight[1]) / rowHeight).floor() as usize;
+		if (index >= self._options.len())
+		{
+			return Some(None);
+		}
+		return Some(Some(index));
+	}
+
+	fn refreshDisplay(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+		let height = bottomRight[1] - leftTop[1];
+		let padding = self._style.padding;
+		let border = self._style.border;
+
+		let popupBottom = bottomRight[1] + height * self._options.len() as f32;
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+		if (self._open)
+		{
+			self._hitbox.updateFromPoint(bottomRight[0], popupBottom);
+		}
+
+		UiStyle::square(
+			&mut self._focusPlane,
+			[leftTop[0] - border, leftTop[1] - border, bottomRight[0] + border, bottomRight[1] + border],
+			z,
+			self._style.focus,
+		);
+		UiStyle::square(&mut self._fieldPlane, [leftTop[0], leftTop[1], bottomRight[0], bottomRight[1]], z + 1, self._style.backgroundFor(self._state));
+		let arrow = height / 3.0;
+		UiStyle::square(
+			&mut self._arrowPlane,
+			[bottomRight[0] - padding - arrow, leftTop[1] + arrow, bottomRight[0] - padding, bottomRight[1] - arrow],
+			z + 2,
+			self._style.foreground,
+		);
+
+		let shown = self.getSelectedLabel().cloned().unwrap_or_else(|| self._placeholder.clone());
+		self._style.label(&mut self._text, &shown, leftTop[0] + padding, leftTop[1], bottomRight[1], z + 2);
+
+		let popupZ = z + POPUP_Z;
+		UiStyle::square(&mut self._popupPlane, [leftTop[0], bottomRight[1], bottomRight[0], popupBottom], popupZ, self._style.background);
+		if let Some(hovered) = self._hovered.or(self._selected)
+		{
+			let top = bottomRight[1] + height * hovered as f32;
+			UiStyle::square(&mut self._hoverPlane, [leftTop[0], top, bottomRight[0], top + height], popupZ + 1, self._style.backgroundFor(UiButtonState::HOVER));
+		}
+		for (i, text) in self._popupTexts.iter_mut().enumerate()
+		{
+			let top = bottomRight[1] + height * i as f32;
+			self._style.label(text, &self._options[i], leftTop[0] + padding, top, top + height, popupZ + 2);
+		}
+	}
+}
+
+impl event_trait for UiDropdown
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returning = self._events.clone().trigger(eventtype, self);
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER =>
+			{
+				let mut newstate = UiButtonState::IDLE;
+				let mut hovered = None;
+				if (eventtype == event_type::HOVER)
+				{
+					match self.mouseTarget()
+					{
+						None => newstate = UiButtonState::HOVER,
+						Some(target) => hovered = target,
+					}
+				}
+				if (self._state != newstate || self._hovered != hovered)
+				{
+					self._state = newstate;
+					self._hovered = hovered;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::CLICKED =>
+			{
+				if (self._state != UiButtonState::PRESSED)
+				{
+					self._state = UiButtonState::PRESSED;
+					match self.mouseTarget()
+					{
+						None => self._open = !self._open,
+						Some(Some(index)) =>
+						{
+							self.select(index);
+							self._open = false;
+						}
+						Some(None) =>
+						{
+							// keyboard/gamepad activation, select the next option without opening
+							if (!self._options.is_empty())
+							{
+								let next = self._selected.map(|x| (x + 1) % self._options.len()).unwrap_or(0);
+								self.select(next);
+							}
+						}
+					}
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					// clicking anywhere else remove the focus, and close the popup
+					if (!focused)
+					{
+						self._open = false;
+						self._hovered = None;
+					}
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self._style.refresh();
+				self._text.event_trigger(eventtype);
+				self._popupTexts.iter_mut().for_each(|x| {
+					x.event_trigger(eventtype);
+				});
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			_ =>
+			{}
+		}
+
+		if (self._cacheinfos.isPresent() && returning)
+		{
+			self.cache_submit();
+		}
+		return returning;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::IDLE => true,
+			event_type::HOVER => true,
+			event_type::CLICKED => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
+			event_type::WINREFRESH => true,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiDropdown> for UiDropdown
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiDropdown) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl hideable for UiDropdown
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._open = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiDropdown
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self._text.cache_mustUpdate() || (self._open && self._popupTexts.iter().any(|x| x.cache_mustUpdate()))
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		self.refreshDisplay();
+		if (self._focused)
+		{
+			self._focusPlane.cache_submit();
+		}
+		else
+		{
+			self._focusPlane.cache_remove();
+		}
+		self._fieldPlane.cache_submit();
+		self._arrowPlane.cache_submit();
+		self._text.cache_submit();
+
+		if (self._open)
+		{
+			self._popupPlane.cache_submit();
+			if (self._hovered.or(self._selected).is_some())
+			{
+				self._hoverPlane.cache_submit();
+			}
+			else
+			{
+				self._hoverPlane.cache_remove();
+			}
+			self._popupTexts.iter_mut().for_each(|x| x.cache_submit());
+		}
+		else
+		{
+			self._popupPlane.cache_remove();
+			self._hoverPlane.cache_remove();
+			self._popupTexts.iter_mut().for_each(|x| x.cache_remove());
+		}
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._focusPlane.cache_remove();
+		self._fieldPlane.cache_remove();
+		self._arrowPlane.cache_remove();
+		self._text.cache_remove();
+		self._popupPlane.cache_remove();
+		self._hoverPlane.cache_remove();
+		self._popupTexts.iter_mut().for_each(|x| x.cache_remove());
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiDropdown
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		// the popup is a layer over everything, only the field is clipped
+		self._focusPlane.layout_clip(clip);
+		self._fieldPlane.layout_clip(clip);
+		self._arrowPlane.layout_clip(clip);
+		self._text.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		let mut uuids: Vec<Uuid> = [&self._focusPlane, &self._fieldPlane, &self._arrowPlane, &self._popupPlane, &self._hoverPlane]
+			.iter()
+			.map(|x| x.cache_infos().getUuid())
+			.collect();
+		uuids.push(self._text.cache_infos().getUuid());
+		uuids.extend(self._popupTexts.iter().map(|x| x.cache_infos().getUuid()));
+		return uuids;
+	}
+}
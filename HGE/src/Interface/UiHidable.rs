@@ -2,10 +2,12 @@
 use crate::components::cacheInfos::cacheInfos;
 use crate::components::event::{event, event_trait, event_trait_add, event_type};
 use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
 use crate::Interface::UiHitbox::UiHitbox;
 use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
 use crate::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dsimple_def};
 use crate::Shaders::ShaderDrawerImpl::{ShaderDrawerImpl, ShaderDrawerImplReturn};
+use uuid::Uuid;
 
 pub trait UiHidable_content: UiPageContent + ShaderDrawerImplReturn<HGE_shader_2Dsimple_def>{}
 dyn_clone::clone_trait_object!(UiHidable_content);
@@ -160,4 +162,19 @@ impl UiPageContent for UiHidable
 	fn getHitbox(&self) -> UiHitbox {
 		self._hitbox.clone()
 	}
+	
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition) {
+		self._content.iter_mut().for_each(|x|x.layout_place(leftTop.clone(), bottomRight.clone()));
+		self._cacheinfos.setNeedUpdate(true);
+	}
+	
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>) {
+		self._content.iter_mut().for_each(|x|x.layout_clip(clip));
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return self._content.iter().flat_map(|x| x.cache_uuids()).collect();
+	}
 }
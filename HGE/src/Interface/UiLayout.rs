@@ -0,0 +1,577 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::enums::{ALIGN_H, ALIGN_V};
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type, UiScrollEvent};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UiLayout_direction
+{
+	/// from left to right
+	HORIZONTAL,
+	/// from top to bottom
+	VERTICAL,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UiLayout_type
+{
+	/// contents keep their size and are placed one after the other
+	STACK(UiLayout_direction),
+	/// same as stack, but the free space is shared between contents using their grow factor
+	FLEX(UiLayout_direction),
+	/// contents are placed in cells, row by row, with this number of columns
+	GRID(u16),
+}
+
+/// inner space between the layout border and its contents, in pixel
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UiLayout_padding
+{
+	pub left: f32,
+	pub top: f32,
+	pub right: f32,
+	pub bottom: f32,
+}
+
+impl UiLayout_padding
+{
+	pub fn same(value: f32) -> Self
+	{
+		return UiLayout_padding {
+			left: value,
+			top: value,
+			right: value,
+			bottom: value,
+		};
+	}
+}
+
+#[derive(Clone)]
+struct UiLayout_item
+{
+	content: Box<dyn UiPageContent + Send + Sync>,
+	size: interfacePosition,
+	grow: f32,
+}
+
+/// container placing its contents automatically inside a square, recomputed on each window refresh
+#[derive(Clone)]
+pub struct UiLayout
+{
+	_type: UiLayout_type,
+	_position: [interfacePosition; 2],
+	_padding: UiLayout_padding,
+	_gap: f32,
+	_alignH: ALIGN_H,
+	_alignV: ALIGN_V,
+	_content: Vec<UiLayout_item>,
+	_clip: Option<[f32; 4]>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_event: event<Self>,
+}
+
+impl UiLayout
+{
+	pub fn new(layouttype: UiLayout_type, leftTop: interfacePosition, bottomRight: interfacePosition) -> Self
+	{
+		return UiLayout {
+			_type: layouttype,
+			_position: [leftTop, bottomRight],
+			_padding: UiLayout_padding::default(),
+			_gap: 0.0,
+			_alignH: ALIGN_H::LEFT,
+			_alignV: ALIGN_V::TOP,
+			_content: Vec::new(),
+			_clip: None,
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_event: event::new(),
+		};
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setType(&mut self, layouttype: UiLayout_type)
+	{
+		self._type = layouttype;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setPadding(&mut self, padding: UiLayout_padding)
+	{
+		self._padding = padding;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// space between two contents, in pixel
+	pub fn setGap(&mut self, gap: f32)
+	{
+		self._gap = gap.max(0.0);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setAlign(&mut self, alignH: ALIGN_H, alignV: ALIGN_V)
+	{
+		self._alignH = alignH;
+		self._alignV = alignV;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// add a content with a fixed size, x as width and y as height (percent are relative to the window)
+	/// a size of 0 on the cross axis (or in a grid cell) stretch the content
+	pub fn add(&mut self, content: impl UiPageContent + Send + Sync + 'static, size: interfacePosition)
+	{
+		self.addGrow(content, size, 0.0);
+	}
+
+	/// same as add, with a grow factor used by FLEX (share of the free space) and GRID (fill the cell)
+	pub fn addGrow(&mut self, content: impl UiPageContent + Send + Sync + 'static, size: interfacePosition, grow: f32)
+	{
+		self.addBoxed(Box::new(content), size, grow);
+	}
+
+	/// same as addGrow, for a content already boxed
+	pub fn addBoxed(&mut self, content: Box<dyn UiPageContent + Send + Sync>, size: interfacePosition, grow: f32)
+	{
+		self._content.push(UiLayout_item {
+			content,
+			size,
+			grow: grow.max(0.0),
+		});
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn remove(&mut self, index: usize)
+	{
+		if (index >= self._content.len())
+		{
+			return;
+		}
+		let mut item = self._content.remove(index);
+		item.content.cache_remove();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn clear(&mut self)
+	{
+		self._content.iter_mut().for_each(|x| x.content.cache_remove());
+		self._content.clear();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn len(&self) -> usize
+	{
+		return self._content.len();
+	}
+
+	pub fn content_mut(&mut self, index: usize) -> Option<&mut Box<dyn UiPageContent + Send + Sync>>
+	{
+		self._cacheinfos.setNeedUpdate(true);
+		self._content.get_mut(index).map(|x| &mut x.content)
+	}
+
+	pub fn boxed(self) -> Box<UiLayout>
+	{
+		return Box::new(self);
+	}
+
+	/// compute and apply contents positions, called automatically on submit when something changed
+	pub fn layout_update(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+
+		let inner = [
+			leftTop[0] + self._padding.left,
+			leftTop[1] + self._padding.top,
+			bottomRight[0] - self._padding.right,
+			bottomRight[1] - self._padding.bottom,
+		];
+
+		let rects = match self._type
+		{
+			UiLayout_type::STACK(direction) => self.computeLine(inner, direction, false),
+			UiLayout_type::FLEX(direction) => self.computeLine(inner, direction, true),
+			UiLayout_type::GRID(columns) => self.computeGrid(inner, columns),
+		};
+
+		for (item, rect) in self._content.iter_mut().zip(rects)
+		{
+			item.content.layout_clip(self._clip);
+			item.content.layout_place(
+				interfacePosition::new_pixel_z(rect[0].round() as i32, rect[1].round() as i32, z),
+				interfacePosition::new_pixel_z(rect[2].round() as i32, rect[3].round() as i32, z),
+			);
+		}
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn alignH_ratio(align: ALIGN_H) -> f32
+	{
+		match align
+		{
+			ALIGN_H::LEFT => 0.0,
+			ALIGN_H::CENTER => 0.5,
+			ALIGN_H::RIGHT => 1.0,
+		}
+	}
+
+	fn alignV_ratio(align: ALIGN_V) -> f32
+	{
+		match align
+		{
+			ALIGN_V::TOP => 0.0,
+			ALIGN_V::CENTER => 0.5,
+			ALIGN_V::BOTTOM => 1.0,
+		}
+	}
+
+	// return [left, top, right, bottom] for each content
+	fn computeLine(&self, inner: [f32; 4], direction: UiLayout_direction, withGrow: bool) -> Vec<[f32; 4]>
+	{
+		if (self._content.is_empty())
+		{
+			return Vec::new();
+		}
+
+		let (mainAxis, crossAxis, mainAlign, crossAlign) = match direction
+		{
+			UiLayout_direction::HORIZONTAL => (0, 1, Self::alignH_ratio(self._alignH), Self::alignV_ratio(self._alignV)),
+			UiLayout_direction::VERTICAL => (1, 0, Self::alignV_ratio(self._alignV), Self::alignH_ratio(self._alignH)),
+		};
+		let innerSize = [inner[2] - inner[0], inner[3] - inner[1]];
+		let sizes: Vec<[f32; 2]> = self._content.iter().map(|x| x.size.convertToPixel()).collect();
+
+		let mut mains: Vec<f32> = sizes.iter().map(|x| x[mainAxis].max(0.0)).collect();
+		let gaps = self._gap * (self._content.len() - 1) as f32;
+		let mut free = innerSize[mainAxis] - gaps - mains.iter().sum::<f32>();
+
+		let totalGrow: f32 = self._content.iter().map(|x| x.grow).sum();
+		if (withGrow && free > 0.0 && totalGrow > 0.0)
+		{
+			for (main, item) in mains.iter_mut().zip(self._content.iter())
+			{
+				*main += free * item.grow / totalGrow;
+			}
+			free = 0.0;
+		}
+
+		let mut cursor = inner[mainAxis] + free.max(0.0) * mainAlign;
+		let mut rects = Vec::new();
+		for (i, main) in mains.iter().enumerate()
+		{
+			let mut cross = sizes[i][crossAxis];
+			if (cross <= 0.0)
+			{
+				cross = innerSize[crossAxis];
+			}
+			let crossStart = inner[crossAxis] + (innerSize[crossAxis] - cross).max(0.0) * crossAlign;
+
+			let mut rect = [0.0; 4];
+			rect[mainAxis] = cursor;
+			rect[mainAxis + 2] = cursor + main;
+			rect[crossAxis] = crossStart;
+			rect[crossAxis + 2] = crossStart + cross;
+			rects.push(rect);
+
+			cursor += main + self._gap;
+		}
+
+		return rects;
+	}
+
+	// return [left, top, right, bottom] for each content
+	fn computeGrid(&self, inner: [f32; 4], columns: u16) -> Vec<[f32; 4]>
+	{
+		let count = self._content.len();
+		if (count == 0)
+		{
+			return Vec::new();
+		}
+
+		let columns = columns.max(1) as usize;
+		let rows = (count + columns - 1) / columns;
+		let innerSize = [inner[2] - inner[0], inner[3] - inner[1]];
+		let cellWidth = ((innerSize[0] - self._gap * (columns - 1) as f32) / columns as f32).max(0.0);
+		let sizes: Vec<[f32; 2]> = self._content.iter().map(|x| x.size.convertToPixel()).collect();
+
+		// a row is as high as its highest content, rows without sized content share the remaining height
+		let mut heights: Vec<f32> = (0..rows)
+			.map(|row| sizes[row * columns..((row + 1) * columns).min(count)].iter().fold(0.0f32, |acc, x| acc.max(x[1])))
+			.collect();
+		let autoRows = heights.iter().filter(|x| **x <= 0.0).count();
+		if (autoRows > 0)
+		{
+			let used = self._gap * (rows - 1) as f32 + heights.iter().sum::<f32>();
+			let autoHeight = ((innerSize[1] - used) / autoRows as f32).max(0.0);
+			heights.iter_mut().filter(|x| **x <= 0.0).for_each(|x| *x = autoHeight);
+		}
+
+		let alignH = Self::alignH_ratio(self._alignH);
+		let alignV = Self::alignV_ratio(self._alignV);
+		let mut rects = Vec::new();
+		let mut cellY = inner[1];
+		for row in 0..rows
+		{
+			for column in 0..columns
+			{
+				let i = row * columns + column;
+				if (i >= count)
+				{
+					break;
+				}
+
+				let cellX = inner[0] + column as f32 * (cellWidth + self._gap);
+				let mut width = sizes[i][0];
+				let mut height = sizes[i][1];
+				if (self._content[i].grow > 0.0 || width <= 0.0)
+				{
+					width = cellWidth;
+				}
+				if (self._content[i].grow > 0.0 || height <= 0.0)
+				{
+					height = heights[row];
+				}
+
+				let x = cellX + (cellWidth - width).max(0.0) * alignH;
+				let y = cellY + (heights[row] - height).max(0.0) * alignV;
+				rects.push([x, y, x + width, y + height]);
+			}
+			cellY += heights[row] + self._gap;
+		}
+
+		return rects;
+	}
+
+	fn checkContentUpdate(&self) -> bool
+	{
+		self._content.iter().any(|x| x.content.cache_mustUpdate())
+	}
+}
+
+impl event_trait_add<UiLayout> for UiLayout
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiLayout) -> bool + Send + Sync + 'static)
+	{
+		self._event.add(eventtype, func);
+	}
+}
+
+impl event_trait for UiLayout
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returned = self._event.clone().trigger(eventtype, self);
+
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER | event_type::CLICKED =>
+			{
+				// the page only know the layout hitbox, dispatch to the content under the mouse
+				let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+				for item in self._content.iter_mut()
+				{
+					if (item.content.getType() != UiPageContent_type::INTERACTIVE)
+					{
+						continue;
+					}
+
+					let mut subevent = event_type::IDLE;
+					if (eventtype != event_type::IDLE && item.content.getHitbox().isInside(mousex, mousey))
+					{
+						subevent = eventtype;
+					}
+					if (item.content.event_trigger(subevent))
+					{
+						returned = true;
+					}
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self._content.iter_mut().for_each(|item| {
+					item.content.event_trigger(eventtype);
+				});
+				self._cacheinfos.setNeedUpdate(true);
+				if (self._cacheinfos.isPresent())
+				{
+					self.cache_submit();
+				}
+				returned = true;
+			}
+			_ =>
+			{
+				for item in self._content.iter_mut()
+				{
+					if (item.content.event_trigger(eventtype))
+					{
+						returned = true;
+					}
+				}
+			}
+		}
+
+		return returned;
+	}
+
+	fn event_have(&self, _eventtype: event_type) -> bool
+	{
+		true
+	}
+}
+
+impl hideable for UiLayout
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiLayout
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self.checkContentUpdate()
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		if (self._cacheinfos.isNeedUpdate() || self._cacheinfos.isAbsent())
+		{
+			self.layout_update();
+		}
+
+		for item in self._content.iter_mut()
+		{
+			if (item.content.cache_mustUpdate())
+			{
+				item.content.cache_submit();
+			}
+		}
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._content.iter_mut().for_each(|x| x.content.cache_remove());
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiLayout
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		if (self._clip != clip)
+		{
+			self._clip = clip;
+			self._cacheinfos.setNeedUpdate(true);
+		}
+	}
+
+	fn event_scroll(&mut self, event: &UiScrollEvent) -> bool
+	{
+		let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+		for item in self._content.iter_mut()
+		{
+			// a drag keep going on the content that accepted its start, other contents ignore it
+			let underPointer = match event
+			{
+				UiScrollEvent::WHEEL(_) => item.content.getHitbox().isInside(mousex, mousey),
+				UiScrollEvent::DRAG_START([x, y]) => item.content.getHitbox().isInside(*x as u16, *y as u16),
+				UiScrollEvent::DRAG_MOVE(_) | UiScrollEvent::DRAG_END => true,
+			};
+			if (underPointer && item.content.event_scroll(event))
+			{
+				return true;
+			}
+		}
+		return false;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return self._content.iter().flat_map(|x| x.content.cache_uuids()).collect();
+	}
+
+	fn childs(&self) -> Vec<&dyn UiPageContent>
+	{
+		return self._content.iter().map(|x| &*x.content as &dyn UiPageContent).collect();
+	}
+
+	fn childs_mut(&mut self) -> Vec<&mut dyn UiPageContent>
+	{
+		return self._content.iter_mut().map(|x| &mut *x.content as &mut dyn UiPageContent).collect();
+	}
+}
@@ -0,0 +1,540 @@
+use crate::assetStreamReader::assetManager;
+use crate::components::color::color;
+use crate::components::corners::corner4;
+use crate::components::enums::{ALIGN_H, ALIGN_V};
+use crate::components::event::{event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::Bar::{Bar, Bar_orientation, Bar_state};
+use crate::Interface::ManagerFont::ManagerFont;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::Text::Text;
+use crate::Interface::UiButton::{UiButton, UiButton_content};
+use crate::Interface::UiHidable::{UiHidable, UiHidable_content};
+use crate::Interface::UiLayout::{UiLayout, UiLayout_direction, UiLayout_padding, UiLayout_type};
+use crate::Interface::UiPage::{UiPage, UiPageContent};
+use crate::Interface::UiPanel9Slice::{UiPanel9Slice, UiPanel9Slice_mode};
+use crate::Interface::UiScrollView::UiScrollView;
+use crate::Interface::UiTheme::UiTheme;
+use anyhow::anyhow;
+use glyph_brush::OwnedText;
+use glyph_brush_layout::{HorizontalAlign, Layout};
+use std::sync::Arc;
+use Hconfig::serde_json::{Map, Value as JsonValue};
+
+/// function bound by name to a markup event, registered with ManagerInterface::UiHandlerAdd
+pub type UiMarkup_handler = Arc<dyn Fn(&mut dyn UiPageContent) -> bool + Send + Sync>;
+
+type JsonObject = Map<String, JsonValue>;
+
+// keys accepted on every element, "size" and "grow" are used by UiLayout children
+const KEYS_COMMON: [&str; 5] = ["name", "type", "events", "size", "grow"];
+
+/// build a UiPage from a json description :
+/// { "focusOrder": ["name", ...], "content": [ { "name": "bg", "type": "Plane", ... }, ... ] }
+/// types : Plane, Text, Bar, UiPanel9Slice, UiButton, UiHidable, UiLayout, UiScrollView
+/// positions are [x, y(, z)] in pixel, or { "percent": [x, y], "z": z } / { "pixel": [x, y], "z": z }
+/// "events": { "EACH_SECOND": "handlerName" } and "clicked" (UiButton) use functions registered with ManagerInterface::UiHandlerAdd
+pub struct UiMarkup;
+
+impl UiMarkup
+{
+	/// read and build a page from a json file read with assetManager
+	pub fn loadFile(path: impl Into<String>) -> anyhow::Result<UiPage>
+	{
+		let path = path.into();
+		let Some(fileread) = assetManager::singleton().readFile(&path)
+		else
+		{
+			return Err(anyhow!("cannot load : {}", path));
+		};
+		let json: JsonValue = Hconfig::serde_json::from_reader(fileread).map_err(|err| anyhow!("{} : {}", path, err))?;
+		return Self::loadJson(&json).map_err(|err| anyhow!("{} : {}", path, err));
+	}
+
+	pub fn loadJson(json: &JsonValue) -> anyhow::Result<UiPage>
+	{
+		let root = Self::object(json, "page")?;
+		Self::checkKeys(root, "page", &["content", "focusOrder"], &[])?;
+
+		let mut page = UiPage::new();
+		for (i, element) in Self::array(root, "content", "page")?.iter().enumerate()
+		{
+			let Some(name) = element.get("name").and_then(|x| x.as_str())
+			else
+			{
+				return Err(anyhow!("page/{} : page contents need a \"name\"", i));
+			};
+			let content = Self::element(element, name)?;
+			page.addBoxed(name, content);
+		}
+
+		if let Some(order) = root.get("focusOrder")
+		{
+			let order = order
+				.as_array()
+				.and_then(|x| x.iter().map(|name| name.as_str().map(|name| name.to_string())).collect::<Option<Vec<String>>>())
+				.ok_or(anyhow!("page : \"focusOrder\" must be an array of names"))?;
+			page.focusOrder_set(order);
+		}
+		return Ok(page);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// any element, path is used in error messages
+	fn element(json: &JsonValue, path: &str) -> anyhow::Result<Box<dyn UiPageContent + Send + Sync>>
+	{
+		let obj = Self::object(json, path)?;
+		return match Self::string(obj, "type", path)?.unwrap_or_default()
+		{
+			"Plane" => Ok(Box::new(Self::plane(obj, path)?)),
+			"Text" => Ok(Box::new(Self::text(obj, path)?)),
+			"Bar" => Ok(Box::new(Self::bar(obj, path)?)),
+			"UiPanel9Slice" => Ok(Box::new(Self::panel(obj, path)?)),
+			"UiButton" => Ok(Box::new(Self::button(obj, path)?)),
+			"UiHidable" => Ok(Box::new(Self::hidable(obj, path)?)),
+			"UiLayout" => Ok(Box::new(Self::layout(obj, path)?)),
+			"UiScrollView" => Ok(Box::new(Self::scrollView(obj, path)?)),
+			other => Err(anyhow!("{} : unknown type \"{}\"", path, other)),
+		};
+	}
+
+	// content of a UiButton
+	fn buttonContent(json: &JsonValue, path: &str) -> anyhow::Result<Box<dyn UiButton_content + Send + Sync>>
+	{
+		let obj = Self::object(json, path)?;
+		return match Self::string(obj, "type", path)?.unwrap_or_default()
+		{
+			"Plane" => Ok(Box::new(Self::plane(obj, path)?)),
+			"Text" => Ok(Box::new(Self::text(obj, path)?)),
+			"UiPanel9Slice" => Ok(Box::new(Self::panel(obj, path)?)),
+			other => Err(anyhow!("{} : type \"{}\" cannot be inside a UiButton (Plane, Text or UiPanel9Slice)", path, other)),
+		};
+	}
+
+	// content of a UiHidable
+	fn hidableContent(json: &JsonValue, path: &str) -> anyhow::Result<Box<dyn UiHidable_content + Send + Sync>>
+	{
+		let obj = Self::object(json, path)?;
+		return match Self::string(obj, "type", path)?.unwrap_or_default()
+		{
+			"Plane" => Ok(Box::new(Self::plane(obj, path)?)),
+			"Text" => Ok(Box::new(Self::text(obj, path)?)),
+			"UiPanel9Slice" => Ok(Box::new(Self::panel(obj, path)?)),
+			other => Err(anyhow!("{} : type \"{}\" cannot be inside a UiHidable (Plane, Text or UiPanel9Slice)", path, other)),
+		};
+	}
+
+	fn plane(obj: &JsonObject, path: &str) -> anyhow::Result<Plane<interfacePosition>>
+	{
+		Self::checkKeys(obj, path, &["leftTop", "bottomRight", "color", "texture"], &KEYS_COMMON)?;
+		let mut plane = Plane::new();
+		plane.setSquare(Self::positionRequired(obj, "leftTop", path)?, Self::positionRequired(obj, "bottomRight", path)?);
+		if let Some(value) = obj.get("color")
+		{
+			plane.setColor(corner4::same(Self::color(value, "color", path)?));
+		}
+		if let Some(texture) = Self::string(obj, "texture", path)?
+		{
+			plane.components_mut().texture_mut().set(texture);
+		}
+		Self::events(&mut plane, obj, path)?;
+		return Ok(plane);
+	}
+
+	fn text(obj: &JsonObject, path: &str) -> anyhow::Result<Text>
+	{
+		Self::checkKeys(obj, path, &["position", "text", "textSize", "color", "font", "align"], &KEYS_COMMON)?;
+		let mut ownedtext = OwnedText::new(Self::string(obj, "text", path)?.unwrap_or_default());
+		if let Some(value) = obj.get("color")
+		{
+			ownedtext = ownedtext.with_color(Self::color(value, "color", path)?.toArray());
+		}
+		if let Some(font) = Self::string(obj, "font", path)?
+		{
+			let Some(fontId) = ManagerFont::singleton().FontIdGet(font)
+			else
+			{
+				return Err(anyhow!("{} : unknown font \"{}\"", path, font));
+			};
+			ownedtext = ownedtext.with_font_id(fontId);
+		}
+
+		let mut text = Text::new();
+		text.addText(ownedtext);
+		text.setPos(Self::positionRequired(obj, "position", path)?);
+		if let Some(value) = obj.get("textSize")
+		{
+			text.setTextDynamicSize(UiTheme::parseTextSize(value).map_err(|err| anyhow!("{} : \"textSize\" {}", path, err))?);
+		}
+		if let Some(align) = Self::string(obj, "align", path)?
+		{
+			let align = match align
+			{
+				"LEFT" => HorizontalAlign::Left,
+				"CENTER" => HorizontalAlign::Center,
+				"RIGHT" => HorizontalAlign::Right,
+				_ => return Err(anyhow!("{} : \"align\" must be LEFT, CENTER or RIGHT", path)),
+			};
+			text.setLayout(Layout::default_single_line().h_align(align));
+		}
+		Self::events(&mut text, obj, path)?;
+		return Ok(text);
+	}
+
+	fn bar(obj: &JsonObject, path: &str) -> anyhow::Result<Bar>
+	{
+		Self::checkKeys(obj, path, &["leftTop", "bottomRight", "color", "states", "texture", "progress", "orientation"], &KEYS_COMMON)?;
+		let mut bar = Bar::new(Self::positionRequired(obj, "leftTop", path)?, Self::positionRequired(obj, "bottomRight", path)?);
+		if let Some(value) = obj.get("color")
+		{
+			bar.addState(0, Bar_state { color: Self::color(value, "color", path)? });
+		}
+		// [[progress between 0.0 and 1.0, color], ...]
+		if let Some(states) = obj.get("states")
+		{
+			for state in states.as_array().ok_or(anyhow!("{} : \"states\" must be an array of [progress, color]", path))?
+			{
+				let (Some(progress), Some(value)) = (state.get(0).and_then(|x| x.as_f64()), state.get(1))
+				else
+				{
+					return Err(anyhow!("{} : \"states\" must be an array of [progress, color]", path));
+				};
+				let progress = (progress.clamp(0.0, 1.0) * 10000.0) as u16;
+				bar.addState(progress, Bar_state { color: Self::color(value, "states", path)? });
+			}
+		}
+		if let Some(texture) = Self::string(obj, "texture", path)?
+		{
+			bar.components_mut().texture_mut().set(texture);
+		}
+		if let Some(progress) = Self::number(obj, "progress", path)?
+		{
+			bar.updateProgress(progress);
+		}
+		match Self::string(obj, "orientation", path)?
+		{
+			None | Some("HORIZONTAL") => bar.setOrientation(Bar_orientation::HORIZONTAL),
+			Some("VERTICAL") => bar.setOrientation(Bar_orientation::VERTICAL),
+			_ => return Err(anyhow!("{} : \"orientation\" must be HORIZONTAL or VERTICAL", path)),
+		}
+		Self::events(&mut bar, obj, path)?;
+		return Ok(bar);
+	}
+
+	fn panel(obj: &JsonObject, path: &str) -> anyhow::Result<UiPanel9Slice>
+	{
+		Self::checkKeys(obj, path, &["leftTop", "bottomRight", "style", "texture", "insets", "scale", "mode", "color"], &KEYS_COMMON)?;
+		let mut panel = UiPanel9Slice::new(Self::positionRequired(obj, "leftTop", path)?, Self::positionRequired(obj, "bottomRight", path)?);
+		if let Some(class) = Self::string(obj, "style", path)?
+		{
+			panel.setStyle(&UiTheme::singleton().get(class));
+		}
+		if let Some(texture) = Self::string(obj, "texture", path)?
+		{
+			panel.setTexture(texture);
+		}
+		if let Some(value) = obj.get("insets")
+		{
+			panel.setInsets(Self::sides(value, "insets", path)?);
+		}
+		if let Some(scale) = Self::number(obj, "scale", path)?
+		{
+			panel.setScale(scale);
+		}
+		match Self::string(obj, "mode", path)?
+		{
+			None | Some("STRETCH") => panel.setMode(UiPanel9Slice_mode::STRETCH),
+			Some("TILE") => panel.setMode(UiPanel9Slice_mode::TILE),
+			_ => return Err(anyhow!("{} : \"mode\" must be STRETCH or TILE", path)),
+		}
+		if let Some(value) = obj.get("color")
+		{
+			panel.setColor(Self::color(value, "color", path)?);
+		}
+		Self::events(&mut panel, obj, path)?;
+		return Ok(panel);
+	}
+
+	fn button(obj: &JsonObject, path: &str) -> anyhow::Result<UiButton>
+	{
+		Self::checkKeys(obj, path, &["content", "clicked"], &KEYS_COMMON)?;
+		if (obj.contains_key("events"))
+		{
+			return Err(anyhow!("{} : UiButton use \"clicked\" instead of \"events\"", path));
+		}
+		let mut button = UiButton::new();
+		for (i, content) in Self::array(obj, "content", path)?.iter().enumerate()
+		{
+			let content = Self::buttonContent(content, &format!("{}/{}", path, i))?;
+			button.content_mut().push(content);
+		}
+		if let Some(name) = Self::string(obj, "clicked", path)?
+		{
+			let handler = Self::handler(name, path)?;
+			button.setClickedFn(move |button| {
+				handler(button);
+			});
+		}
+		return Ok(button);
+	}
+
+	fn hidable(obj: &JsonObject, path: &str) -> anyhow::Result<UiHidable>
+	{
+		Self::checkKeys(obj, path, &["content", "hidden"], &KEYS_COMMON)?;
+		let mut hidable = UiHidable::new();
+		for (i, content) in Self::array(obj, "content", path)?.iter().enumerate()
+		{
+			let content = Self::hidableContent(content, &format!("{}/{}", path, i))?;
+			hidable.content_mut().push(content);
+		}
+		if let Some(hidden) = obj.get("hidden")
+		{
+			if (hidden.as_bool().ok_or(anyhow!("{} : \"hidden\" must be a boolean", path))?)
+			{
+				hidable.hide();
+			}
+		}
+		Self::events(&mut hidable, obj, path)?;
+		return Ok(hidable);
+	}
+
+	fn layout(obj: &JsonObject, path: &str) -> anyhow::Result<UiLayout>
+	{
+		Self::checkKeys(obj, path, &["leftTop", "bottomRight", "layout", "direction", "columns", "padding", "gap", "align", "content"], &KEYS_COMMON)?;
+		let direction = match Self::string(obj, "direction", path)?
+		{
+			None | Some("VERTICAL") => UiLayout_direction::VERTICAL,
+			Some("HORIZONTAL") => UiLayout_direction::HORIZONTAL,
+			_ => return Err(anyhow!("{} : \"direction\" must be HORIZONTAL or VERTICAL", path)),
+		};
+		let layouttype = match Self::string(obj, "layout", path)?
+		{
+			None | Some("STACK") => UiLayout_type::STACK(direction),
+			Some("FLEX") => UiLayout_type::FLEX(direction),
+			Some("GRID") => UiLayout_type::GRID(Self::number(obj, "columns", path)?.unwrap_or(1.0).max(1.0) as u16),
+			_ => return Err(anyhow!("{} : \"layout\" must be STACK, FLEX or GRID", path)),
+		};
+
+		let mut layout = UiLayout::new(layouttype, Self::positionRequired(obj, "leftTop", path)?, Self::positionRequired(obj, "bottomRight", path)?);
+		if let Some(value) = obj.get("padding")
+		{
+			let [left, top, right, bottom] = Self::sides(value, "padding", path)?;
+			layout.setPadding(UiLayout_padding { left, top, right, bottom });
+		}
+		if let Some(gap) = Self::number(obj, "gap", path)?
+		{
+			layout.setGap(gap);
+		}
+		if let Some(align) = obj.get("align")
+		{
+			let align = (align.get(0).and_then(|x| x.as_str()), align.get(1).and_then(|x| x.as_str()));
+			let alignH = match align.0
+			{
+				Some("LEFT") => ALIGN_H::LEFT,
+				Some("CENTER") => ALIGN_H::CENTER,
+				Some("RIGHT") => ALIGN_H::RIGHT,
+				_ => return Err(anyhow!("{} : \"align\" must be [LEFT|CENTER|RIGHT, TOP|CENTER|BOTTOM]", path)),
+			};
+			let alignV = match align.1
+			{
+				Some("TOP") => ALIGN_V::TOP,
+				Some("CENTER") => ALIGN_V::CENTER,
+				Some("BOTTOM") => ALIGN_V::BOTTOM,
+				_ => return Err(anyhow!("{} : \"align\" must be [LEFT|CENTER|RIGHT, TOP|CENTER|BOTTOM]", path)),
+			};
+			layout.setAlign(alignH, alignV);
+		}
+
+		for (i, content) in Self::array(obj, "content", path)?.iter().enumerate()
+		{
+			let childpath = format!("{}/{}", path, i);
+			let child = Self::object(content, &childpath)?;
+			let size = Self::position(child, "size", &childpath)?.unwrap_or(interfacePosition::new_pixel(0, 0));
+			let grow = Self::number(child, "grow", &childpath)?.unwrap_or(0.0);
+			layout.addBoxed(Self::element(content, &childpath)?, size, grow);
+		}
+		Self::events(&mut layout, obj, path)?;
+		return Ok(layout);
+	}
+
+	fn scrollView(obj: &JsonObject, path: &str) -> anyhow::Result<UiScrollView>
+	{
+		Self::checkKeys(obj, path, &["leftTop", "bottomRight", "content", "contentSize", "axis", "friction"], &KEYS_COMMON)?;
+		let mut view = UiScrollView::new(Self::positionRequired(obj, "leftTop", path)?, Self::positionRequired(obj, "bottomRight", path)?);
+		if let Some(content) = obj.get("content")
+		{
+			let size = Self::position(obj, "contentSize", path)?.unwrap_or(interfacePosition::new_pixel(0, 0));
+			view.setContentBoxed(Self::element(content, &format!("{}/content", path))?, size);
+		}
+		if let Some(axis) = obj.get("axis")
+		{
+			let (Some(horizontal), Some(vertical)) = (axis.get(0).and_then(|x| x.as_bool()), axis.get(1).and_then(|x| x.as_bool()))
+			else
+			{
+				return Err(anyhow!("{} : \"axis\" must be [horizontal, vertical] booleans", path));
+			};
+			view.setAxis(horizontal, vertical);
+		}
+		if let Some(friction) = Self::number(obj, "friction", path)?
+		{
+			view.setFriction(friction);
+		}
+		Self::events(&mut view, obj, path)?;
+		return Ok(view);
+	}
+
+	// "events": { "EVENT_TYPE": "handlerName", ... }
+	fn events<T>(content: &mut T, obj: &JsonObject, path: &str) -> anyhow::Result<()>
+	where
+		T: UiPageContent + event_trait_add<T> + Send + Sync + 'static,
+	{
+		let Some(events) = obj.get("events")
+		else
+		{
+			return Ok(());
+		};
+		let Some(events) = events.as_object()
+		else
+		{
+			return Err(anyhow!("{} : \"events\" must be an object of {{ \"EVENT_TYPE\": \"handlerName\" }}", path));
+		};
+
+		for (eventname, handlername) in events
+		{
+			let eventtype = match eventname.as_str()
+			{
+				"IDLE" => event_type::IDLE,
+				"HOVER" => event_type::HOVER,
+				"CLICKED" => event_type::CLICKED,
+				"EACH_SECOND" => event_type::EACH_SECOND,
+				"EACH_TICK" => event_type::EACH_TICK,
+				"WINREFRESH" => event_type::WINREFRESH,
+				"ENTER" => event_type::ENTER,
+				"EXIT" => event_type::EXIT,
+				"FOCUS" => event_type::FOCUS,
+				"BLUR" => event_type::BLUR,
+				_ => return Err(anyhow!("{} : unknown event \"{}\"", path, eventname)),
+			};
+			let Some(handlername) = handlername.as_str()
+			else
+			{
+				return Err(anyhow!("{} : event \"{}\" must be a handler name", path, eventname));
+			};
+			let handler = Self::handler(handlername, path)?;
+			content.event_add(eventtype, move |x| handler(x));
+		}
+		return Ok(());
+	}
+
+	fn handler(name: &str, path: &str) -> anyhow::Result<UiMarkup_handler>
+	{
+		return ManagerInterface::singleton()
+			.UiHandlerGet(name)
+			.ok_or(anyhow!("{} : unknown handler \"{}\", register it with ManagerInterface::UiHandlerAdd", path, name));
+	}
+
+	// [x, y(, z)] in pixel, { "percent": [x, y], "z": z } or { "pixel": [x, y], "z": z }
+	fn position(obj: &JsonObject, key: &str, path: &str) -> anyhow::Result<Option<interfacePosition>>
+	{
+		let Some(value) = obj.get(key)
+		else
+		{
+			return Ok(None);
+		};
+		let error = || anyhow!("{} : \"{}\" must be [x, y(, z)] in pixel or {{ \"percent\"|\"pixel\": [x, y], \"z\": z }}", path, key);
+
+		if let Some(array) = value.as_array()
+		{
+			let xy = [array.get(0).and_then(|x| x.as_f64()), array.get(1).and_then(|x| x.as_f64())];
+			let z = array.get(2).map(|x| x.as_u64());
+			return match (xy, z, array.len())
+			{
+				([Some(x), Some(y)], None, 2) => Ok(Some(interfacePosition::new_pixel(x.round() as i32, y.round() as i32))),
+				([Some(x), Some(y)], Some(Some(z)), 3) => Ok(Some(interfacePosition::new_pixel_z(x.round() as i32, y.round() as i32, z as u16))),
+				_ => Err(error()),
+			};
+		}
+
+		let Some(position) = value.as_object()
+		else
+		{
+			return Err(error());
+		};
+		let z = match position.get("z")
+		{
+			None => 0,
+			Some(z) => z.as_u64().ok_or_else(error)? as u16,
+		};
+		let xy = |value: &JsonValue| -> Option<[f32; 2]> { Some([value.get(0)?.as_f64()? as f32, value.get(1)?.as_f64()? as f32]) };
+		if let Some([x, y]) = position.get("percent").and_then(xy)
+		{
+			return Ok(Some(interfacePosition::new_percent_z(x, y, z)));
+		}
+		if let Some([x, y]) = position.get("pixel").and_then(xy)
+		{
+			return Ok(Some(interfacePosition::new_pixel_z(x.round() as i32, y.round() as i32, z)));
+		}
+		return Err(error());
+	}
+
+	fn positionRequired(obj: &JsonObject, key: &str, path: &str) -> anyhow::Result<interfacePosition>
+	{
+		return Self::position(obj, key, path)?.ok_or(anyhow!("{} : \"{}\" is required", path, key));
+	}
+
+	// one number for all sides or [left, top, right, bottom]
+	fn sides(value: &JsonValue, key: &str, path: &str) -> anyhow::Result<[f32; 4]>
+	{
+		if let Some(size) = value.as_f64()
+		{
+			return Ok([size as f32; 4]);
+		}
+		return UiTheme::parseArray4(value).ok_or(anyhow!("{} : \"{}\" must be a number or [left, top, right, bottom]", path, key));
+	}
+
+	fn color(value: &JsonValue, key: &str, path: &str) -> anyhow::Result<color>
+	{
+		return UiTheme::parseColor(value).map_err(|err| anyhow!("{} : \"{}\" {}", path, key, err));
+	}
+
+	fn number(obj: &JsonObject, key: &str, path: &str) -> anyhow::Result<Option<f32>>
+	{
+		return match obj.get(key)
+		{
+			None => Ok(None),
+			Some(value) => Ok(Some(value.as_f64().ok_or(anyhow!("{} : \"{}\" must be a number", path, key))? as f32)),
+		};
+	}
+
+	fn string<'a>(obj: &'a JsonObject, key: &str, path: &str) -> anyhow::Result<Option<&'a str>>
+	{
+		return match obj.get(key)
+		{
+			None => Ok(None),
+			Some(value) => Ok(Some(value.as_str().ok_or(anyhow!("{} : \"{}\" must be a string", path, key))?)),
+		};
+	}
+
+	fn array<'a>(obj: &'a JsonObject, key: &str, path: &str) -> anyhow::Result<&'a Vec<JsonValue>>
+	{
+		return obj.get(key).and_then(|x| x.as_array()).ok_or(anyhow!("{} : \"{}\" must be an array", path, key));
+	}
+
+	fn object<'a>(json: &'a JsonValue, path: &str) -> anyhow::Result<&'a JsonObject>
+	{
+		return json.as_object().ok_or(anyhow!("{} : must be an object", path));
+	}
+
+	// refuse unknown keys, to catch typos in markup files
+	fn checkKeys(obj: &JsonObject, path: &str, keys: &[&str], common: &[&str]) -> anyhow::Result<()>
+	{
+		if let Some(key) = obj.keys().find(|key| !keys.contains(&key.as_str()) && !common.contains(&key.as_str()))
+		{
+			return Err(anyhow!("{} : unknown key \"{}\"", path, key));
+		}
+		return Ok(());
+	}
+}
@@ -0,0 +1,165 @@
+use crate::components::color::color;
+use crate::components::corners::corner4;
+use crate::components::event::{event_trait, event_type};
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::HGEMain::HGEMain;
+use crate::Interface::Text::Text;
+use crate::Interface::UiStyle::UiStyle;
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use std::time::{Duration, Instant};
+
+/// default z of the dim plane, overlay contents should be above it
+pub const UIOVERLAY_DIM_Z: u16 = 800;
+/// z of toasts background, texts are one layer above
+pub const UITOAST_Z: u16 = 950;
+/// UiTheme class used by toasts
+pub const UITOAST_STYLE_CLASS: &str = "toast";
+// space between toasts and from the bottom of the window, in pixel
+const UITOAST_MARGIN: f32 = 16.0;
+
+/// how an overlay page is shown above the active page, see ManagerInterface::pushOverlay
+#[derive(Clone, Copy, Debug)]
+pub struct UiOverlay_options
+{
+	/// pages below don't receive any input while this overlay is on top
+	pub modal: bool,
+	/// color of a plane covering the whole window below the overlay, None to disable
+	pub dim: Option<color>,
+	pub dimZ: u16,
+}
+
+impl Default for UiOverlay_options
+{
+	fn default() -> Self
+	{
+		return UiOverlay_options {
+			modal: true,
+			dim: Some(color::from([0.0, 0.0, 0.0, 0.5])),
+			dimZ: UIOVERLAY_DIM_Z,
+		};
+	}
+}
+
+impl UiOverlay_options
+{
+	/// not modal and not dimmed, for popups that let the page below work
+	pub fn popup() -> Self
+	{
+		return UiOverlay_options {
+			modal: false,
+			dim: None,
+			dimZ: UIOVERLAY_DIM_Z,
+		};
+	}
+}
+
+pub(crate) struct UiOverlay_entry
+{
+	pub(crate) name: String,
+	pub(crate) options: UiOverlay_options,
+	dimPlane: Option<Plane<interfacePosition>>,
+}
+
+impl UiOverlay_entry
+{
+	pub(crate) fn new(name: String, options: UiOverlay_options) -> Self
+	{
+		let dimPlane = options.dim.map(|dimcolor| {
+			let mut plane = Plane::new();
+			plane.setSquare(interfacePosition::new_percent_z(0.0, 0.0, options.dimZ), interfacePosition::new_percent_z(1.0, 1.0, options.dimZ));
+			plane.setColor(corner4::same(dimcolor));
+			plane.cache_submit();
+			plane
+		});
+		return UiOverlay_entry {
+			name,
+			options,
+			dimPlane,
+		};
+	}
+
+	pub(crate) fn winRefresh(&mut self)
+	{
+		if let Some(plane) = &mut self.dimPlane
+		{
+			plane.event_trigger(event_type::WINREFRESH);
+		}
+	}
+
+	pub(crate) fn remove(&mut self)
+	{
+		if let Some(plane) = &mut self.dimPlane
+		{
+			plane.cache_remove();
+		}
+	}
+}
+
+pub(crate) struct UiToast
+{
+	message: String,
+	until: Instant,
+	background: Plane<interfacePosition>,
+	text: Text,
+}
+
+impl UiToast
+{
+	pub(crate) fn new(message: String, duration: Duration) -> Self
+	{
+		return UiToast {
+			message,
+			until: Instant::now() + duration,
+			background: Plane::new(),
+			text: Text::new(),
+		};
+	}
+
+	pub(crate) fn isExpired(&self) -> bool
+	{
+		return Instant::now() >= self.until;
+	}
+
+	/// place the toast centered with its bottom at "bottom", return the top
+	pub(crate) fn place(&mut self, style: &UiStyle, bottom: f32) -> f32
+	{
+		let window = HGEMain::singleton().getWindowInfos();
+		let padding = style.padding;
+		let height = style.textSize.getInt() + padding * 2.0;
+		let width = style.textWidth(&self.message) + padding * 2.0;
+		let left = (window.widthF - width) / 2.0;
+
+		UiStyle::square(&mut self.background, [left, bottom - height, left + width, bottom], UITOAST_Z, style.background);
+		style.label(&mut self.text, &self.message, left + padding, bottom - height, bottom, UITOAST_Z + 1);
+		return bottom - height;
+	}
+
+	pub(crate) fn submit(&mut self)
+	{
+		if (self.background.cache_mustUpdate())
+		{
+			self.background.cache_submit();
+		}
+		if (self.text.cache_mustUpdate())
+		{
+			self.text.cache_submit();
+		}
+	}
+
+	pub(crate) fn remove(&mut self)
+	{
+		self.background.cache_remove();
+		self.text.cache_remove();
+	}
+
+	/// place all toasts from the bottom of the window, newest at the bottom
+	pub(crate) fn placeAll(toasts: &mut Vec<UiToast>, style: &UiStyle)
+	{
+		let mut bottom = HGEMain::singleton().getWindowInfos().heightF - UITOAST_MARGIN;
+		for toast in toasts.iter_mut().rev()
+		{
+			bottom = toast.place(style, bottom) - UITOAST_MARGIN;
+		}
+	}
+}
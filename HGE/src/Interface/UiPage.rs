@@ -1,4 +1,5 @@
 use crate::components::event::{event, event_trait, event_type};
+use crate::components::interfacePosition::interfacePosition;
 use crate::Interface::UiHitbox::UiHitbox;
 use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
 use downcast_rs::{impl_downcast, Downcast};
@@ -9,6 +10,7 @@ use std::any::Any;
 use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use uuid::Uuid;
 use HArcMut::HArcMut;
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
@@ -25,6 +27,62 @@ pub enum UiEvent
 	HOVER,
 }
 
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum UiTextKey
+{
+	BACKSPACE,
+	DELETE,
+	LEFT,
+	RIGHT,
+	HOME,
+	END,
+	SELECT_ALL,
+	COPY,
+	CUT,
+	PASTE,
+	/// enter key
+	SUBMIT,
+}
+
+/// text input sent to the focused content
+#[derive(Clone, Debug)]
+pub enum UiTextEvent
+{
+	/// typed or IME committed text
+	TEXT(String),
+	/// IME composition in progress, empty when finished
+	PREEDIT(String),
+	/// editing key, with shift pressed
+	KEY(UiTextKey, bool),
+}
+
+/// scrolling input sent to the content under the pointer, in window pixel
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum UiScrollEvent
+{
+	/// mouse wheel, positive values show what is below/right of the content
+	WHEEL([f32; 2]),
+	/// pointer (mouse or touch) pressed at this position
+	DRAG_START([f32; 2]),
+	/// pointer moved while pressed
+	DRAG_MOVE([f32; 2]),
+	/// pointer released
+	DRAG_END,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum UiFocus_direction
+{
+	UP,
+	DOWN,
+	LEFT,
+	RIGHT,
+	/// next content in the focus order
+	NEXT,
+	/// previous content in the focus order
+	PREV,
+}
+
 pub trait UiPageContent: DynClone + event_trait + ShaderDrawerImpl + Downcast
 {
 	fn getType(&self) -> UiPageContent_type
@@ -33,6 +91,54 @@ pub trait UiPageContent: DynClone + event_trait + ShaderDrawerImpl + Downcast
 	}
 
 	fn getHitbox(&self) -> UiHitbox;
+
+	/// called by layout containers to move the content inside a rectangle, do nothing by default
+	fn layout_place(&mut self, _leftTop: interfacePosition, _bottomRight: interfacePosition) {}
+
+	/// called by scrolling containers to hide everything outside [left, top, right, bottom] (window pixel), None to remove it
+	fn layout_clip(&mut self, _clip: Option<[f32; 4]>) {}
+
+	/// return true if the content can take the focus (keyboard/gamepad navigation)
+	fn isFocusable(&self) -> bool
+	{
+		return false;
+	}
+
+	/// receive keyboard text when focused, return true if used
+	fn event_text(&mut self, _event: &UiTextEvent) -> bool
+	{
+		return false;
+	}
+
+	/// receive wheel and drag scrolling, return true if used
+	fn event_scroll(&mut self, _event: &UiScrollEvent) -> bool
+	{
+		return false;
+	}
+
+	/// receive navigation directions when focused, return true if used (the focus then stay on the content)
+	fn event_navigate(&mut self, _direction: UiFocus_direction) -> bool
+	{
+		return false;
+	}
+
+	/// uuids of everything submitted by the content (itself and sub contents), used to transform a whole page
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return vec![self.cache_infos().getUuid()];
+	}
+
+	/// sub contents of containers, used by the focus navigation to reach nested contents
+	fn childs(&self) -> Vec<&dyn UiPageContent>
+	{
+		return vec![];
+	}
+
+	/// same as childs, in the same order
+	fn childs_mut(&mut self) -> Vec<&mut dyn UiPageContent>
+	{
+		return vec![];
+	}
 }
 
 dyn_clone::clone_trait_object!(UiPageContent);
@@ -43,6 +149,9 @@ pub struct UiPage
 {
 	_content: BTreeMap<String, HArcMut<Box<dyn UiPageContent + Sync + Send>>>,
 	_events: event<UiPage>,
+	_focusOrder: Vec<String>,
+	_focused: Option<String>,
+	_dragTarget: Option<String>,
 }
 
 impl UiPage
@@ -52,13 +161,22 @@ impl UiPage
 		return UiPage {
 			_content: BTreeMap::new(),
 			_events: event::new(),
+			_focusOrder: Vec::new(),
+			_focused: None,
+			_dragTarget: None,
 		};
 	}
 
 	pub fn add(&mut self, name: impl Into<String>, content: impl UiPageContent + Any + Clone + Sync + Send + 'static) -> HArcMut<Box<dyn UiPageContent + Sync + Send>>
 	{
-		let name: String = name.into();
 		let content: Box<dyn UiPageContent + Sync + Send> = Box::new(content); // need to be explicit
+		return self.addBoxed(name, content);
+	}
+
+	/// same as add, for a content already boxed (built at runtime, like by UiMarkup)
+	pub fn addBoxed(&mut self, name: impl Into<String>, content: Box<dyn UiPageContent + Sync + Send>) -> HArcMut<Box<dyn UiPageContent + Sync + Send>>
+	{
+		let name: String = name.into();
 		let returning = HArcMut::new(content);
 		if let Some(oldone) = self._content.insert(name, returning.clone())
 		{
@@ -72,6 +190,14 @@ impl UiPage
 	pub fn remove(&mut self, name: impl Into<String>)
 	{
 		let name = name.into();
+		if (self._focused.as_ref().map(|x| *x == name || x.starts_with(&format!("{}/", name))).unwrap_or(false))
+		{
+			self._focused = None;
+		}
+		if (self._dragTarget.as_ref() == Some(&name))
+		{
+			self._dragTarget = None;
+		}
 		if let Some(entity) = self._content.remove(&name)
 		{
 			entity.get_mut().cache_remove();
@@ -130,6 +256,206 @@ impl UiPage
 		return haveClicked.load(Ordering::Relaxed);
 	}
 
+	/// define the order used by NEXT/PREV navigation, with content names
+	/// by default, focusable contents are sorted from top to bottom, then left to right
+	pub fn focusOrder_set(&mut self, order: Vec<String>)
+	{
+		self._focusOrder = order;
+	}
+
+	/// name of the focused content, contents nested in containers are named "container/index" (one index per level)
+	pub fn focus_get(&self) -> Option<String>
+	{
+		return self._focused.clone();
+	}
+
+	/// give the focus to a content (see focus_get for nested contents), the old one receive BLUR and the new one FOCUS
+	pub fn focus_set(&mut self, name: impl Into<String>) -> bool
+	{
+		let name = name.into();
+		if (self._focused.as_ref() == Some(&name))
+		{
+			return false;
+		}
+
+		if (!self.focusWalkAll().iter().any(|(key, _)| *key == name))
+		{
+			return false;
+		}
+
+		self.focus_clear();
+		self.contentUpdate(&name, |i| i.event_trigger(event_type::FOCUS));
+		self._focused = Some(name);
+		return true;
+	}
+
+	pub fn focus_clear(&mut self)
+	{
+		if let Some(oldone) = self._focused.take()
+		{
+			self.contentUpdate(&oldone, |i| i.event_trigger(event_type::BLUR));
+		}
+	}
+
+	/// move the focus, the first navigation focus the first content of the focus order
+	/// the focused content can use the direction instead (like a slider changing its value)
+	pub fn eventNavigate(&mut self, direction: UiFocus_direction) -> bool
+	{
+		if let Some(focused) = self._focused.clone()
+		{
+			if (self.contentUpdate(&focused, |i| i.event_navigate(direction)))
+			{
+				return true;
+			}
+		}
+
+		let candidates = self.focusCandidates();
+		let order = self.focusOrder(&candidates);
+		if (order.is_empty())
+		{
+			return false;
+		}
+
+		let Some((currentName, currentCenter)) = self._focused.as_ref().and_then(|name| candidates.iter().find(|x| &x.0 == name).cloned())
+		else
+		{
+			return self.focus_set(order[0].clone());
+		};
+
+		let next = match direction
+		{
+			UiFocus_direction::NEXT | UiFocus_direction::PREV =>
+			{
+				let len = order.len();
+				match order.iter().position(|x| *x == currentName)
+				{
+					None => order[0].clone(),
+					Some(i) if direction == UiFocus_direction::NEXT => order[(i + 1) % len].clone(),
+					Some(i) => order[(i + len - 1) % len].clone(),
+				}
+			}
+			_ =>
+			{
+				let Some(name) = Self::focusSpatial(&candidates, &currentName, currentCenter, direction)
+				else
+				{
+					return false;
+				};
+				name
+			}
+		};
+
+		return self.focus_set(next);
+	}
+
+	/// give the focus to the focusable content under the mouse, or remove it
+	pub fn focus_atPosition(&mut self, x: u16, y: u16)
+	{
+		let found = self.focusWalkAll().into_iter().find(|(_, hitbox)| hitbox.isInside(x, y));
+
+		match found.map(|(name, _)| name)
+		{
+			None => self.focus_clear(),
+			Some(name) =>
+			{
+				self.focus_set(name);
+			}
+		}
+	}
+
+	/// send text input to the focused content, return true if it was used
+	pub fn eventText(&self, event: &UiTextEvent) -> bool
+	{
+		let Some(focused) = &self._focused
+		else
+		{
+			return false;
+		};
+
+		return self.contentUpdate(focused, |i| i.event_text(event));
+	}
+
+	/// send scrolling to the content under the pointer, a drag stay on the content that accepted its start
+	pub fn eventScroll(&mut self, x: u16, y: u16, event: &UiScrollEvent) -> bool
+	{
+		let event = *event;
+		match event
+		{
+			UiScrollEvent::WHEEL(_) | UiScrollEvent::DRAG_START(_) =>
+			{
+				let used = Arc::new(AtomicBool::new(false));
+				let mut target = None;
+				for (name, elem) in self._content.iter()
+				{
+					{
+						let tmp = elem.get();
+						if (tmp.getType() != UiPageContent_type::INTERACTIVE || !tmp.getHitbox().isInside(x, y))
+						{
+							continue;
+						}
+					}
+
+					let sub_used = used.clone();
+					elem.updateIf(move |i| {
+						let eventok = i.event_scroll(&event);
+						sub_used.store(eventok, Ordering::Relaxed);
+						eventok
+					});
+					if (used.load(Ordering::Relaxed))
+					{
+						target = Some(name.clone());
+						break;
+					}
+				}
+
+				if let UiScrollEvent::DRAG_START(_) = event
+				{
+					self._dragTarget = target;
+				}
+				return used.load(Ordering::Relaxed);
+			}
+			UiScrollEvent::DRAG_MOVE(_) | UiScrollEvent::DRAG_END =>
+			{
+				let target = match event
+				{
+					UiScrollEvent::DRAG_END => self._dragTarget.take(),
+					_ => self._dragTarget.clone(),
+				};
+				let Some(elem) = target.and_then(|x| self._content.get(&x))
+				else
+				{
+					return false;
+				};
+
+				let used = Arc::new(AtomicBool::new(false));
+				let sub_used = used.clone();
+				elem.updateIf(move |i| {
+					let eventok = i.event_scroll(&event);
+					sub_used.store(eventok, Ordering::Relaxed);
+					eventok
+				});
+				return used.load(Ordering::Relaxed);
+			}
+		}
+	}
+
+	/// send CLICKED to the focused content, return true if it was used
+	pub fn eventActivate(&self) -> bool
+	{
+		let Some(focused) = &self._focused
+		else
+		{
+			return false;
+		};
+
+		return self.contentUpdate(focused, |i| {
+			let eventok = i.event_trigger(event_type::CLICKED);
+			// no mouse release will come, go back to idle to allow the next activation
+			i.event_trigger(event_type::IDLE);
+			eventok
+		});
+	}
+
 	pub fn subevent_trigger(&self, eventtype: event_type)
 	{
 		for (_, content) in self._content.iter().filter(|(_, elem)| {
@@ -154,6 +480,12 @@ impl UiPage
 			});
 	}
 
+	/// uuids of all contents, see UiPageContent::cache_uuids
+	pub fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return self._content.values().flat_map(|elem| elem.get().cache_uuids()).collect();
+	}
+
 	pub fn cache_remove(&self)
 	{
 		self._content.iter().for_each(|(_, elem)| {
@@ -185,6 +517,149 @@ impl UiPage
 	}
 }
 
+impl UiPage
+{
+	///////////////// PRIVATE ////////////////
+
+	// focusable and drawn contents, with the center of their hitbox
+	fn focusCandidates(&self) -> Vec<(String, [f32; 2])>
+	{
+		self.focusWalkAll()
+			.into_iter()
+			.filter(|(_, hitbox)| !hitbox.isEmpty())
+			.map(|(name, hitbox)| {
+				let hitbox = hitbox.getRawWithWH();
+				(name, [hitbox[0] + hitbox[2] / 2.0, hitbox[1] + hitbox[3] / 2.0])
+			})
+			.collect()
+	}
+
+	// every focusable content, nested ones included, with their hitbox
+	fn focusWalkAll(&self) -> Vec<(String, UiHitbox)>
+	{
+		let mut found = vec![];
+		self._content.iter().for_each(|(name, elem)| {
+			let tmp = elem.get();
+			Self::focusWalk(&***tmp, name.clone(), &mut found);
+		});
+		return found;
+	}
+
+	fn focusWalk(content: &dyn UiPageContent, name: String, found: &mut Vec<(String, UiHitbox)>)
+	{
+		if (content.isFocusable())
+		{
+			found.push((name.clone(), content.getHitbox()));
+		}
+		for (index, child) in content.childs().into_iter().enumerate()
+		{
+			Self::focusWalk(child, format!("{}/{}", name, index), found);
+		}
+	}
+
+	// run "func" on a content named like in focus_get, return its result
+	fn contentUpdate(&self, name: &String, mut func: impl FnMut(&mut dyn UiPageContent) -> bool) -> bool
+	{
+		let Some((root, path)) = self.contentResolve(name)
+		else
+		{
+			return false;
+		};
+		let Some(elem) = self._content.get(&root)
+		else
+		{
+			return false;
+		};
+
+		let mut returning = false;
+		elem.updateIf(|i| {
+			let content: &mut dyn UiPageContent = &mut **i;
+			returning = match Self::childFromPath(content, &path)
+			{
+				Some(child) => func(child),
+				None => false,
+			};
+			returning
+		});
+		return returning;
+	}
+
+	// split a name like in focus_get into the page content name and the child index of each level
+	fn contentResolve(&self, name: &String) -> Option<(String, Vec<usize>)>
+	{
+		if (self._content.contains_key(name))
+		{
+			return Some((name.clone(), vec![]));
+		}
+
+		return self._content.keys().find_map(|root| {
+			let path = name.strip_prefix(root.as_str())?.strip_prefix('/')?;
+			let path: Option<Vec<usize>> = path.split('/').map(|x| x.parse().ok()).collect();
+			path.map(|path| (root.clone(), path))
+		});
+	}
+
+	fn childFromPath<'a>(content: &'a mut dyn UiPageContent, path: &[usize]) -> Option<&'a mut dyn UiPageContent>
+	{
+		let Some((first, others)) = path.split_first()
+		else
+		{
+			return Some(content);
+		};
+
+		let child = content.childs_mut().into_iter().nth(*first)?;
+		return Self::childFromPath(child, others);
+	}
+
+	fn focusOrder(&self, candidates: &Vec<(String, [f32; 2])>) -> Vec<String>
+	{
+		if (!self._focusOrder.is_empty())
+		{
+			return self._focusOrder.iter().filter(|x| candidates.iter().any(|(name, _)| name == *x)).cloned().collect();
+		}
+
+		let mut sorted = candidates.clone();
+		sorted.sort_by(|a, b| a.1[1].total_cmp(&b.1[1]).then(a.1[0].total_cmp(&b.1[0])));
+		return sorted.into_iter().map(|x| x.0).collect();
+	}
+
+	// nearest content in the direction, a content aligned with the current one is preferred
+	fn focusSpatial(candidates: &Vec<(String, [f32; 2])>, currentName: &String, currentCenter: [f32; 2], direction: UiFocus_direction) -> Option<String>
+	{
+		let mut best: Option<(f32, String)> = None;
+		for (name, center) in candidates.iter()
+		{
+			if (name == currentName)
+			{
+				continue;
+			}
+
+			let dx = center[0] - currentCenter[0];
+			let dy = center[1] - currentCenter[1];
+			let (main, cross) = match direction
+			{
+				UiFocus_direction::UP => (-dy, dx),
+				UiFocus_direction::DOWN => (dy, dx),
+				UiFocus_direction::LEFT => (-dx, dy),
+				UiFocus_direction::RIGHT => (dx, dy),
+				UiFocus_direction::NEXT | UiFocus_direction::PREV => return None,
+			};
+			if (main <= 0.0)
+			{
+				continue;
+			}
+
+			let score = main + cross.abs() * 2.0;
+			if (best.as_ref().map(|x| score < x.0).unwrap_or(true))
+			{
+				best = Some((score, name.clone()));
+			}
+		}
+
+		return best.map(|x| x.1);
+	}
+}
+
 impl event_trait for UiPage
 {
 	fn event_trigger(&mut self, event_type: event_type) -> bool
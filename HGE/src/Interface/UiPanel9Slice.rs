@@ -0,0 +1,354 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::color::{color, colorBlend};
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::interfacePosition::interfacePosition;
+use crate::Interface::UiButton::UiButton_content;
+use crate::Interface::UiHidable::UiHidable_content;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
+use crate::Interface::UiStyle::UiStyle;
+use crate::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dsimple_def, HGE_shader_2Dsimple_holder};
+use crate::Shaders::ShaderDrawer::ShaderDrawer_Manager;
+use crate::Shaders::ShaderDrawerImpl::{ShaderDrawerImpl, ShaderDrawerImplReturn, ShaderDrawerImplStruct};
+use crate::Textures::Manager::ManagerTexture;
+use crate::Textures::Textures::Texture_part;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UiPanel9Slice_mode
+{
+	/// edges and center are stretched to fill the panel
+	STRETCH,
+	/// edges and center are repeated at their texture size, the last tile is cut
+	TILE,
+}
+
+/// textured panel cut in 9 parts : the corners keep their pixel size, edges and center fill the rest
+/// the texture can be a whole texture, a "texture#part" loaded by a texturePart, or an explicit Texture_part
+#[derive(Clone)]
+pub struct UiPanel9Slice
+{
+	_position: [interfacePosition; 2],
+	_texture: Option<String>,
+	_part: Option<Texture_part>,
+	_insets: [f32; 4],
+	_scale: f32,
+	_mode: UiPanel9Slice_mode,
+	_color: color,
+	_clip: Option<[f32; 4]>,
+	_hitbox: UiHitbox,
+	_events: event<UiPanel9Slice>,
+	_cacheinfos: cacheInfos,
+}
+
+impl UiPanel9Slice
+{
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition) -> Self
+	{
+		return UiPanel9Slice {
+			_position: [leftTop, bottomRight],
+			_texture: None,
+			_part: None,
+			_insets: [0.0; 4],
+			_scale: 1.0,
+			_mode: UiPanel9Slice_mode::STRETCH,
+			_color: color::default(),
+			_clip: None,
+			_hitbox: UiHitbox::new(),
+			_events: event::new(),
+			_cacheinfos: cacheInfos::default(),
+		};
+	}
+
+	/// z depend of leftTop
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// texture name as in ManagerTexture, "name#part" use a part loaded with texture_loadPart
+	pub fn setTexture(&mut self, name: impl Into<String>)
+	{
+		self._texture = Some(name.into());
+		self._part = None;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// use an explicit part of a texture (for example from texturePart_FromCSV)
+	pub fn setTexturePart(&mut self, name: impl Into<String>, part: Texture_part)
+	{
+		self._texture = Some(name.into());
+		self._part = Some(part);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// borders [left, top, right, bottom] in texture pixel
+	pub fn setInsets(&mut self, insets: [f32; 4])
+	{
+		self._insets = insets.map(|x| x.max(0.0));
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// size of one texture pixel on screen, for the corners and tiles
+	pub fn setScale(&mut self, scale: f32)
+	{
+		self._scale = scale.max(0.0);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setMode(&mut self, mode: UiPanel9Slice_mode)
+	{
+		self._mode = mode;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// tint multiplied with the texture
+	pub fn setColor(&mut self, newcolor: color)
+	{
+		self._color = newcolor;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// texture and insets of a UiStyle (texture, textureSlice), the color stay unchanged
+	pub fn setStyle(&mut self, style: &UiStyle)
+	{
+		if let Some(texture) = &style.texture
+		{
+			self.setTexture(texture.clone());
+		}
+		self.setInsets(style.textureSlice);
+	}
+
+	pub fn boxed(self) -> Box<UiPanel9Slice>
+	{
+		return Box::new(self);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// texture name without part, and the part used (the whole texture if no part)
+	fn source(&self) -> Option<(String, Texture_part)>
+	{
+		let fullname = self._texture.as_ref()?;
+		if let Some(part) = self._part
+		{
+			let name = fullname.split_once('#').map(|(name, _)| name).unwrap_or(fullname);
+			return Some((name.to_string(), part));
+		}
+		return ManagerTexture::singleton().getPartFromName(fullname);
+	}
+
+	// split one axis in spans (dest start, dest end, texture start, texture end), texture in 0.0-1.0 of the part
+	fn spans(&self, start: f32, end: f32, insetStart: f32, insetEnd: f32, dim: f32) -> Vec<[f32; 4]>
+	{
+		let dim = dim.max(1.0);
+		let insetStart = insetStart.min(dim);
+		let insetEnd = insetEnd.min(dim - insetStart);
+		let mut borderStart = insetStart * self._scale;
+		let mut borderEnd = insetEnd * self._scale;
+		// panel smaller than its borders : shrink the corners
+		let length = (end - start).max(0.0);
+		if (borderStart + borderEnd > length && borderStart + borderEnd > 0.0)
+		{
+			let ratio = length / (borderStart + borderEnd);
+			borderStart *= ratio;
+			borderEnd *= ratio;
+		}
+
+		let middleStart = insetStart / dim;
+		let middleEnd = 1.0 - insetEnd / dim;
+		let mut spans = vec![[start, start + borderStart, 0.0, middleStart]];
+
+		let tile = (middleEnd - middleStart) * dim * self._scale;
+		let mut pos = start + borderStart;
+		let middleDest = end - borderEnd;
+		if (self._mode == UiPanel9Slice_mode::TILE && tile >= 1.0)
+		{
+			while (pos < middleDest)
+			{
+				let next = (pos + tile).min(middleDest);
+				spans.push([pos, next, middleStart, middleStart + (middleEnd - middleStart) * (next - pos) / tile]);
+				pos = next;
+			}
+		}
+		else
+		{
+			spans.push([pos, middleDest, middleStart, middleEnd]);
+		}
+
+		spans.push([middleDest, end, middleEnd, 1.0]);
+		spans.retain(|x| x[1] > x[0]);
+		return spans;
+	}
+}
+
+impl event_trait for UiPanel9Slice
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut update = self._events.clone().trigger(eventtype, self);
+		if (eventtype == event_type::WINREFRESH)
+		{
+			self._cacheinfos.setNeedUpdate(true);
+			update = true;
+		}
+		if (self._cacheinfos.isPresent() && update)
+		{
+			self.cache_submit();
+		}
+		return update;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::WINREFRESH => true,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiPanel9Slice> for UiPanel9Slice
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiPanel9Slice) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl ShaderDrawerImpl for UiPanel9Slice
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		self._cacheinfos.isNotShow()
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		// texture not loaded yet : stay in "need update" to try again later
+		let Some(structure) = self.cache_get()
+		else
+		{
+			self.cache_remove();
+			return;
+		};
+
+		let tmp = self._cacheinfos;
+		if (!ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
+			holder.insert(tmp, structure);
+		}))
+		{
+			return;
+		}
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		let tmp = self._cacheinfos;
+		ShaderDrawer_Manager::inspect::<HGE_shader_2Dsimple_holder>(move |holder| {
+			holder.remove(tmp);
+		});
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl ShaderDrawerImplReturn<HGE_shader_2Dsimple_def> for UiPanel9Slice
+{
+	fn cache_get(&mut self) -> Option<ShaderDrawerImplStruct<HGE_shader_2Dsimple_def>>
+	{
+		let (texturename, part) = self.source()?;
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+
+		let columns = self.spans(leftTop[0], bottomRight[0], self._insets[0], self._insets[2], part.dim[0] as f32);
+		let rows = self.spans(leftTop[1], bottomRight[1], self._insets[1], self._insets[3], part.dim[1] as f32);
+		let [[uvLeft, uvTop], [uvRight, uvBottom]] = part.uvcoord;
+
+		let mut vertex = Vec::new();
+		let mut indices = Vec::new();
+		for row in rows.iter()
+		{
+			for column in columns.iter()
+			{
+				let first = vertex.len() as u32;
+				for (x, y, u, v) in [
+					(column[0], row[0], column[2], row[2]),
+					(column[1], row[0], column[3], row[2]),
+					(column[0], row[1], column[2], row[3]),
+					(column[1], row[1], column[3], row[3]),
+				]
+				{
+					let pos = interfacePosition::new_pixel_z(x.round() as i32, y.round() as i32, z);
+					vertex.push(HGE_shader_2Dsimple_def {
+						position: pos.convertToVertex(),
+						ispixel: pos.getTypeInt(),
+						texture: Some(texturename.clone()),
+						uvcoord: [uvLeft + (uvRight - uvLeft) * u, uvTop + (uvBottom - uvTop) * v],
+						color: self._color.toArray(),
+						color_blend_type: colorBlend::MUL.toU32(),
+						clip: self._clip.unwrap_or([0.0; 4]),
+					});
+				}
+				indices.extend([0, 1, 2, 1, 3, 2].map(|x| first + x));
+			}
+		}
+
+		return Some(ShaderDrawerImplStruct {
+			vertex,
+			indices,
+		});
+	}
+}
+
+impl UiPageContent for UiPanel9Slice
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		if (self._events.haveOneEvent())
+		{
+			return UiPageContent_type::INTERACTIVE;
+		}
+		return UiPageContent_type::IDLE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		if (self._clip != clip)
+		{
+			self._clip = clip;
+			self._cacheinfos.setNeedUpdate(true);
+		}
+	}
+}
+
+impl UiHidable_content for UiPanel9Slice {}
+impl UiButton_content for UiPanel9Slice {}
@@ -0,0 +1,456 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::Text::Text;
+use crate::Interface::UiButton::UiButtonState;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type};
+use crate::Interface::UiStyle::{UiStyle, UiStyle_themed};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+struct UiRadioGroup_item
+{
+	label: String,
+	boxPlane: Plane<interfacePosition>,
+	markPlane: Plane<interfacePosition>,
+	text: Text,
+}
+
+/// list of exclusive options, one per row from top to bottom
+#[derive(Clone)]
+pub struct UiRadioGroup
+{
+	_position: [interfacePosition; 2],
+	_items: Vec<UiRadioGroup_item>,
+	_selected: Option<usize>,
+	_hovered: Option<usize>,
+	_pressed: bool,
+	_focused: bool,
+	_style: UiStyle_themed,
+	_focusPlane: Plane<interfacePosition>,
+	_changedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiRadioGroup) + Send + Sync>>>>,
+	_clip: Option<[f32; 4]>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_events: event<UiRadioGroup>,
+}
+
+impl UiRadioGroup
+{
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition, options: Vec<String>) -> Self
+	{
+		let mut tmp = UiRadioGroup {
+			_position: [leftTop, bottomRight],
+			_items: Vec::new(),
+			_selected: None,
+			_hovered: None,
+			_pressed: false,
+			_focused: false,
+			_style: UiStyle_themed::new(),
+			_focusPlane: Plane::new(),
+			_changedFn: Arc::new(RwLock::new(None)),
+			_clip: None,
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_events: event::new(),
+		};
+		tmp.setOptions(options);
+		return tmp;
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// replace all options, the selection is kept if still in range
+	pub fn setOptions(&mut self, options: Vec<String>)
+	{
+		self._items.iter_mut().for_each(|x| {
+			x.boxPlane.cache_remove();
+			x.markPlane.cache_remove();
+			x.text.cache_remove();
+		});
+		self._items = options
+			.into_iter()
+			.map(|label| UiRadioGroup_item {
+				label,
+				boxPlane: Plane::new(),
+				markPlane: Plane::new(),
+				text: Text::new(),
+			})
+			.collect();
+		self._selected = self._selected.filter(|x| *x < self._items.len());
+		self._hovered = None;
+		let clip = self._clip;
+		self.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// fixed style, stop following a UiTheme class
+	pub fn setStyle(&mut self, style: UiStyle)
+	{
+		self._style.set(style);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getStyle(&self) -> &UiStyle
+	{
+		return &self._style;
+	}
+
+	/// follow a UiTheme class, the style is reloaded when the theme change
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		self._style.setClass(class);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// change the selection without calling the changed function
+	pub fn setSelected(&mut self, index: Option<usize>)
+	{
+		self._selected = index.filter(|x| *x < self._items.len());
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getSelected(&self) -> Option<usize>
+	{
+		return self._selected;
+	}
+
+	pub fn getSelectedLabel(&self) -> Option<&String>
+	{
+		return self._selected.map(|x| &self._items[x].label);
+	}
+
+	/// called when the user change the selection
+	pub fn setChangedFn(&mut self, func: impl FnMut(&mut UiRadioGroup) + Send + Sync + 'static)
+	{
+		*self._changedFn.write() = Some(Box::new(func));
+	}
+
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
+	pub fn boxed(self) -> Box<UiRadioGroup>
+	{
+		return Box::new(self);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn callChanged(&mut self)
+	{
+		let selfbinding = self._changedFn.clone();
+		let mut binding = selfbinding.write();
+		if let Some(func) = binding.as_mut()
+		{
+			func(self);
+		}
+	}
+
+	// option under the mouse
+	fn itemAtMouse(&self) -> Option<usize>
+	{
+		let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+		if (self._items.is_empty() || !self._hitbox.isInside(mousex, mousey))
+		{
+			return None;
+		}
+
+		let top = self._position[0].convertToPixel()[1];
+		let bottom = self._position[1].convertToPixel()[1];
+		let rowHeight = (bottom - top) / self._items.len() as f32;
+		let index = ((mousey as f32 - top) / rowHeight).floor().max(0.0) as usize;
+		return Some(index.min(self._items.len() - 1));
+	}
+
+	fn refreshDisplay(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+		let border = self._style.border;
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+		UiStyle::square(
+			&mut self._focusPlane,
+			[leftTop[0] - border, leftTop[1] - border, bottomRight[0] + border, bottomRight[1] + border],
+			z,
+			self._style.focus,
+		);
+
+		let rowHeight = (bottomRight[1] - leftTop[1]) / self._items.len().max(1) as f32;
+		let padding = self._style.padding;
+		for (i, item) in self._items.iter_mut().enumerate()
+		{
+			let top = leftTop[1] + rowHeight * i as f32;
+			let boxSize = rowHeight - padding * 2.0;
+			let boxRect = [leftTop[0] + padding, top + padding, leftTop[0] + padding + boxSize, top + padding + boxSize];
+
+			let mut state = UiButtonState::IDLE;
+			if (self._hovered == Some(i))
+			{
+				state = UiButtonState::HOVER;
+				if (self._pressed)
+				{
+					state = UiButtonState::PRESSED;
+				}
+			}
+			UiStyle::square(&mut item.boxPlane, boxRect, z + 1, self._style.backgroundFor(state));
+			UiStyle::square(
+				&mut item.markPlane,
+				[boxRect[0] + padding, boxRect[1] + padding, boxRect[2] - padding, boxRect[3] - padding],
+				z + 2,
+				self._style.foreground,
+			);
+			self._style.label(&mut item.text, &item.label, boxRect[2] + padding * 2.0, top, top + rowHeight, z + 1);
+		}
+	}
+}
+
+impl event_trait for UiRadioGroup
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returning = self._events.clone().trigger(eventtype, self);
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER =>
+			{
+				let hovered = match eventtype
+				{
+					event_type::HOVER => self.itemAtMouse(),
+					_ => None,
+				};
+				if (self._hovered != hovered || self._pressed)
+				{
+					self._hovered = hovered;
+					self._pressed = false;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::CLICKED =>
+			{
+				if (!self._pressed && !self._items.is_empty())
+				{
+					self._pressed = true;
+					self._hovered = self.itemAtMouse();
+					// without the mouse (keyboard/gamepad activation), select the next option
+					let selected = match self._hovered
+					{
+						Some(index) => index,
+						None => self._selected.map(|x| (x + 1) % self._items.len()).unwrap_or(0),
+					};
+					if (self._selected != Some(selected))
+					{
+						self._selected = Some(selected);
+						self.callChanged();
+					}
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self._style.refresh();
+				self._items.iter_mut().for_each(|x| {
+					x.text.event_trigger(eventtype);
+				});
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			_ =>
+			{}
+		}
+
+		if (self._cacheinfos.isPresent() && returning)
+		{
+			self.cache_submit();
+		}
+		return returning;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::IDLE => true,
+			event_type::HOVER => true,
+			event_type::CLICKED => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
+			event_type::WINREFRESH => true,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiRadioGroup> for UiRadioGroup
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiRadioGroup) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl hideable for UiRadioGroup
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiRadioGroup
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self._items.iter().any(|x| x.text.cache_mustUpdate())
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		self.refreshDisplay();
+		if (self._focused)
+		{
+			self._focusPlane.cache_submit();
+		}
+		else
+		{
+			self._focusPlane.cache_remove();
+		}
+		let selected = self._selected;
+		for (i, item) in self._items.iter_mut().enumerate()
+		{
+			item.boxPlane.cache_submit();
+			if (selected == Some(i))
+			{
+				item.markPlane.cache_submit();
+			}
+			else
+			{
+				item.markPlane.cache_remove();
+			}
+			item.text.cache_submit();
+		}
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._focusPlane.cache_remove();
+		self._items.iter_mut().for_each(|x| {
+			x.boxPlane.cache_remove();
+			x.markPlane.cache_remove();
+			x.text.cache_remove();
+		});
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiRadioGroup
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		self._clip = clip;
+		self._focusPlane.layout_clip(clip);
+		self._items.iter_mut().for_each(|x| {
+			x.boxPlane.layout_clip(clip);
+			x.markPlane.layout_clip(clip);
+			x.text.layout_clip(clip);
+		});
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		let mut uuids = vec![self._focusPlane.cache_infos().getUuid()];
+		for item in self._items.iter()
+		{
+			uuids.extend([item.boxPlane.cache_infos().getUuid(), item.markPlane.cache_infos().getUuid(), item.text.cache_infos().getUuid()]);
+		}
+		return uuids;
+	}
+}
@@ -0,0 +1,593 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::Interface::Bar::{Bar, Bar_orientation};
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type, UiScrollEvent};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+// under this speed (pixel per second), inertia stop
+const INERTIA_MIN_SPEED: f32 = 10.0;
+// a drag released after this pause don't keep any inertia
+const INERTIA_RELEASE_DELAY: Duration = Duration::from_millis(100);
+
+/// container showing a part of a bigger content, scrolled with mouse wheel, drag or touch (with inertia)
+/// the content is moved with layout_place and clipped to the view
+#[derive(Clone)]
+pub struct UiScrollView
+{
+	_position: [interfacePosition; 2],
+	_content: Option<Box<dyn UiPageContent + Send + Sync>>,
+	_contentSize: interfacePosition,
+	_axis: [bool; 2],
+	_scroll: [f32; 2],
+	_scrollMax: [f32; 2],
+	_velocity: [f32; 2],
+	_friction: f32,
+	_lastTick: Instant,
+	_dragLast: Option<([f32; 2], Instant)>,
+	_scrollbar: Option<Bar>,
+	_scrollbarWidth: f32,
+	_clip: Option<[f32; 4]>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_event: event<Self>,
+}
+
+impl UiScrollView
+{
+	/// vertical scrolling by default
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition) -> Self
+	{
+		return UiScrollView {
+			_position: [leftTop, bottomRight],
+			_content: None,
+			_contentSize: interfacePosition::new_pixel(0, 0),
+			_axis: [false, true],
+			_scroll: [0.0, 0.0],
+			_scrollMax: [0.0, 0.0],
+			_velocity: [0.0, 0.0],
+			_friction: 4.0,
+			_lastTick: Instant::now(),
+			_dragLast: None,
+			_scrollbar: None,
+			_scrollbarWidth: 0.0,
+			_clip: None,
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_event: event::new(),
+		};
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// set the scrolled content, size x as width and y as height (percent are relative to the window)
+	/// a size of 0 use the view size on this axis
+	pub fn setContent(&mut self, content: impl UiPageContent + Send + Sync + 'static, size: interfacePosition)
+	{
+		self.setContentBoxed(Box::new(content), size);
+	}
+
+	/// same as setContent, for a content already boxed
+	pub fn setContentBoxed(&mut self, content: Box<dyn UiPageContent + Send + Sync>, size: interfacePosition)
+	{
+		if let Some(oldcontent) = &mut self._content
+		{
+			oldcontent.cache_remove();
+		}
+		self._content = Some(content);
+		self._contentSize = size;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setContentSize(&mut self, size: interfacePosition)
+	{
+		self._contentSize = size;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn content_mut(&mut self) -> Option<&mut Box<dyn UiPageContent + Send + Sync>>
+	{
+		self._cacheinfos.setNeedUpdate(true);
+		self._content.as_mut()
+	}
+
+	/// allowed scrolling axis
+	pub fn setAxis(&mut self, horizontal: bool, vertical: bool)
+	{
+		self._axis = [horizontal, vertical];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// inertia decay per second, higher stop faster (0 = never stop)
+	pub fn setFriction(&mut self, friction: f32)
+	{
+		self._friction = friction.max(0.0);
+	}
+
+	/// show a bar on the right border (bottom border if only horizontal), width in pixel
+	/// bar progress show the part of the content already seen
+	pub fn setScrollbar(&mut self, mut bar: Bar, width: f32)
+	{
+		if let Some(oldbar) = &mut self._scrollbar
+		{
+			oldbar.cache_remove();
+		}
+		if (self._axis[1])
+		{
+			bar.setOrientation(Bar_orientation::VERTICAL);
+		}
+		else
+		{
+			bar.setOrientation(Bar_orientation::HORIZONTAL);
+		}
+		self._scrollbar = Some(bar);
+		self._scrollbarWidth = width.max(0.0);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn removeScrollbar(&mut self)
+	{
+		if let Some(mut oldbar) = self._scrollbar.take()
+		{
+			oldbar.cache_remove();
+		}
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// scroll offset in pixel, clamped to the content size on the next update
+	pub fn scrollTo(&mut self, x: f32, y: f32)
+	{
+		self._scroll = [x, y];
+		self._velocity = [0.0, 0.0];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getScroll(&self) -> [f32; 2]
+	{
+		return self._scroll;
+	}
+
+	/// maximum scroll offset, computed by layout_update
+	pub fn getScrollMax(&self) -> [f32; 2]
+	{
+		return self._scrollMax;
+	}
+
+	pub fn boxed(self) -> Box<UiScrollView>
+	{
+		return Box::new(self);
+	}
+
+	/// compute and apply content position and clipping, called automatically on submit when something changed
+	pub fn layout_update(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+		let view = [(bottomRight[0] - leftTop[0]).max(0.0), (bottomRight[1] - leftTop[1]).max(0.0)];
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+
+		let mut rect = [leftTop[0], leftTop[1], bottomRight[0], bottomRight[1]];
+		if let Some(parentclip) = self._clip
+		{
+			rect = [rect[0].max(parentclip[0]), rect[1].max(parentclip[1]), rect[2].min(parentclip[2]), rect[3].min(parentclip[3])];
+			// an empty clip would be seen as "no clip" by the shader
+			rect[2] = rect[2].max(rect[0] + 0.001);
+		}
+
+		let mut size = self._contentSize.convertToPixel();
+		for i in [0, 1]
+		{
+			if (size[i] <= 0.0)
+			{
+				size[i] = view[i];
+			}
+			self._scrollMax[i] = 0.0;
+			if (self._axis[i])
+			{
+				self._scrollMax[i] = (size[i] - view[i]).max(0.0);
+			}
+			self._scroll[i] = self._scroll[i].clamp(0.0, self._scrollMax[i]);
+		}
+
+		if let Some(content) = &mut self._content
+		{
+			let left = leftTop[0] - self._scroll[0];
+			let top = leftTop[1] - self._scroll[1];
+			content.layout_clip(Some(rect));
+			content.layout_place(
+				interfacePosition::new_pixel_z(left.round() as i32, top.round() as i32, z),
+				interfacePosition::new_pixel_z((left + size[0]).round() as i32, (top + size[1]).round() as i32, z),
+			);
+		}
+
+		let barAxis = if (self._axis[1]) { 1 } else { 0 };
+		let width = self._scrollbarWidth;
+		if let Some(bar) = &mut self._scrollbar
+		{
+			let barLeftTop = match barAxis
+			{
+				1 => [bottomRight[0] - width, leftTop[1]],
+				_ => [leftTop[0], bottomRight[1] - width],
+			};
+			bar.setSquare(
+				interfacePosition::new_pixel_z(barLeftTop[0].round() as i32, barLeftTop[1].round() as i32, z + 1),
+				interfacePosition::new_pixel_z(bottomRight[0].round() as i32, bottomRight[1].round() as i32, z + 1),
+			);
+			bar.layout_clip(self._clip);
+			bar.updateProgress((self._scroll[barAxis] + view[barAxis]) / size[barAxis].max(1.0));
+		}
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn scrollBy(&mut self, delta: [f32; 2]) -> bool
+	{
+		let mut moved = false;
+		for i in [0, 1]
+		{
+			if (!self._axis[i])
+			{
+				continue;
+			}
+			let newscroll = (self._scroll[i] + delta[i]).clamp(0.0, self._scrollMax[i]);
+			if (newscroll != self._scroll[i])
+			{
+				self._scroll[i] = newscroll;
+				moved = true;
+			}
+			else
+			{
+				// bumped into a border
+				self._velocity[i] = 0.0;
+			}
+		}
+
+		if (moved)
+		{
+			self._cacheinfos.setNeedUpdate(true);
+		}
+		return moved;
+	}
+
+	fn inertiaUpdate(&mut self) -> bool
+	{
+		let dt = self._lastTick.elapsed().as_secs_f32();
+		self._lastTick = Instant::now();
+		if (self._dragLast.is_some() || self._velocity == [0.0, 0.0])
+		{
+			return false;
+		}
+
+		let moved = self.scrollBy([self._velocity[0] * dt, self._velocity[1] * dt]);
+		let decay = (-self._friction * dt).exp();
+		for i in [0, 1]
+		{
+			self._velocity[i] *= decay;
+			if (self._velocity[i].abs() < INERTIA_MIN_SPEED)
+			{
+				self._velocity[i] = 0.0;
+			}
+		}
+		return moved;
+	}
+}
+
+impl event_trait_add<UiScrollView> for UiScrollView
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiScrollView) -> bool + Send + Sync + 'static)
+	{
+		self._event.add(eventtype, func);
+	}
+}
+
+impl event_trait for UiScrollView
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returned = self._event.clone().trigger(eventtype, self);
+
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER | event_type::CLICKED =>
+			{
+				// the page only know the view hitbox, the content decide with the mouse position
+				let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+				if let Some(content) = &mut self._content
+				{
+					if (content.getType() == UiPageContent_type::INTERACTIVE)
+					{
+						let mut subevent = event_type::IDLE;
+						if (eventtype != event_type::IDLE && content.getHitbox().isInside(mousex, mousey))
+						{
+							subevent = eventtype;
+						}
+						if (content.event_trigger(subevent))
+						{
+							returned = true;
+						}
+					}
+				}
+			}
+			event_type::EACH_TICK =>
+			{
+				if let Some(content) = &mut self._content
+				{
+					if (content.event_trigger(eventtype))
+					{
+						returned = true;
+					}
+				}
+				if (self.inertiaUpdate())
+				{
+					returned = true;
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				if let Some(content) = &mut self._content
+				{
+					content.event_trigger(eventtype);
+				}
+				self._cacheinfos.setNeedUpdate(true);
+				if (self._cacheinfos.isPresent())
+				{
+					self.cache_submit();
+				}
+				returned = true;
+			}
+			_ =>
+			{
+				if let Some(content) = &mut self._content
+				{
+					if (content.event_trigger(eventtype))
+					{
+						returned = true;
+					}
+				}
+			}
+		}
+
+		return returned;
+	}
+
+	fn event_have(&self, _eventtype: event_type) -> bool
+	{
+		true
+	}
+}
+
+impl hideable for UiScrollView
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiScrollView
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self._content.as_ref().map(|x| x.cache_mustUpdate()).unwrap_or(false)
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		if (self._cacheinfos.isNeedUpdate() || self._cacheinfos.isAbsent())
+		{
+			self.layout_update();
+		}
+
+		if let Some(content) = &mut self._content
+		{
+			if (content.cache_mustUpdate())
+			{
+				content.cache_submit();
+			}
+		}
+
+		let scrollMax = self._scrollMax;
+		if let Some(bar) = &mut self._scrollbar
+		{
+			if (scrollMax == [0.0, 0.0])
+			{
+				bar.cache_remove();
+			}
+			else if (bar.cache_mustUpdate())
+			{
+				bar.cache_submit();
+			}
+		}
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		if let Some(content) = &mut self._content
+		{
+			content.cache_remove();
+		}
+		if let Some(bar) = &mut self._scrollbar
+		{
+			bar.cache_remove();
+		}
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiScrollView
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		if (self._clip != clip)
+		{
+			self._clip = clip;
+			self._cacheinfos.setNeedUpdate(true);
+		}
+	}
+
+	fn event_scroll(&mut self, event: &UiScrollEvent) -> bool
+	{
+		if (self._hide)
+		{
+			return false;
+		}
+
+		// nested contents (another scroll view) get the event first, unless we are the one dragging
+		if let (None, Some(content)) = (self._dragLast, &mut self._content)
+		{
+			if (content.event_scroll(event))
+			{
+				return true;
+			}
+		}
+
+		let used = match *event
+		{
+			UiScrollEvent::WHEEL(mut delta) =>
+			{
+				// vertical wheel scroll an horizontal only view
+				if (self._axis[0] && !self._axis[1])
+				{
+					delta = [delta[0] + delta[1], 0.0];
+				}
+				self._velocity = [0.0, 0.0];
+				self.scrollBy(delta)
+			}
+			UiScrollEvent::DRAG_START(position) =>
+			{
+				if (self._scrollMax == [0.0, 0.0])
+				{
+					return false;
+				}
+				self._velocity = [0.0, 0.0];
+				self._dragLast = Some((position, Instant::now()));
+				true
+			}
+			UiScrollEvent::DRAG_MOVE(position) =>
+			{
+				let Some((last, lasttime)) = self._dragLast
+				else
+				{
+					return false;
+				};
+				let delta = [last[0] - position[0], last[1] - position[1]];
+				let dt = lasttime.elapsed().as_secs_f32().max(0.001);
+				for i in [0, 1]
+				{
+					// smoothed to avoid a jump from the last pointer event
+					self._velocity[i] = self._velocity[i] * 0.2 + (delta[i] / dt) * 0.8;
+				}
+				self._dragLast = Some((position, Instant::now()));
+				self.scrollBy(delta);
+				true
+			}
+			UiScrollEvent::DRAG_END =>
+			{
+				let Some((_, lasttime)) = self._dragLast.take()
+				else
+				{
+					return false;
+				};
+				if (lasttime.elapsed() > INERTIA_RELEASE_DELAY)
+				{
+					self._velocity = [0.0, 0.0];
+				}
+				self._lastTick = Instant::now();
+				true
+			}
+		};
+
+		if (self._cacheinfos.isPresent() && self._cacheinfos.isNeedUpdate())
+		{
+			self.cache_submit();
+		}
+		return used;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		let mut uuids = self._content.as_ref().map(|x| x.cache_uuids()).unwrap_or_default();
+		if let Some(scrollbar) = &self._scrollbar
+		{
+			uuids.extend(scrollbar.cache_uuids());
+		}
+		return uuids;
+	}
+
+	fn childs(&self) -> Vec<&dyn UiPageContent>
+	{
+		return self._content.iter().map(|x| &**x as &dyn UiPageContent).collect();
+	}
+
+	fn childs_mut(&mut self) -> Vec<&mut dyn UiPageContent>
+	{
+		return self._content.iter_mut().map(|x| &mut **x as &mut dyn UiPageContent).collect();
+	}
+}
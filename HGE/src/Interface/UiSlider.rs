@@ -0,0 +1,513 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::Bar::{Bar, Bar_state};
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::UiButton::UiButtonState;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiFocus_direction, UiPageContent, UiPageContent_type, UiScrollEvent};
+use crate::Interface::UiStyle::{UiStyle, UiStyle_themed};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// horizontal slider, the filled part is a Bar, the value is changed by click or drag
+#[derive(Clone)]
+pub struct UiSlider
+{
+	_position: [interfacePosition; 2],
+	_min: f32,
+	_max: f32,
+	_step: f32,
+	_value: f32,
+	_state: UiButtonState,
+	_focused: bool,
+	_dragging: bool,
+	_style: UiStyle_themed,
+	_focusPlane: Plane<interfacePosition>,
+	_trackPlane: Plane<interfacePosition>,
+	_bar: Bar,
+	_knobPlane: Plane<interfacePosition>,
+	_changedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiSlider) + Send + Sync>>>>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_events: event<UiSlider>,
+}
+
+impl UiSlider
+{
+	/// range 0.0 to 1.0 by default, without step
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition) -> Self
+	{
+		return UiSlider {
+			_position: [leftTop.clone(), bottomRight.clone()],
+			_min: 0.0,
+			_max: 1.0,
+			_step: 0.0,
+			_value: 0.0,
+			_state: UiButtonState::IDLE,
+			_focused: false,
+			_dragging: false,
+			_style: UiStyle_themed::new(),
+			_focusPlane: Plane::new(),
+			_trackPlane: Plane::new(),
+			_bar: Bar::new(leftTop, bottomRight),
+			_knobPlane: Plane::new(),
+			_changedFn: Arc::new(RwLock::new(None)),
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_events: event::new(),
+		};
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// value range, step of 0 mean no snapping
+	pub fn setRange(&mut self, min: f32, max: f32, step: f32)
+	{
+		self._min = min.min(max);
+		self._max = max.max(min);
+		self._step = step.max(0.0);
+		self._value = self.snap(self._value);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// change the value without calling the changed function
+	pub fn setValue(&mut self, value: f32)
+	{
+		self._value = self.snap(value);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getValue(&self) -> f32
+	{
+		return self._value;
+	}
+
+	/// fixed style, stop following a UiTheme class
+	pub fn setStyle(&mut self, style: UiStyle)
+	{
+		self._style.set(style);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getStyle(&self) -> &UiStyle
+	{
+		return &self._style;
+	}
+
+	/// follow a UiTheme class, the style is reloaded when the theme change
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		self._style.setClass(class);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// called when the user change the value (each move while dragging)
+	pub fn setChangedFn(&mut self, func: impl FnMut(&mut UiSlider) + Send + Sync + 'static)
+	{
+		*self._changedFn.write() = Some(Box::new(func));
+	}
+
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
+	pub fn boxed(self) -> Box<UiSlider>
+	{
+		return Box::new(self);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn callChanged(&mut self)
+	{
+		let selfbinding = self._changedFn.clone();
+		let mut binding = selfbinding.write();
+		if let Some(func) = binding.as_mut()
+		{
+			func(self);
+		}
+	}
+
+	fn snap(&self, value: f32) -> f32
+	{
+		let mut value = value.clamp(self._min, self._max);
+		if (self._step > 0.0)
+		{
+			value = self._min + ((value - self._min) / self._step).round() * self._step;
+			value = value.min(self._max);
+		}
+		return value;
+	}
+
+	fn ratio(&self) -> f32
+	{
+		if (self._max <= self._min)
+		{
+			return 0.0;
+		}
+		return (self._value - self._min) / (self._max - self._min);
+	}
+
+	// [left, right] of the track in pixel, the knob center move between them
+	fn trackRange(&self) -> [f32; 2]
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let knob = (bottomRight[1] - leftTop[1]) / 2.0;
+		return [leftTop[0] + knob / 2.0, bottomRight[0] - knob / 2.0];
+	}
+
+	fn changeValue(&mut self, value: f32) -> bool
+	{
+		let value = self.snap(value);
+		if (value == self._value)
+		{
+			return false;
+		}
+		self._value = value;
+		self.callChanged();
+		self._cacheinfos.setNeedUpdate(true);
+		return true;
+	}
+
+	// step used by keyboard/gamepad, a tenth of the range for continuous sliders
+	fn navigationStep(&self) -> f32
+	{
+		if (self._step <= 0.0)
+		{
+			return (self._max - self._min) / 10.0;
+		}
+		return self._step;
+	}
+
+	fn changeFromPixel(&mut self, x: f32) -> bool
+	{
+		let [left, right] = self.trackRange();
+		let ratio = ((x - left) / (right - left).max(1.0)).clamp(0.0, 1.0);
+		return self.changeValue(self._min + ratio * (self._max - self._min));
+	}
+
+	fn refreshDisplay(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+		let height = bottomRight[1] - leftTop[1];
+		let border = self._style.border;
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+		UiStyle::square(
+			&mut self._focusPlane,
+			[leftTop[0] - border, leftTop[1] - border, bottomRight[0] + border, bottomRight[1] + border],
+			z,
+			self._style.focus,
+		);
+
+		let [left, right] = self.trackRange();
+		let trackTop = leftTop[1] + height / 3.0;
+		let trackBottom = bottomRight[1] - height / 3.0;
+		UiStyle::square(&mut self._trackPlane, [left, trackTop, right, trackBottom], z + 1, self._style.backgroundFor(self._state));
+
+		self._bar.setSquare(
+			interfacePosition::new_pixel_z(left.round() as i32, trackTop.round() as i32, z + 2),
+			interfacePosition::new_pixel_z(right.round() as i32, trackBottom.round() as i32, z + 2),
+		);
+		self._bar.addState(0, Bar_state { color: self._style.foreground });
+		self._bar.updateProgress(self.ratio());
+
+		let knobCenter = left + (right - left) * self.ratio();
+		let knob = height / 2.0;
+		UiStyle::square(
+			&mut self._knobPlane,
+			[knobCenter - knob / 2.0, leftTop[1], knobCenter + knob / 2.0, bottomRight[1]],
+			z + 3,
+			self._style.text,
+		);
+	}
+}
+
+impl event_trait for UiSlider
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returning = self._events.clone().trigger(eventtype, self);
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER =>
+			{
+				let newstate = match eventtype
+				{
+					event_type::HOVER => UiButtonState::HOVER,
+					_ => UiButtonState::IDLE,
+				};
+				if (self._state != newstate)
+				{
+					self._state = newstate;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::CLICKED =>
+			{
+				if (self._state != UiButtonState::PRESSED)
+				{
+					self._state = UiButtonState::PRESSED;
+					let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+					if (self._hitbox.isInside(mousex, mousey))
+					{
+						self.changeFromPixel(mousex as f32);
+					}
+					else
+					{
+						// keyboard/gamepad activation, go up one step and loop to min
+						let mut value = self._value + self.navigationStep();
+						if (self._value >= self._max)
+						{
+							value = self._min;
+						}
+						self.changeValue(value);
+					}
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self._style.refresh();
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			_ =>
+			{}
+		}
+
+		if (self._cacheinfos.isPresent() && returning)
+		{
+			self.cache_submit();
+		}
+		return returning;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::IDLE => true,
+			event_type::HOVER => true,
+			event_type::CLICKED => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
+			event_type::WINREFRESH => true,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiSlider> for UiSlider
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiSlider) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl hideable for UiSlider
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiSlider
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow()
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		self.refreshDisplay();
+		if (self._focused)
+		{
+			self._focusPlane.cache_submit();
+		}
+		else
+		{
+			self._focusPlane.cache_remove();
+		}
+		self._trackPlane.cache_submit();
+		self._bar.cache_submit();
+		self._knobPlane.cache_submit();
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._focusPlane.cache_remove();
+		self._trackPlane.cache_remove();
+		self._bar.cache_remove();
+		self._knobPlane.cache_remove();
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiSlider
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		self._focusPlane.layout_clip(clip);
+		self._trackPlane.layout_clip(clip);
+		self._bar.layout_clip(clip);
+		self._knobPlane.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn event_navigate(&mut self, direction: UiFocus_direction) -> bool
+	{
+		let step = match direction
+		{
+			UiFocus_direction::LEFT => -self.navigationStep(),
+			UiFocus_direction::RIGHT => self.navigationStep(),
+			_ => return false,
+		};
+		if (self.changeValue(self._value + step) && self._cacheinfos.isPresent())
+		{
+			self.cache_submit();
+		}
+		// stay on the slider at the ends of the range
+		return true;
+	}
+
+	fn event_scroll(&mut self, event: &UiScrollEvent) -> bool
+	{
+		if (self._hide)
+		{
+			return false;
+		}
+
+		match *event
+		{
+			UiScrollEvent::WHEEL(_) => return false,
+			UiScrollEvent::DRAG_START([x, y]) =>
+			{
+				if (!self._hitbox.isInside(x as u16, y as u16))
+				{
+					return false;
+				}
+				self._dragging = true;
+				self.changeFromPixel(x);
+			}
+			UiScrollEvent::DRAG_MOVE([x, _]) =>
+			{
+				if (!self._dragging)
+				{
+					return false;
+				}
+				self.changeFromPixel(x);
+			}
+			UiScrollEvent::DRAG_END =>
+			{
+				if (!self._dragging)
+				{
+					return false;
+				}
+				self._dragging = false;
+			}
+		}
+
+		if (self._cacheinfos.isPresent() && self._cacheinfos.isNeedUpdate())
+		{
+			self.cache_submit();
+		}
+		return true;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		let mut uuids = vec![self._focusPlane.cache_infos().getUuid(), self._trackPlane.cache_infos().getUuid(), self._knobPlane.cache_infos().getUuid()];
+		uuids.extend(self._bar.cache_uuids());
+		return uuids;
+	}
+}
@@ -0,0 +1,496 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::Text::Text;
+use crate::Interface::UiButton::UiButtonState;
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiFocus_direction, UiPageContent, UiPageContent_type};
+use crate::Interface::UiStyle::{UiStyle, UiStyle_themed};
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// numeric field with a "-" button on the left and a "+" button on the right
+#[derive(Clone)]
+pub struct UiSpinner
+{
+	_position: [interfacePosition; 2],
+	_min: f32,
+	_max: f32,
+	_step: f32,
+	_value: f32,
+	_precision: usize,
+	_loop: bool,
+	_state: UiButtonState,
+	_hoveredSide: i8,
+	_focused: bool,
+	_style: UiStyle_themed,
+	_focusPlane: Plane<interfacePosition>,
+	_fieldPlane: Plane<interfacePosition>,
+	_minusPlane: Plane<interfacePosition>,
+	_plusPlane: Plane<interfacePosition>,
+	_minusText: Text,
+	_plusText: Text,
+	_text: Text,
+	_changedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiSpinner) + Send + Sync>>>>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_events: event<UiSpinner>,
+}
+
+impl UiSpinner
+{
+	/// range 0 to 100 by step of 1 by default
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition) -> Self
+	{
+		return UiSpinner {
+			_position: [leftTop, bottomRight],
+			_min: 0.0,
+			_max: 100.0,
+			_step: 1.0,
+			_value: 0.0,
+			_precision: 0,
+			_loop: false,
+			_state: UiButtonState::IDLE,
+			_hoveredSide: 0,
+			_focused: false,
+			_style: UiStyle_themed::new(),
+			_focusPlane: Plane::new(),
+			_fieldPlane: Plane::new(),
+			_minusPlane: Plane::new(),
+			_plusPlane: Plane::new(),
+			_minusText: Text::new(),
+			_plusText: Text::new(),
+			_text: Text::new(),
+			_changedFn: Arc::new(RwLock::new(None)),
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_events: event::new(),
+		};
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setRange(&mut self, min: f32, max: f32, step: f32)
+	{
+		self._min = min.min(max);
+		self._max = max.max(min);
+		self._step = step.abs();
+		self._value = self._value.clamp(self._min, self._max);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// number of decimals shown
+	pub fn setPrecision(&mut self, precision: usize)
+	{
+		self._precision = precision;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// go back to min after max (and to max before min)
+	pub fn setLoop(&mut self, looping: bool)
+	{
+		self._loop = looping;
+	}
+
+	/// change the value without calling the changed function
+	pub fn setValue(&mut self, value: f32)
+	{
+		self._value = value.clamp(self._min, self._max);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getValue(&self) -> f32
+	{
+		return self._value;
+	}
+
+	/// fixed style, stop following a UiTheme class
+	pub fn setStyle(&mut self, style: UiStyle)
+	{
+		self._style.set(style);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getStyle(&self) -> &UiStyle
+	{
+		return &self._style;
+	}
+
+	/// follow a UiTheme class, the style is reloaded when the theme change
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		self._style.setClass(class);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// called when the user change the value
+	pub fn setChangedFn(&mut self, func: impl FnMut(&mut UiSpinner) + Send + Sync + 'static)
+	{
+		*self._changedFn.write() = Some(Box::new(func));
+	}
+
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
+	pub fn boxed(self) -> Box<UiSpinner>
+	{
+		return Box::new(self);
+	}
+
+	/// add step * count to the value (negative to decrease), return true if changed
+	pub fn increment(&mut self, count: i32) -> bool
+	{
+		let mut value = self._value + self._step * count as f32;
+		if (self._loop)
+		{
+			if (value > self._max + f32::EPSILON)
+			{
+				value = self._min;
+			}
+			else if (value < self._min - f32::EPSILON)
+			{
+				value = self._max;
+			}
+		}
+		let value = value.clamp(self._min, self._max);
+		if (value == self._value)
+		{
+			return false;
+		}
+
+		self._value = value;
+		let selfbinding = self._changedFn.clone();
+		let mut binding = selfbinding.write();
+		if let Some(func) = binding.as_mut()
+		{
+			func(self);
+		}
+		self._cacheinfos.setNeedUpdate(true);
+		return true;
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// -1 on the "-" button, 1 on the "+" button, 0 elsewhere
+	fn sideAtMouse(&self) -> i8
+	{
+		let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+		if (!self._hitbox.isInside(mousex, mousey))
+		{
+			return 0;
+		}
+
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let button = bottomRight[1] - leftTop[1];
+		if ((mousex as f32) < leftTop[0] + button)
+		{
+			return -1;
+		}
+		if ((mousex as f32) > bottomRight[0] - button)
+		{
+			return 1;
+		}
+		return 0;
+	}
+
+	fn refreshDisplay(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+		let button = bottomRight[1] - leftTop[1];
+		let padding = self._style.padding;
+		let border = self._style.border;
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+		UiStyle::square(
+			&mut self._focusPlane,
+			[leftTop[0] - border, leftTop[1] - border, bottomRight[0] + border, bottomRight[1] + border],
+			z,
+			self._style.focus,
+		);
+		UiStyle::square(&mut self._fieldPlane, [leftTop[0], leftTop[1], bottomRight[0], bottomRight[1]], z + 1, self._style.background);
+
+		let mut minusState = UiButtonState::IDLE;
+		let mut plusState = UiButtonState::IDLE;
+		match self._hoveredSide
+		{
+			-1 => minusState = self._state,
+			1 => plusState = self._state,
+			_ => (),
+		}
+		let minusRect = [leftTop[0], leftTop[1], leftTop[0] + button, bottomRight[1]];
+		let plusRect = [bottomRight[0] - button, leftTop[1], bottomRight[0], bottomRight[1]];
+		UiStyle::square(&mut self._minusPlane, minusRect, z + 2, self._style.backgroundFor(minusState));
+		UiStyle::square(&mut self._plusPlane, plusRect, z + 2, self._style.backgroundFor(plusState));
+
+		let signWidth = self._style.textWidth("-");
+		self._style.label(&mut self._minusText, "-", minusRect[0] + (button - signWidth) / 2.0, leftTop[1], bottomRight[1], z + 3);
+		let signWidth = self._style.textWidth("+");
+		self._style.label(&mut self._plusText, "+", plusRect[0] + (button - signWidth) / 2.0, leftTop[1], bottomRight[1], z + 3);
+
+		let value = format!("{:.*}", self._precision, self._value);
+		let valueWidth = self._style.textWidth(&value);
+		let center = (leftTop[0] + bottomRight[0]) / 2.0;
+		self._style.label(&mut self._text, &value, (center - valueWidth / 2.0).max(minusRect[2] + padding), leftTop[1], bottomRight[1], z + 2);
+	}
+}
+
+impl event_trait for UiSpinner
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returning = self._events.clone().trigger(eventtype, self);
+		match eventtype
+		{
+			event_type::IDLE | event_type::HOVER =>
+			{
+				let mut newstate = UiButtonState::IDLE;
+				let mut side = 0;
+				if (eventtype == event_type::HOVER)
+				{
+					newstate = UiButtonState::HOVER;
+					side = self.sideAtMouse();
+				}
+				if (self._state != newstate || self._hoveredSide != side)
+				{
+					self._state = newstate;
+					self._hoveredSide = side;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::CLICKED =>
+			{
+				if (self._state != UiButtonState::PRESSED)
+				{
+					self._state = UiButtonState::PRESSED;
+					self._hoveredSide = self.sideAtMouse();
+					let [mousex, mousey] = ManagerInterface::singleton().getMousePosition();
+					if (self._hoveredSide != 0)
+					{
+						self.increment(self._hoveredSide as i32);
+					}
+					else if (!self._hitbox.isInside(mousex, mousey))
+					{
+						// keyboard/gamepad activation, go up and loop to min
+						let looping = self._loop;
+						self._loop = true;
+						self.increment(1);
+						self._loop = looping;
+					}
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self._style.refresh();
+				self._text.event_trigger(eventtype);
+				self._minusText.event_trigger(eventtype);
+				self._plusText.event_trigger(eventtype);
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			_ =>
+			{}
+		}
+
+		if (self._cacheinfos.isPresent() && returning)
+		{
+			self.cache_submit();
+		}
+		return returning;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::IDLE => true,
+			event_type::HOVER => true,
+			event_type::CLICKED => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
+			event_type::WINREFRESH => true,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiSpinner> for UiSpinner
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiSpinner) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl hideable for UiSpinner
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiSpinner
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self._text.cache_mustUpdate()
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		self.refreshDisplay();
+		if (self._focused)
+		{
+			self._focusPlane.cache_submit();
+		}
+		else
+		{
+			self._focusPlane.cache_remove();
+		}
+		self._fieldPlane.cache_submit();
+		self._minusPlane.cache_submit();
+		self._plusPlane.cache_submit();
+		self._minusText.cache_submit();
+		self._plusText.cache_submit();
+		self._text.cache_submit();
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._focusPlane.cache_remove();
+		self._fieldPlane.cache_remove();
+		self._minusPlane.cache_remove();
+		self._plusPlane.cache_remove();
+		self._minusText.cache_remove();
+		self._plusText.cache_remove();
+		self._text.cache_remove();
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiSpinner
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		self._focusPlane.layout_clip(clip);
+		self._fieldPlane.layout_clip(clip);
+		self._minusPlane.layout_clip(clip);
+		self._plusPlane.layout_clip(clip);
+		self._minusText.layout_clip(clip);
+		self._plusText.layout_clip(clip);
+		self._text.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn event_navigate(&mut self, direction: UiFocus_direction) -> bool
+	{
+		let count = match direction
+		{
+			UiFocus_direction::LEFT => -1,
+			UiFocus_direction::RIGHT => 1,
+			_ => return false,
+		};
+		if (self.increment(count) && self._cacheinfos.isPresent())
+		{
+			self.cache_submit();
+		}
+		return true;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return [&self._focusPlane, &self._fieldPlane, &self._minusPlane, &self._plusPlane]
+			.iter()
+			.map(|x| x.cache_infos().getUuid())
+			.chain([&self._minusText, &self._plusText, &self._text].iter().map(|x| x.cache_infos().getUuid()))
+			.collect();
+	}
+}
@@ -0,0 +1,162 @@
+use crate::components::color::color;
+use crate::components::corners::corner4;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::ManagerFont::ManagerFont;
+use crate::Interface::Text::{Text, TextSize};
+use crate::Interface::UiButton::UiButtonState;
+use crate::Interface::UiTheme::UiTheme;
+use glyph_brush::OwnedText;
+use std::ops::Deref;
+
+/// colors and sizes shared by widgets (button, checkbox, slider, radio, dropdown, spinner)
+#[derive(Clone)]
+pub struct UiStyle
+{
+	/// box, track or field
+	pub background: color,
+	/// background under the mouse
+	pub backgroundHover: color,
+	/// background while pressed
+	pub backgroundPressed: color,
+	/// check mark, filled part of a slider, selected item
+	pub foreground: color,
+	/// outline shown around the focused widget
+	pub focus: color,
+	pub text: color,
+	pub textSize: TextSize,
+	/// font name as registered in ManagerFont
+	pub font: String,
+	/// inner space in pixel
+	pub padding: f32,
+	/// focus outline thickness in pixel
+	pub border: f32,
+	/// texture name drawn as a 9-slice background by UiButton, tinted with the background colors
+	pub texture: Option<String>,
+	/// 9-slice borders [left, top, right, bottom] in texture pixel
+	pub textureSlice: [f32; 4],
+}
+
+impl Default for UiStyle
+{
+	fn default() -> Self
+	{
+		return UiStyle {
+			background: color::from([0.2, 0.2, 0.2, 1.0]),
+			backgroundHover: color::from([0.3, 0.3, 0.3, 1.0]),
+			backgroundPressed: color::from([0.15, 0.15, 0.15, 1.0]),
+			foreground: color::from([0.2, 0.5, 0.9, 1.0]),
+			focus: color::from([1.0, 0.8, 0.2, 1.0]),
+			text: color::default(),
+			textSize: TextSize::NORMAL,
+			font: "user".to_string(),
+			padding: 4.0,
+			border: 2.0,
+			texture: None,
+			textureSlice: [0.0; 4],
+		};
+	}
+}
+
+impl UiStyle
+{
+	pub fn backgroundFor(&self, state: UiButtonState) -> color
+	{
+		match state
+		{
+			UiButtonState::IDLE => self.background,
+			UiButtonState::HOVER => self.backgroundHover,
+			UiButtonState::PRESSED => self.backgroundPressed,
+		}
+	}
+
+	/// text width in pixel with this style
+	pub fn textWidth(&self, text: &str) -> f32
+	{
+		let fontId = ManagerFont::singleton().FontIdGet(&self.font).unwrap_or_default();
+		let advances = ManagerFont::singleton().Text_advances(text, self.textSize.getInt(), fontId);
+		return advances.last().copied().unwrap_or(0.0);
+	}
+
+	// place a plane on a pixel rect [left, top, right, bottom] with a plain color
+	pub(crate) fn square(plane: &mut Plane<interfacePosition>, rect: [f32; 4], z: u16, newcolor: color)
+	{
+		plane.setSquare(
+			interfacePosition::new_pixel_z(rect[0].round() as i32, rect[1].round() as i32, z),
+			interfacePosition::new_pixel_z(rect[2].round() as i32, rect[3].round() as i32, z),
+		);
+		plane.setColor(corner4::same(newcolor));
+	}
+
+	// replace a text content, vertically centered between top and bottom
+	pub(crate) fn label(&self, text: &mut Text, value: &str, left: f32, top: f32, bottom: f32, z: u16)
+	{
+		let scale = self.textSize.getInt();
+		let fontId = ManagerFont::singleton().FontIdGet(&self.font).unwrap_or_default();
+		let originY = top + ((bottom - top) - scale).max(0.0) / 2.0;
+
+		text.getMutText().clear();
+		if (value.is_empty())
+		{
+			text.setHidden();
+		}
+		else
+		{
+			text.addText(OwnedText::new(value).with_color(self.text.toArray()).with_font_id(fontId));
+		}
+		text.setTextDynamicSize(TextSize::FIX(scale));
+		text.setPos(interfacePosition::new_pixel_z(left.round() as i32, originY.round() as i32, z));
+	}
+}
+
+/// style of a widget, fixed or following a UiTheme class
+#[derive(Clone)]
+pub(crate) struct UiStyle_themed
+{
+	_style: UiStyle,
+	_class: Option<String>,
+}
+
+impl UiStyle_themed
+{
+	pub fn new() -> Self
+	{
+		return UiStyle_themed {
+			_style: UiStyle::default(),
+			_class: None,
+		};
+	}
+
+	/// fixed style, stop following a UiTheme class
+	pub fn set(&mut self, style: UiStyle)
+	{
+		self._style = style;
+		self._class = None;
+	}
+
+	pub fn setClass(&mut self, class: impl Into<String>)
+	{
+		let class = class.into();
+		self._style = UiTheme::singleton().get(&class);
+		self._class = Some(class);
+	}
+
+	/// reload the class after a theme change (WINREFRESH)
+	pub fn refresh(&mut self)
+	{
+		if let Some(class) = &self._class
+		{
+			self._style = UiTheme::singleton().get(class);
+		}
+	}
+}
+
+impl Deref for UiStyle_themed
+{
+	type Target = UiStyle;
+
+	fn deref(&self) -> &UiStyle
+	{
+		return &self._style;
+	}
+}
@@ -0,0 +1,819 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::components::color::color;
+use crate::components::corners::corner4;
+use crate::components::event::{event, event_trait, event_trait_add, event_type};
+use crate::components::hideable::hideable;
+use crate::components::interfacePosition::interfacePosition;
+use crate::entities::Plane::Plane;
+use crate::Interface::ManagerFont::ManagerFont;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::Text::{Text, TextSize};
+use crate::Interface::UiHitbox::UiHitbox;
+use crate::Interface::UiPage::{UiPageContent, UiPageContent_type, UiTextEvent, UiTextKey};
+use crate::Interface::UiTheme::UiTheme;
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImpl;
+use glyph_brush::OwnedText;
+use parking_lot::RwLock;
+use regex::Regex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const CARET_BLINK: Duration = Duration::from_millis(500);
+const CARET_WIDTH: f32 = 2.0;
+
+#[derive(Clone)]
+pub enum UiTextInput_validation
+{
+	NONE,
+	/// digits, with an optional leading "-" and only one "."
+	NUMERIC,
+	/// the whole value is checked, use ^ and $ to match it entirely
+	REGEX(Regex),
+	FUNC(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl UiTextInput_validation
+{
+	pub fn isValid(&self, value: &str) -> bool
+	{
+		match self
+		{
+			UiTextInput_validation::NONE => true,
+			UiTextInput_validation::NUMERIC =>
+			{
+				let mut haveDot = false;
+				value.chars().enumerate().all(|(i, c)| {
+					if (c == '.' && !haveDot)
+					{
+						haveDot = true;
+						return true;
+					}
+					c.is_ascii_digit() || (c == '-' && i == 0)
+				})
+			}
+			UiTextInput_validation::REGEX(regex) => regex.is_match(value),
+			UiTextInput_validation::FUNC(func) => func(value),
+		}
+	}
+}
+
+/// single line text field, with caret, selection, clipboard and IME preedit
+#[derive(Clone)]
+pub struct UiTextInput
+{
+	_position: [interfacePosition; 2],
+	_padding: f32,
+	_value: String,
+	_placeholder: String,
+	_maxLength: Option<usize>,
+	_validation: UiTextInput_validation,
+	_textSize: TextSize,
+	_font: String,
+	_color: color,
+	_placeholderColor: color,
+	_styleClass: Option<String>,
+	_caret: usize,
+	_anchor: Option<usize>,
+	_preedit: String,
+	_scroll: f32,
+	_advances: Vec<f32>,
+	_originX: f32,
+	_focused: bool,
+	_caretVisible: bool,
+	_caretBlink: Instant,
+	_text: Text,
+	_caretPlane: Plane<interfacePosition>,
+	_selectionPlane: Plane<interfacePosition>,
+	_haveSelection: bool,
+	_changedFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiTextInput) + Send + Sync>>>>,
+	_submitFn: Arc<RwLock<Option<Box<dyn FnMut(&mut UiTextInput) + Send + Sync>>>>,
+	_hitbox: UiHitbox,
+	_hide: bool,
+	_cacheinfos: cacheInfos,
+	_events: event<UiTextInput>,
+}
+
+impl UiTextInput
+{
+	pub fn new(leftTop: interfacePosition, bottomRight: interfacePosition) -> Self
+	{
+		let mut selectionPlane = Plane::new();
+		selectionPlane.setColor(corner4::same(color::from([0.2, 0.4, 0.9, 0.5])));
+
+		return UiTextInput {
+			_position: [leftTop, bottomRight],
+			_padding: 4.0,
+			_value: String::new(),
+			_placeholder: String::new(),
+			_maxLength: None,
+			_validation: UiTextInput_validation::NONE,
+			_textSize: TextSize::NORMAL,
+			_font: "user".to_string(),
+			_color: color::default(),
+			_placeholderColor: color::from([1.0, 1.0, 1.0, 0.5]),
+			_styleClass: None,
+			_caret: 0,
+			_anchor: None,
+			_preedit: String::new(),
+			_scroll: 0.0,
+			_advances: vec![0.0],
+			_originX: 0.0,
+			_focused: false,
+			_caretVisible: true,
+			_caretBlink: Instant::now(),
+			_text: Text::new(),
+			_caretPlane: Plane::new(),
+			_selectionPlane: selectionPlane,
+			_haveSelection: false,
+			_changedFn: Arc::new(RwLock::new(None)),
+			_submitFn: Arc::new(RwLock::new(None)),
+			_hitbox: UiHitbox::new(),
+			_hide: false,
+			_cacheinfos: cacheInfos::default(),
+			_events: event::new(),
+		};
+	}
+
+	pub fn setSquare(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self._position = [leftTop, bottomRight];
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// inner space between the border and the text, in pixel
+	pub fn setPadding(&mut self, padding: f32)
+	{
+		self._padding = padding.max(0.0);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// replace the value, without validation, but truncated to max length
+	pub fn setValue(&mut self, value: impl Into<String>)
+	{
+		let mut value: String = value.into();
+		value.retain(|c| !c.is_control());
+		if let Some(max) = self._maxLength
+		{
+			value = value.chars().take(max).collect();
+		}
+		self._value = value;
+		self._caret = self._value.chars().count();
+		self._anchor = None;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn getValue(&self) -> &String
+	{
+		return &self._value;
+	}
+
+	/// text shown when the value is empty
+	pub fn setPlaceholder(&mut self, placeholder: impl Into<String>)
+	{
+		self._placeholder = placeholder.into();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// max length in chars
+	pub fn setMaxLength(&mut self, max: Option<usize>)
+	{
+		self._maxLength = max;
+		if let Some(max) = max
+		{
+			if (self._value.chars().count() > max)
+			{
+				let value = self._value.clone();
+				self.setValue(value);
+			}
+		}
+	}
+
+	pub fn setValidation(&mut self, validation: UiTextInput_validation)
+	{
+		self._validation = validation;
+	}
+
+	pub fn setTextSize(&mut self, size: TextSize)
+	{
+		self._textSize = size;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// font name as registered in ManagerFont ("user" by default)
+	pub fn setFont(&mut self, name: impl Into<String>)
+	{
+		self._font = name.into();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setColor(&mut self, text: color, placeholder: color)
+	{
+		self._color = text;
+		self._placeholderColor = placeholder;
+		self._caretPlane.setColor(corner4::same(text));
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn setSelectionColor(&mut self, selection: color)
+	{
+		self._selectionPlane.setColor(corner4::same(selection));
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	/// follow a UiTheme class (text, textSize, font, padding, foreground as selection)
+	/// the style is reloaded when the theme change, replacing the values of the setters above
+	pub fn setStyleClass(&mut self, class: impl Into<String>)
+	{
+		self._styleClass = Some(class.into());
+		self.styleClassApply();
+	}
+
+	/// called each time the value is changed by the user
+	pub fn setChangedFn(&mut self, func: impl FnMut(&mut UiTextInput) + Send + Sync + 'static)
+	{
+		*self._changedFn.write() = Some(Box::new(func));
+	}
+
+	/// called when enter is pressed
+	pub fn setSubmitFn(&mut self, func: impl FnMut(&mut UiTextInput) + Send + Sync + 'static)
+	{
+		*self._submitFn.write() = Some(Box::new(func));
+	}
+
+	/// selection as char index [start, end[
+	pub fn getSelection(&self) -> Option<(usize, usize)>
+	{
+		let anchor = self._anchor?;
+		if (anchor == self._caret)
+		{
+			return None;
+		}
+		return Some((anchor.min(self._caret), anchor.max(self._caret)));
+	}
+
+	pub fn selectAll(&mut self)
+	{
+		self._anchor = Some(0);
+		self._caret = self._value.chars().count();
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	pub fn isFocused(&self) -> bool
+	{
+		return self._focused;
+	}
+
+	pub fn boxed(self) -> Box<UiTextInput>
+	{
+		return Box::new(self);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn styleClassApply(&mut self)
+	{
+		let Some(class) = &self._styleClass
+		else
+		{
+			return;
+		};
+		let style = UiTheme::singleton().get(class);
+		let mut placeholder = style.text;
+		placeholder.a *= 0.5;
+		let mut selection = style.foreground;
+		selection.a *= 0.5;
+
+		self._padding = style.padding.max(0.0);
+		self._textSize = style.textSize;
+		self._font = style.font;
+		self.setColor(style.text, placeholder);
+		self.setSelectionColor(selection);
+	}
+
+	fn charToByte(&self, index: usize) -> usize
+	{
+		self._value.char_indices().nth(index).map(|x| x.0).unwrap_or(self._value.len())
+	}
+
+	fn selectedText(&self) -> Option<String>
+	{
+		let (start, end) = self.getSelection()?;
+		return Some(self._value[self.charToByte(start)..self.charToByte(end)].to_string());
+	}
+
+	fn deleteSelection(&mut self) -> bool
+	{
+		let Some((start, end)) = self.getSelection()
+		else
+		{
+			return false;
+		};
+
+		let range = self.charToByte(start)..self.charToByte(end);
+		self._value.replace_range(range, "");
+		self._caret = start;
+		self._anchor = None;
+		return true;
+	}
+
+	// insert at caret (replacing the selection), refused if the result is not valid
+	fn insert(&mut self, text: &str) -> bool
+	{
+		let mut text: String = text.chars().filter(|c| !c.is_control()).collect();
+		if (text.is_empty())
+		{
+			return false;
+		}
+
+		let (start, end) = self.getSelection().unwrap_or((self._caret, self._caret));
+		if let Some(max) = self._maxLength
+		{
+			let remaining = max.saturating_sub(self._value.chars().count() - (end - start));
+			text = text.chars().take(remaining).collect();
+			if (text.is_empty())
+			{
+				return false;
+			}
+		}
+
+		let mut newvalue = self._value.clone();
+		newvalue.replace_range(self.charToByte(start)..self.charToByte(end), &text);
+		if (!self._validation.isValid(&newvalue))
+		{
+			return false;
+		}
+
+		self._value = newvalue;
+		self._caret = start + text.chars().count();
+		self._anchor = None;
+		return true;
+	}
+
+	fn moveCaret(&mut self, newpos: usize, select: bool)
+	{
+		if (select)
+		{
+			if (self._anchor.is_none())
+			{
+				self._anchor = Some(self._caret);
+			}
+		}
+		else
+		{
+			self._anchor = None;
+		}
+		self._caret = newpos.min(self._value.chars().count());
+	}
+
+	// return true if the value have changed
+	fn applyKey(&mut self, key: UiTextKey, shift: bool) -> bool
+	{
+		let len = self._value.chars().count();
+		match key
+		{
+			UiTextKey::BACKSPACE =>
+			{
+				if (self.deleteSelection())
+				{
+					return true;
+				}
+				if (self._caret == 0)
+				{
+					return false;
+				}
+				let range = self.charToByte(self._caret - 1)..self.charToByte(self._caret);
+				self._value.replace_range(range, "");
+				self._caret -= 1;
+				return true;
+			}
+			UiTextKey::DELETE =>
+			{
+				if (self.deleteSelection())
+				{
+					return true;
+				}
+				if (self._caret >= len)
+				{
+					return false;
+				}
+				let range = self.charToByte(self._caret)..self.charToByte(self._caret + 1);
+				self._value.replace_range(range, "");
+				return true;
+			}
+			UiTextKey::LEFT =>
+			{
+				match self.getSelection()
+				{
+					Some((start, _)) if !shift => self.moveCaret(start, false),
+					_ => self.moveCaret(self._caret.saturating_sub(1), shift),
+				}
+			}
+			UiTextKey::RIGHT =>
+			{
+				match self.getSelection()
+				{
+					Some((_, end)) if !shift => self.moveCaret(end, false),
+					_ => self.moveCaret(self._caret + 1, shift),
+				}
+			}
+			UiTextKey::HOME => self.moveCaret(0, shift),
+			UiTextKey::END => self.moveCaret(len, shift),
+			UiTextKey::SELECT_ALL => self.selectAll(),
+			UiTextKey::COPY =>
+			{
+				if let Some(text) = self.selectedText()
+				{
+					ManagerInterface::singleton().clipboard_set(text);
+				}
+			}
+			UiTextKey::CUT =>
+			{
+				if let Some(text) = self.selectedText()
+				{
+					ManagerInterface::singleton().clipboard_set(text);
+					return self.deleteSelection();
+				}
+			}
+			UiTextKey::PASTE =>
+			{
+				let clipboard = ManagerInterface::singleton().clipboard_get();
+				let firstline = clipboard.lines().next().unwrap_or("").to_string();
+				return self.insert(&firstline);
+			}
+			UiTextKey::SUBMIT =>
+			{
+				let selfbinding = self._submitFn.clone();
+				let mut binding = selfbinding.write();
+				if let Some(func) = binding.as_mut()
+				{
+					func(self);
+				}
+			}
+		}
+
+		return false;
+	}
+
+	fn valueChanged(&mut self)
+	{
+		let selfbinding = self._changedFn.clone();
+		let mut binding = selfbinding.write();
+		if let Some(func) = binding.as_mut()
+		{
+			func(self);
+		}
+	}
+
+	// caret char index nearest to a window pixel position
+	fn caretFromPixel(&self, x: f32) -> usize
+	{
+		let local = x - self._originX + self._scroll;
+		let mut best = 0;
+		for (i, advance) in self._advances.iter().enumerate()
+		{
+			if ((advance - local).abs() < (self._advances[best] - local).abs())
+			{
+				best = i;
+			}
+		}
+		return best.min(self._value.chars().count());
+	}
+
+	fn caretShow(&mut self)
+	{
+		self._caretVisible = true;
+		self._caretBlink = Instant::now();
+	}
+
+	fn caretSubmit(&mut self)
+	{
+		if (self._focused && self._caretVisible && !self._hide)
+		{
+			self._caretPlane.cache_submit();
+		}
+		else
+		{
+			self._caretPlane.cache_remove();
+		}
+	}
+
+	fn refreshDisplay(&mut self)
+	{
+		let leftTop = self._position[0].convertToPixel();
+		let bottomRight = self._position[1].convertToPixel();
+		let z = self._position[0].getZ();
+
+		self._hitbox = UiHitbox::new();
+		self._hitbox.updateFromPoint(leftTop[0], leftTop[1]);
+		self._hitbox.updateFromPoint(bottomRight[0], bottomRight[1]);
+
+		let scale = self._textSize.getInt();
+		let fontId = ManagerFont::singleton().FontIdGet(&self._font).unwrap_or_default();
+		let width = (bottomRight[0] - leftTop[0] - self._padding * 2.0).max(0.0);
+		let originY = leftTop[1] + ((bottomRight[1] - leftTop[1]) - scale).max(0.0) / 2.0;
+		self._originX = leftTop[0] + self._padding;
+
+		// IME preedit is shown at caret position, without being part of the value
+		let mut display = self._value.clone();
+		display.insert_str(self.charToByte(self._caret), &self._preedit);
+		let displayCaret = self._caret + self._preedit.chars().count();
+		let advances = ManagerFont::singleton().Text_advances(&display, scale, fontId);
+		let caretX = advances[displayCaret.min(advances.len() - 1)];
+		let total = advances[advances.len() - 1];
+
+		// keep the caret inside the field
+		if (caretX - self._scroll > width)
+		{
+			self._scroll = caretX - width;
+		}
+		if (caretX < self._scroll)
+		{
+			self._scroll = caretX;
+		}
+		self._scroll = self._scroll.min((total - width).max(0.0)).max(0.0);
+
+		let (visible, textcolor, textX) = if (display.is_empty())
+		{
+			(self._placeholder.clone(), self._placeholderColor, self._originX)
+		}
+		else
+		{
+			let first = advances.iter().position(|x| *x >= self._scroll).unwrap_or(0);
+			let last = advances.iter().rposition(|x| *x <= self._scroll + width).unwrap_or(0);
+			let visible: String = display.chars().skip(first).take(last.saturating_sub(first)).collect();
+			(visible, self._color, self._originX + advances[first] - self._scroll)
+		};
+
+		self._text.getMutText().clear();
+		if (visible.is_empty())
+		{
+			self._text.setHidden();
+		}
+		else
+		{
+			self._text.addText(OwnedText::new(visible).with_color(textcolor.toArray()).with_font_id(fontId));
+		}
+		self._text.setTextDynamicSize(TextSize::FIX(scale));
+		self._text.setPos(interfacePosition::new_pixel_z(textX.round() as i32, originY.round() as i32, z + 1));
+
+		let caretLeft = (self._originX + caretX - self._scroll).round() as i32;
+		self._caretPlane.setSquare(
+			interfacePosition::new_pixel_z(caretLeft, originY.round() as i32, z + 1),
+			interfacePosition::new_pixel_z(caretLeft + CARET_WIDTH as i32, (originY + scale).round() as i32, z + 1),
+		);
+
+		self._haveSelection = false;
+		if let Some((start, end)) = self.getSelection().filter(|_| self._preedit.is_empty())
+		{
+			// advances only hold the origin while fonts are not loaded
+			let left = self._originX + (advances.get(start).copied().unwrap_or(total) - self._scroll).max(0.0);
+			let right = self._originX + (advances.get(end).copied().unwrap_or(total) - self._scroll).min(width);
+			if (right > left)
+			{
+				self._selectionPlane.setSquare(
+					interfacePosition::new_pixel_z(left.round() as i32, originY.round() as i32, z),
+					interfacePosition::new_pixel_z(right.round() as i32, (originY + scale).round() as i32, z),
+				);
+				self._haveSelection = true;
+			}
+		}
+
+		self._advances = advances;
+	}
+}
+
+impl event_trait for UiTextInput
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		let mut returning = self._events.clone().trigger(eventtype, self);
+		match eventtype
+		{
+			event_type::CLICKED =>
+			{
+				let [mousex, _] = ManagerInterface::singleton().getMousePosition();
+				let newcaret = self.caretFromPixel(mousex as f32);
+				self.moveCaret(newcaret, false);
+				self.caretShow();
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			event_type::FOCUS | event_type::BLUR =>
+			{
+				let focused = eventtype == event_type::FOCUS;
+				if (self._focused != focused)
+				{
+					self._focused = focused;
+					ManagerInterface::singleton().textInput_want(focused);
+					if (!focused)
+					{
+						self._anchor = None;
+						self._preedit.clear();
+					}
+					self.caretShow();
+					self._cacheinfos.setNeedUpdate(true);
+					returning = true;
+				}
+			}
+			event_type::EACH_TICK =>
+			{
+				if (self._focused && self._caretBlink.elapsed() >= CARET_BLINK)
+				{
+					self._caretVisible = !self._caretVisible;
+					self._caretBlink = Instant::now();
+					if (self._cacheinfos.isPresent())
+					{
+						self.caretSubmit();
+					}
+				}
+			}
+			event_type::WINREFRESH =>
+			{
+				self.styleClassApply();
+				self._text.event_trigger(eventtype);
+				self._cacheinfos.setNeedUpdate(true);
+				returning = true;
+			}
+			_ =>
+			{}
+		}
+
+		if (self._cacheinfos.isPresent() && returning)
+		{
+			self.cache_submit();
+		}
+		return returning;
+	}
+
+	fn event_have(&self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::CLICKED => true,
+			event_type::FOCUS => true,
+			event_type::BLUR => true,
+			event_type::WINREFRESH => true,
+			event_type::EACH_TICK => self._focused,
+			_ => self._events.have(eventtype),
+		}
+	}
+}
+
+impl event_trait_add<UiTextInput> for UiTextInput
+{
+	fn event_add(&mut self, eventtype: event_type, func: impl Fn(&mut UiTextInput) -> bool + Send + Sync + 'static)
+	{
+		self._events.add(eventtype, func);
+	}
+}
+
+impl hideable for UiTextInput
+{
+	fn hide(&mut self)
+	{
+		self._hide = true;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn show(&mut self)
+	{
+		self._hide = false;
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isShow(&self) -> bool
+	{
+		!self._hide
+	}
+}
+
+impl ShaderDrawerImpl for UiTextInput
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		if (self._hide)
+		{
+			return self._cacheinfos.isPresent();
+		}
+
+		self._cacheinfos.isNotShow() || self._text.cache_mustUpdate()
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self._cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self._cacheinfos
+	}
+
+	fn cache_submit(&mut self)
+	{
+		if (self._hide)
+		{
+			self._cacheinfos.setNeedUpdate(false);
+			self.cache_remove();
+			return;
+		}
+
+		self.refreshDisplay();
+		self._text.cache_submit();
+		self.caretSubmit();
+		if (self._haveSelection)
+		{
+			self._selectionPlane.cache_submit();
+		}
+		else
+		{
+			self._selectionPlane.cache_remove();
+		}
+
+		self._cacheinfos.setNeedUpdate(false);
+		self._cacheinfos.setPresent();
+	}
+
+	fn cache_remove(&mut self)
+	{
+		self._text.cache_remove();
+		self._caretPlane.cache_remove();
+		self._selectionPlane.cache_remove();
+		self._cacheinfos.setAbsent();
+	}
+}
+
+impl UiPageContent for UiTextInput
+{
+	fn getType(&self) -> UiPageContent_type
+	{
+		return UiPageContent_type::INTERACTIVE;
+	}
+
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self._hitbox.clone()
+	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		self._text.layout_clip(clip);
+		self._caretPlane.layout_clip(clip);
+		self._selectionPlane.layout_clip(clip);
+		self._cacheinfos.setNeedUpdate(true);
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		return !self._hide;
+	}
+
+	fn event_text(&mut self, event: &UiTextEvent) -> bool
+	{
+		let changed = match event
+		{
+			UiTextEvent::TEXT(text) =>
+			{
+				self._preedit.clear();
+				self.insert(text)
+			}
+			UiTextEvent::PREEDIT(text) =>
+			{
+				self._preedit = text.chars().filter(|c| !c.is_control()).collect();
+				false
+			}
+			UiTextEvent::KEY(key, shift) => self.applyKey(*key, *shift),
+		};
+
+		if (changed)
+		{
+			self.valueChanged();
+		}
+
+		// enter without submit callback is left to focus activation
+		let used = match event
+		{
+			UiTextEvent::KEY(UiTextKey::SUBMIT, _) => self._submitFn.read().is_some(),
+			_ => true,
+		};
+
+		self.caretShow();
+		self._cacheinfos.setNeedUpdate(true);
+		if (self._cacheinfos.isPresent())
+		{
+			self.cache_submit();
+		}
+		return used;
+	}
+
+	fn cache_uuids(&self) -> Vec<Uuid>
+	{
+		return vec![self._text.cache_infos().getUuid(), self._caretPlane.cache_infos().getUuid(), self._selectionPlane.cache_infos().getUuid()];
+	}
+}
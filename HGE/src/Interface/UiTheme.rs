@@ -0,0 +1,248 @@
+use crate::assetStreamReader::assetManager;
+use crate::components::color::color;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::Text::TextSize;
+use crate::Interface::UiStyle::UiStyle;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use foldhash::{HashMap, HashMapExt};
+use std::sync::{Arc, OnceLock};
+use Hconfig::serde_json::{Map, Value as JsonValue};
+
+/// class used when a requested class is missing, and parent of every class without "extends"
+pub const UITHEME_DEFAULT_CLASS: &str = "default";
+
+/// named UiStyle shared by every page, widgets follow a class with "setStyleClass"
+/// changing the theme refresh all pages (dark mode, colorblind palette, ...)
+pub struct UiTheme
+{
+	_styles: ArcSwap<HashMap<String, UiStyle>>,
+}
+
+static SINGLETON: OnceLock<UiTheme> = OnceLock::new();
+
+impl UiTheme
+{
+	fn new() -> UiTheme
+	{
+		return UiTheme {
+			_styles: ArcSwap::new(Arc::new(HashMap::new())),
+		};
+	}
+
+	pub fn singleton() -> &'static UiTheme
+	{
+		return SINGLETON.get_or_init(|| UiTheme::new());
+	}
+
+	/// style of a class, fallback to the "default" class then to UiStyle::default()
+	pub fn get(&self, class: &str) -> UiStyle
+	{
+		let styles = self._styles.load();
+		if let Some(style) = styles.get(class).or_else(|| styles.get(UITHEME_DEFAULT_CLASS))
+		{
+			return style.clone();
+		}
+		return UiStyle::default();
+	}
+
+	pub fn have(&self, class: &str) -> bool
+	{
+		return self._styles.load().contains_key(class);
+	}
+
+	pub fn classes(&self) -> Vec<String>
+	{
+		return self._styles.load().keys().cloned().collect();
+	}
+
+	/// add or replace one class and refresh all pages
+	pub fn set(&self, class: impl Into<String>, style: UiStyle)
+	{
+		let class = class.into();
+		self._styles.rcu(|old| {
+			let mut styles = HashMap::clone(old);
+			styles.insert(class.clone(), style.clone());
+			styles
+		});
+		ManagerInterface::singleton().WindowRefreshed();
+	}
+
+	/// replace the whole theme from a json file read with assetManager
+	/// on error, the current theme is kept
+	pub fn loadFile(&self, path: impl Into<String>) -> anyhow::Result<()>
+	{
+		let path = path.into();
+		let Some(fileread) = assetManager::singleton().readFile(&path)
+		else
+		{
+			return Err(anyhow!("cannot load : {}", path));
+		};
+		let json: JsonValue = Hconfig::serde_json::from_reader(fileread).map_err(|err| anyhow!("{} : {}", path, err))?;
+		return self.loadJson(&json);
+	}
+
+	/// replace the whole theme from a json object : { "class": { "field": value, "extends": "otherclass" }, ... }
+	/// on error, the current theme is kept
+	pub fn loadJson(&self, json: &JsonValue) -> anyhow::Result<()>
+	{
+		let Some(classes) = json.as_object()
+		else
+		{
+			return Err(anyhow!("theme must be a json object of classes"));
+		};
+
+		let mut styles = HashMap::new();
+		for name in classes.keys()
+		{
+			Self::parseClass(classes, name, &mut styles, &mut Vec::new())?;
+		}
+		self._styles.store(Arc::new(styles));
+		ManagerInterface::singleton().WindowRefreshed();
+		return Ok(());
+	}
+
+	// "#rrggbb", "#rrggbbaa" or [r, g, b(, a)] between 0.0 and 1.0
+	pub(crate) fn parseColor(value: &JsonValue) -> anyhow::Result<color>
+	{
+		if let Some(hex) = value.as_str()
+		{
+			let digits = hex.strip_prefix('#').unwrap_or(hex);
+			if (!digits.is_ascii() || (digits.len() != 6 && digits.len() != 8))
+			{
+				return Err(anyhow!("color \"{}\" must be #rrggbb or #rrggbbaa", hex));
+			}
+			let mut rgba = [255u8; 4];
+			for (i, channel) in rgba.iter_mut().enumerate().take(digits.len() / 2)
+			{
+				*channel = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).map_err(|_| anyhow!("color \"{}\" is not hexadecimal", hex))?;
+			}
+			return Ok(color::from(rgba));
+		}
+
+		if let Some(array) = value.as_array().filter(|x| x.len() == 3 || x.len() == 4)
+		{
+			let mut rgba = [1.0; 4];
+			for (i, item) in array.iter().enumerate()
+			{
+				rgba[i] = item.as_f64().ok_or(anyhow!("color channels must be numbers"))? as f32;
+			}
+			return Ok(color::from(rgba));
+		}
+		return Err(anyhow!("color must be \"#rrggbb(aa)\" or [r, g, b(, a)]"));
+	}
+
+	// named size (NORMAL, SMALL, SMALLER, BIG, BIGGER) or a fixed size in pixel
+	pub(crate) fn parseTextSize(value: &JsonValue) -> anyhow::Result<TextSize>
+	{
+		if let Some(size) = value.as_f64()
+		{
+			return Ok(TextSize::FIX(size as f32));
+		}
+		return match value.as_str().unwrap_or_default()
+		{
+			"NORMAL" => Ok(TextSize::NORMAL),
+			"SMALL" => Ok(TextSize::SMALL),
+			"SMALLER" => Ok(TextSize::SMALLER),
+			"BIG" => Ok(TextSize::BIG),
+			"BIGGER" => Ok(TextSize::BIGGER),
+			_ => Err(anyhow!("must be NORMAL, SMALL, SMALLER, BIG, BIGGER or a size in pixel")),
+		};
+	}
+
+	// [a, b, c, d] of numbers
+	pub(crate) fn parseArray4(value: &JsonValue) -> Option<[f32; 4]>
+	{
+		let array = value.as_array()?;
+		if (array.len() != 4)
+		{
+			return None;
+		}
+		let mut result = [0.0; 4];
+		for (i, item) in array.iter().enumerate()
+		{
+			result[i] = item.as_f64()? as f32;
+		}
+		return Some(result);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// resolve a class and its parents, "stack" detect loops in "extends"
+	fn parseClass(classes: &Map<String, JsonValue>, name: &str, styles: &mut HashMap<String, UiStyle>, stack: &mut Vec<String>) -> anyhow::Result<UiStyle>
+	{
+		if let Some(style) = styles.get(name)
+		{
+			return Ok(style.clone());
+		}
+		if (stack.iter().any(|x| x == name))
+		{
+			return Err(anyhow!("theme class \"{}\" : extends loop ({})", name, stack.join(" > ")));
+		}
+		let Some(fields) = classes.get(name).and_then(|x| x.as_object())
+		else
+		{
+			return Err(anyhow!("theme class \"{}\" : missing or not an object", name));
+		};
+
+		stack.push(name.to_string());
+		let parent = match fields.get("extends")
+		{
+			Some(JsonValue::String(parent)) => Some(parent.as_str()),
+			Some(_) => return Err(anyhow!("theme class \"{}\" : extends must be a class name", name)),
+			None if name != UITHEME_DEFAULT_CLASS && classes.contains_key(UITHEME_DEFAULT_CLASS) => Some(UITHEME_DEFAULT_CLASS),
+			None => None,
+		};
+		let mut style = match parent
+		{
+			Some(parent) => Self::parseClass(classes, parent, styles, stack)?,
+			None => UiStyle::default(),
+		};
+		stack.pop();
+
+		for (field, value) in fields
+		{
+			Self::parseField(&mut style, field, value).map_err(|err| anyhow!("theme class \"{}\", field \"{}\" : {}", name, field, err))?;
+		}
+		styles.insert(name.to_string(), style.clone());
+		return Ok(style);
+	}
+
+	fn parseField(style: &mut UiStyle, field: &str, value: &JsonValue) -> anyhow::Result<()>
+	{
+		match field
+		{
+			"extends" =>
+			{}
+			"background" => style.background = Self::parseColor(value)?,
+			"backgroundHover" => style.backgroundHover = Self::parseColor(value)?,
+			"backgroundPressed" => style.backgroundPressed = Self::parseColor(value)?,
+			"foreground" => style.foreground = Self::parseColor(value)?,
+			"focus" => style.focus = Self::parseColor(value)?,
+			"text" => style.text = Self::parseColor(value)?,
+			"textSize" => style.textSize = Self::parseTextSize(value)?,
+			"font" => style.font = value.as_str().ok_or(anyhow!("must be a string"))?.to_string(),
+			"padding" => style.padding = value.as_f64().ok_or(anyhow!("must be a number"))? as f32,
+			"border" => style.border = value.as_f64().ok_or(anyhow!("must be a number"))? as f32,
+			"texture" =>
+			{
+				style.texture = match value
+				{
+					JsonValue::Null => None,
+					JsonValue::String(texture) => Some(texture.clone()),
+					_ => return Err(anyhow!("must be a texture name or null")),
+				};
+			}
+			"textureSlice" =>
+			{
+				style.textureSlice = match value
+				{
+					JsonValue::Number(size) => [size.as_f64().unwrap_or(0.0) as f32; 4],
+					_ => Self::parseArray4(value).ok_or(anyhow!("must be a number or [left, top, right, bottom]"))?,
+				};
+			}
+			_ => return Err(anyhow!("unknown field")),
+		}
+		return Ok(());
+	}
+}
@@ -0,0 +1,108 @@
+use crate::Animation::AnimationUtils;
+use crate::HGEMain::HGEMain;
+use crate::Shaders::HGE_shader_2Dsimple::HGE_shader_2Dsimple_transform;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UiTransition_type
+{
+	/// the old page fade out while the new one fade in
+	FADE,
+	/// both pages move to the left, the new page come from the right
+	SLIDE_LEFT,
+	/// both pages move to the right, the new page come from the left
+	SLIDE_RIGHT,
+	/// both pages move up, the new page come from the bottom
+	SLIDE_UP,
+	/// both pages move down, the new page come from the top
+	SLIDE_DOWN,
+	/// the old page grow and fade out, the new one grow from smaller and fade in
+	SCALE,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UiTransition_easing
+{
+	LINEAR,
+	/// slow start and end
+	SMOOTHSTEP,
+	/// slow start, bigger value is slower (see AnimationUtils::pow)
+	EASE_IN(u16),
+	/// fast start, bigger value is faster (see AnimationUtils::sqrt)
+	EASE_OUT(u16),
+}
+
+/// animation between two pages, see ManagerInterface::changeActivePage_transition
+#[derive(Clone, Copy, Debug)]
+pub struct UiTransition
+{
+	pub kind: UiTransition_type,
+	pub duration: Duration,
+	pub easing: UiTransition_easing,
+}
+
+// size of the new page at the start of a SCALE transition
+const UITRANSITION_SCALE_FROM: f32 = 0.9;
+
+impl UiTransition
+{
+	pub fn new(kind: UiTransition_type, duration: Duration) -> Self
+	{
+		return UiTransition {
+			kind,
+			duration,
+			easing: UiTransition_easing::SMOOTHSTEP,
+		};
+	}
+
+	pub fn withEasing(mut self, easing: UiTransition_easing) -> Self
+	{
+		self.easing = easing;
+		return self;
+	}
+
+	/// transform of the old page and of the new page for a progress between 0.0 and 1.0
+	pub fn transforms(&self, progress: f32) -> [HGE_shader_2Dsimple_transform; 2]
+	{
+		let progress = match self.easing
+		{
+			UiTransition_easing::LINEAR => AnimationUtils::linear(0.0, 1.0, progress),
+			UiTransition_easing::SMOOTHSTEP => AnimationUtils::smoothstep(0.0, 1.0, progress),
+			UiTransition_easing::EASE_IN(pow) => AnimationUtils::pow(0.0, 1.0, progress, pow),
+			UiTransition_easing::EASE_OUT(iter) => AnimationUtils::sqrt(0.0, 1.0, progress, iter),
+		};
+		let mut oldpage = HGE_shader_2Dsimple_transform::default();
+		let mut newpage = HGE_shader_2Dsimple_transform::default();
+
+		match self.kind
+		{
+			UiTransition_type::FADE =>
+			{
+				oldpage.alpha = 1.0 - progress;
+				newpage.alpha = progress;
+			}
+			UiTransition_type::SCALE =>
+			{
+				oldpage.alpha = 1.0 - progress;
+				oldpage.scale = 1.0 + (1.0 - UITRANSITION_SCALE_FROM) * progress;
+				newpage.alpha = progress;
+				newpage.scale = UITRANSITION_SCALE_FROM + (1.0 - UITRANSITION_SCALE_FROM) * progress;
+			}
+			_ =>
+			{
+				let window = HGEMain::singleton().getWindowInfos();
+				let (axis, size, direction) = match self.kind
+				{
+					UiTransition_type::SLIDE_LEFT => (0, window.widthF, -1.0),
+					UiTransition_type::SLIDE_RIGHT => (0, window.widthF, 1.0),
+					UiTransition_type::SLIDE_UP => (1, window.heightF, -1.0),
+					_ => (1, window.heightF, 1.0),
+				};
+				oldpage.offset[axis] = direction * size * progress;
+				newpage.offset[axis] = -direction * size * (1.0 - progress);
+			}
+		}
+
+		return [oldpage, newpage];
+	}
+}
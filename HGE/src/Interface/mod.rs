@@ -1,10 +1,28 @@
 pub mod ManagerInterface;
 pub mod Line;
 pub mod Text;
+pub mod TextMarkup;
+pub mod TextEffect;
 pub mod Utils2D;
 pub mod ManagerFont;
+pub mod FontSdf;
+pub mod ManagerLocale;
 pub mod UiPage;
 pub mod UiButton;
 pub mod Bar;
 pub mod UiHitbox;
 pub mod UiHidable;
+pub mod UiLayout;
+pub mod UiTextInput;
+pub mod UiScrollView;
+pub mod UiStyle;
+pub mod UiTheme;
+pub mod UiPanel9Slice;
+pub mod UiMarkup;
+pub mod UiOverlay;
+pub mod UiTransition;
+pub mod UiCheckbox;
+pub mod UiRadioGroup;
+pub mod UiSlider;
+pub mod UiDropdown;
+pub mod UiSpinner;
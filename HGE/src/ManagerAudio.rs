@@ -1,536 +1,1848 @@
 use crate::assetStreamReader::assetManager;
+use crate::components::system::TimeStats::TimeStatsStorage;
+use crate::components::worldPosition::worldPosition;
+use crate::configs::HGEconfig::HGEconfig;
+use crate::HGEMain::HGEMain;
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
-use foldhash::HashMap;
+use foldhash::{HashMap, HashSet};
 use parking_lot::{Mutex, RwLock};
+use rodio::dynamic_mixer::{DynamicMixer, DynamicMixerController};
+use rodio::source::Spatial;
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
-use std::io::Cursor;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
 use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
+use Hconfig::serde_json::Value as JsonValue;
+use Hconfig::HConfigManager::HConfigManager;
 use Htrace::{namedThread, HTrace};
 
-#[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
-pub enum audio_channel
+/// default bus used by the music playlist
+pub const AUDIO_BUS_MUSIC: &str = "music";
+/// default bus used by effect_play*
+pub const AUDIO_BUS_EFFECT: &str = "effect";
+
+// half distance between the two ears of the listener, in "spatial_refDistance" unit
+const SPATIAL_EAR_HALFDISTANCE: f32 = 0.1;
+// number of spatial voices, shared by all buses
+const SPATIAL_VOICES: usize = 8;
+// ducking level change per second
+const DUCKING_SPEED: f32 = 4.0;
+// default memory budget of the decoded effects cache, in bytes
+const CACHE_DEFAULT_BUDGET: usize = 32 * 1024 * 1024;
+// output format of null and offline streams
+const OFFLINE_CHANNELS: u16 = 2;
+const OFFLINE_SAMPLERATE: u32 = 44100;
+
+/// what to do when a bus have all its voices playing and a new sound is requested
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum audio_bus_stealing
 {
-	MUSIC,
-	EFFECT,
+	/// stop the voice that started first
+	OLDEST,
+	/// stop the voice with the lowest volume
+	QUIETEST,
+	/// ignore the new sound
+	REFUSE,
 }
 
-struct audio_queue
+impl audio_bus_stealing
 {
-	name: String,
-	channel: audio_channel,
-	volume: f32,
+	// voice to stop among the "playing" ones (id, start, level)
+	fn victim(self, mut playing: impl Iterator<Item = (usize, Instant, f32)>) -> Option<usize>
+	{
+		return match self
+		{
+			audio_bus_stealing::OLDEST => playing.min_by_key(|(_, startedAt, _)| *startedAt).map(|(id, _, _)| id),
+			audio_bus_stealing::QUIETEST => playing.min_by(|(_, _, a), (_, _, b)| a.total_cmp(b)).map(|(id, _, _)| id),
+			audio_bus_stealing::REFUSE => None,
+		};
+	}
 }
 
-struct audio_storage
+/// effect applied to every sound played on a bus
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum audio_bus_effect
 {
-	path: String,
-	level: f32,
+	/// cutoff frequency in Hz
+	LOWPASS(u32),
+	/// delay of the echo and amplitude of the echo
+	REVERB(Duration, f32),
 }
 
-struct audio_channel_sink
+#[derive(Debug, Clone)]
+pub struct audio_bus
 {
-	pub sink: Sink,
-	pub content_level: f32,
+	pub level: f32,
+	pub maxVoices: u8,
+	pub stealing: audio_bus_stealing,
+	pub effects: Vec<audio_bus_effect>,
+	/// (bus name, level) : this bus is lowered to "level" while the other bus play something
+	pub duckedBy: Vec<(String, f32)>,
 }
 
-impl audio_channel_sink
+impl audio_bus
 {
-	pub fn new(handle: &OutputStreamHandle) -> Self
+	pub fn new(maxVoices: u8) -> Self
 	{
-		audio_channel_sink {
-			sink: Sink::try_new(&handle).unwrap(),
-			content_level: 1.0,
+		Self {
+			level: 1.0,
+			maxVoices: maxVoices.max(1),
+			stealing: audio_bus_stealing::OLDEST,
+			effects: vec![],
+			duckedBy: vec![],
 		}
 	}
 }
 
-pub struct deviceStream
+impl Default for audio_bus
 {
-	pub stream: OutputStream,
-	pub handle: OutputStreamHandle,
-	_channel: HashMap<audio_channel, Vec<audio_channel_sink>>,
-	_channelRun: HashMap<audio_channel, u8>,
-	_isPaused: bool,
+	fn default() -> Self
+	{
+		Self::new(8)
+	}
 }
 
-impl deviceStream
+/// how the playlist continue when a track end
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum audio_music_repeat
+{
+	/// loop on the whole playlist
+	ALL,
+	/// loop on the current track
+	ONE,
+	/// stop at the end of the playlist
+	NONE,
+}
+
+/// event sent to music_onEvent callbacks, with the name of the track
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum audio_music_event
+{
+	STARTED(String),
+	ENDED(String),
+}
+
+enum audio_music_request
+{
+	PLAY(String),
+	NEXT,
+	PREV,
+}
+
+/// sound triggered on a null or offline stream, see deviceStream::history_take()
+#[derive(PartialEq, Debug, Clone)]
+pub struct audio_played
 {
-	fn addChannel(&mut self, channel: audio_channel)
+	pub name: String,
+	pub bus: String,
+	/// volume of the voice when the sound started (global, bus, ducking and local level, without spatial attenuation)
+	pub level: f32,
+	/// position of the emitter, for spatialized effect
+	pub position: Option<worldPosition>,
+}
+
+struct audio_queue
+{
+	name: String,
+	bus: String,
+	volume: f32,
+	emitter: Option<audio_emitter>,
+}
+
+/// position of a spatialized sound, can be updated while the sound is playing
+#[derive(Clone)]
+pub struct audio_emitter
+{
+	_position: Arc<ArcSwap<worldPosition>>,
+	_follow: Option<Arc<dyn Fn() -> Option<worldPosition> + Send + Sync>>,
+	_stop: Arc<ArcSwap<bool>>,
+}
+
+impl audio_emitter
+{
+	fn new(position: worldPosition, follow: Option<Arc<dyn Fn() -> Option<worldPosition> + Send + Sync>>) -> Self
 	{
-		match self._channel.get_mut(&channel)
-		{
-			None =>
-			{
-				self._channel
-					.insert(channel, vec![audio_channel_sink::new(&self.handle)]);
-			}
-			Some(data) =>
-			{
-				data.push(audio_channel_sink::new(&self.handle));
-			}
-		};
+		Self {
+			_position: Arc::new(ArcSwap::new(Arc::new(position))),
+			_follow: follow,
+			_stop: Arc::new(ArcSwap::new(Arc::new(false))),
+		}
 	}
 
-	fn checkPause(&mut self, globalpause: bool)
+	pub fn position_set(&self, position: worldPosition)
 	{
-		if (globalpause == self._isPaused)
-		{
-			return;
-		}
+		self._position.swap(Arc::new(position));
+	}
 
-		if (globalpause)
+	pub fn position_get(&self) -> worldPosition
+	{
+		**self._position.load()
+	}
+
+	/// stop the sound at next "executeAudioQueue"
+	pub fn stop(&self)
+	{
+		self._stop.swap(Arc::new(true));
+	}
+
+	pub fn isStopped(&self) -> bool
+	{
+		**self._stop.load()
+	}
+
+	/// update position from the follow function, return false if the sound must stop
+	fn update(&self) -> bool
+	{
+		if (self.isStopped())
 		{
-			self._channel.iter_mut().for_each(|(_, sinks)| {
-				sinks.iter_mut().for_each(|sink| {
-					sink.sink.pause();
-				});
-			});
+			return false;
 		}
-		else
+
+		if let Some(follow) = &self._follow
 		{
-			self._channel.iter_mut().for_each(|(_, sinks)| {
-				sinks.iter_mut().for_each(|sink| {
-					sink.sink.play();
-				});
-			});
+			match follow()
+			{
+				None =>
+				{
+					self.stop();
+					return false;
+				}
+				Some(position) => self.position_set(position),
+			}
 		}
-		self._isPaused = globalpause;
+
+		return true;
 	}
 }
 
-pub struct ManagerAudio
+#[derive(Clone, Copy, Debug)]
+pub struct audio_listener
 {
-	_loadedSound: DashMap<String, audio_storage>,
-	_queueEffect: Mutex<Vec<audio_queue>>,
-	_musicList: RwLock<Vec<String>>,
-
-	_music_nextplayid: ArcSwap<usize>,
-	_music_lastplay: ArcSwap<Instant>,
-	_music_nextStart: ArcSwap<Duration>,
-	_music_nextStartOk: ArcSwap<bool>,
-	_music_swap: ArcSwap<bool>,
-	// false = sink n°0, true = sink n°1
-	_music_swapval: ArcSwap<f32>,
-	_music_speed: ArcSwap<f32>,
-	_music_update: ArcSwap<bool>,
-
-	_audiolevel_global: ArcSwap<f32>,
-	_audiolevel_music: ArcSwap<f32>,
-	_audiolevel_effect: ArcSwap<f32>,
-
-	_global_pause: ArcSwap<bool>,
-	_music_pause: ArcSwap<bool>,
-	_music_pause_state: ArcSwap<bool>,
-	_effect_clear: ArcSwap<bool>,
+	pub position: [f32; 3],
+	/// yaw in radian, same as the camera
+	pub yaw: f32,
 }
 
-static SINGLETON: OnceLock<ManagerAudio> = OnceLock::new();
-
-impl ManagerAudio
+impl audio_listener
 {
-	pub fn singleton() -> &'static Self
+	fn fromCamera() -> Option<Self>
 	{
-		return SINGLETON.get_or_init(|| Self {
-			_loadedSound: Default::default(),
-			_queueEffect: Mutex::new(vec![]),
-			_musicList: RwLock::new(vec![]),
-
-			_music_nextplayid: ArcSwap::new(Arc::new(0)),
-			_music_lastplay: ArcSwap::new(Arc::new(Instant::now())),
-			_music_nextStart: ArcSwap::new(Arc::new(Duration::from_secs(0))),
-			_music_nextStartOk: ArcSwap::new(Arc::new(true)),
-			_music_swap: ArcSwap::new(Arc::new(false)),
-			_music_swapval: ArcSwap::new(Arc::new(0.0)),
-			_music_speed: ArcSwap::new(Arc::new(1.0)),
-			_music_update: ArcSwap::new(Arc::new(false)),
-
-			_audiolevel_global: ArcSwap::new(Arc::new(1.0)),
-			_audiolevel_music: ArcSwap::new(Arc::new(1.0)),
-			_audiolevel_effect: ArcSwap::new(Arc::new(1.0)),
-
-			_global_pause: ArcSwap::new(Arc::new(false)),
-			_music_pause: ArcSwap::new(Arc::new(false)),
-			_music_pause_state: ArcSwap::new(Arc::new(false)),
-			_effect_clear: ArcSwap::new(Arc::new(false)),
+		if (!HGEMain::isInitialized())
+		{
+			return None;
+		}
+
+		let camera = HGEMain::singleton().getCamera();
+		let camera = camera.get();
+		let pos = camera.getPositionXYZ();
+		let offset = camera.getOffset();
+		return Some(Self {
+			position: [pos[0] + offset[0], pos[1] + offset[1], pos[2] + offset[2]],
+			yaw: camera.getYaw().0.to_radians(),
 		});
 	}
 
-	pub fn globalPause_set(&self, pause: bool, stream: &mut Option<deviceStream>)
+	/// return emitter, left ear, right ear, relative to the listener and scaled by refDistance
+	fn computeEars(&self, emitter: worldPosition, refDistance: f32) -> ([f32; 3], [f32; 3], [f32; 3])
 	{
-		if let Some(stream) = stream
-		{
-			self._global_pause.swap(Arc::new(pause));
-			stream.checkPause(pause);
-		}
-		else
-		{
-			self._global_pause.swap(Arc::new(true));
-		}
+		let refDistance = refDistance.max(0.001);
+		let (yaw_sin, yaw_cos) = self.yaw.sin_cos();
+		let right = [yaw_sin * SPATIAL_EAR_HALFDISTANCE, 0.0, -yaw_cos * SPATIAL_EAR_HALFDISTANCE];
+
+		return (
+			[
+				(emitter.x - self.position[0]) / refDistance,
+				(emitter.y - self.position[1]) / refDistance,
+				(emitter.z - self.position[2]) / refDistance,
+			],
+			[-right[0], -right[1], -right[2]],
+			right,
+		);
 	}
 
-	pub fn globalLevel_set(&self, level: f32)
+	fn distance(&self, emitter: worldPosition) -> f32
 	{
-		self._audiolevel_global
-			.swap(Arc::new(level.clamp(0.0, 1.0)));
+		let x = emitter.x - self.position[0];
+		let y = emitter.y - self.position[1];
+		let z = emitter.z - self.position[2];
+		return (x * x + y * y + z * z).sqrt();
 	}
+}
 
-	pub fn globalLevel_get(&self) -> f32
+impl Default for audio_listener
+{
+	fn default() -> Self
 	{
-		*self._audiolevel_global.load_full()
+		Self {
+			position: [0.0, 0.0, 0.0],
+			yaw: 0.0,
+		}
 	}
+}
 
-	pub fn music_ispaused(&self) -> bool
+struct audio_storage
+{
+	path: String,
+	level: f32,
+}
+
+/// sound fully decoded in PCM, shared between all its playing instances
+#[derive(Clone)]
+struct audio_decoded
+{
+	samples: Arc<Vec<i16>>,
+	channels: u16,
+	sample_rate: u32,
+}
+
+impl audio_decoded
+{
+	fn decode(source: impl Source<Item = i16>) -> Self
 	{
-		**self._music_pause.load()
+		let channels = source.channels();
+		let sample_rate = source.sample_rate();
+		Self {
+			samples: Arc::new(source.collect()),
+			channels,
+			sample_rate,
+		}
 	}
 
-	pub fn music_pause(&self)
+	/// size in bytes
+	fn size(&self) -> usize
 	{
-		self._music_pause.swap(Arc::new(true));
+		self.samples.len() * std::mem::size_of::<i16>()
 	}
 
-	pub fn music_unpause(&self)
+	fn source(&self) -> audio_decoded_source
 	{
-		self._music_pause.swap(Arc::new(false));
+		audio_decoded_source {
+			_datas: self.clone(),
+			_pos: 0,
+		}
 	}
+}
 
-	pub fn musicLevel_set(&self, level: f32)
+struct audio_decoded_source
+{
+	_datas: audio_decoded,
+	_pos: usize,
+}
+
+impl Iterator for audio_decoded_source
+{
+	type Item = i16;
+
+	fn next(&mut self) -> Option<Self::Item>
 	{
-		self._audiolevel_music.swap(Arc::new(level.clamp(0.0, 1.0)));
-		self._music_update.swap(Arc::new(true));
+		let sample = self._datas.samples.get(self._pos).copied();
+		self._pos += 1;
+		return sample;
 	}
 
-	pub fn musicLevel_get(&self) -> f32
+	fn size_hint(&self) -> (usize, Option<usize>)
 	{
-		**self._audiolevel_music.load()
+		let remaining = self._datas.samples.len().saturating_sub(self._pos);
+		(remaining, Some(remaining))
 	}
+}
 
-	pub fn effectLevel_set(&self, level: f32)
+impl Source for audio_decoded_source
+{
+	fn current_frame_len(&self) -> Option<usize>
 	{
-		self._audiolevel_effect
-			.swap(Arc::new(level.clamp(0.0, 1.0)));
+		Some(self._datas.samples.len().saturating_sub(self._pos))
 	}
 
-	pub fn effectLevel_get(&self) -> f32
+	fn channels(&self) -> u16
 	{
-		**self._audiolevel_effect.load()
+		self._datas.channels
 	}
 
-	pub fn getDeviceStream() -> Option<deviceStream>
+	fn sample_rate(&self) -> u32
 	{
-		if let Ok((stream, stream_handle)) = OutputStream::try_default()
-		{
-			let mut tmp = deviceStream {
-				stream,
-				handle: stream_handle,
-				_channel: Default::default(),
-				_channelRun: Default::default(),
-				_isPaused: false,
-			};
-
-			for _ in 0..1
-			{
-				tmp.addChannel(audio_channel::MUSIC);
-			}
+		self._datas.sample_rate
+	}
 
-			for _ in 0..8
-			{
-				tmp.addChannel(audio_channel::EFFECT);
-			}
+	fn total_duration(&self) -> Option<Duration>
+	{
+		let frames = self._datas.samples.len() as u64 / self._datas.channels.max(1) as u64;
+		Some(Duration::from_secs_f64(frames as f64 / self._datas.sample_rate.max(1) as f64))
+	}
+}
 
-			return Some(tmp);
-		}
+/// LRU cache of decoded effects, limited by a memory budget
+struct audio_cache
+{
+	_sounds: HashMap<String, (audio_decoded, u64)>,
+	_tick: u64,
+	_size: usize,
+	_budget: usize,
+}
 
-		Self::singleton()._global_pause.swap(Arc::new(true));
-		return None;
+impl audio_cache
+{
+	fn get(&mut self, name: &String) -> Option<audio_decoded>
+	{
+		self._tick += 1;
+		let tick = self._tick;
+		let (decoded, lastuse) = self._sounds.get_mut(name)?;
+		*lastuse = tick;
+		return Some(decoded.clone());
 	}
 
-	pub fn loadFile(&self, name: impl Into<String>, path: impl Into<String>, localLevel: f32)
+	/// insert a decoded sound, evicting the least recently used, return false if the sound is bigger than the budget
+	fn insert(&mut self, name: String, decoded: audio_decoded) -> bool
 	{
-		let name = name.into();
-		let path = path.into();
-
-		HTrace!("manager audio : loadFile {}", path);
-
-		if assetManager::singleton().checkFile(path.clone())
+		self.remove(&name);
+		if (decoded.size() > self._budget)
 		{
-			self._loadedSound.insert(
-				name,
-				audio_storage {
-					path: path,
-					level: localLevel,
-				},
-			);
+			return false;
 		}
+
+		self.evict(self._budget - decoded.size());
+		self._tick += 1;
+		self._size += decoded.size();
+		self._sounds.insert(name, (decoded, self._tick));
+		self.updateStats();
+		return true;
 	}
 
-	pub fn effect_play(&self, name: impl Into<String>)
+	fn remove(&mut self, name: &String)
 	{
-		self.effect_play_vol(name, 1.0);
+		if let Some((decoded, _)) = self._sounds.remove(name)
+		{
+			self._size -= decoded.size();
+			self.updateStats();
+		}
 	}
 
-	pub fn effect_play_vol(&self, name: impl Into<String>, volume: f32)
+	/// remove least recently used sounds until the cache size is under "maxsize"
+	fn evict(&mut self, maxsize: usize)
 	{
-		#[cfg(target_os = "android")]
+		while (self._size > maxsize)
 		{
-			// on android, cancel playing of effect too
-			if (*self._music_pause.load_full())
+			let Some(oldest) = self._sounds.iter().min_by_key(|(_, (_, lastuse))| *lastuse).map(|(name, _)| name.clone())
+			else
 			{
-				return;
-			}
+				break;
+			};
+			self.remove(&oldest);
 		}
-
-		let name = name.into();
-		let _ = namedThread!(move || {
-			Self::singleton()._queueEffect.lock().push(audio_queue {
-				name: name,
-				channel: audio_channel::EFFECT,
-				volume: volume.clamp(0.0, 2.0),
-			});
-		});
 	}
 
-	pub fn effect_clearAll(&self)
+	fn clear(&mut self)
 	{
-		self._effect_clear.swap(Arc::new(true));
+		self._sounds.clear();
+		self._size = 0;
+		self.updateStats();
 	}
 
-	pub fn music_add(&self, name: impl Into<String>)
+	fn updateStats(&self)
 	{
-		let name = name.into();
-		let _ = namedThread!(move || {
-			Self::singleton()._musicList.write().push(name);
-		});
+		TimeStatsStorage::setValue("A_cacheSize", self._size as u128);
+		TimeStatsStorage::setValue("A_cacheCount", self._sounds.len() as u128);
 	}
+}
 
-	pub fn music_changeSpeed(&self, speed: f32)
+struct audio_channel_sink
+{
+	pub sink: Sink,
+	pub content_level: f32,
+	pub startedAt: Instant,
+	pub name: Option<String>,
+	pub duration: Option<Duration>,
+	pub fade: f32,
+	// fade change per second, stop the sink when fade reach 0.0
+	pub fadeStep: f32,
+}
+
+impl audio_channel_sink
+{
+	pub fn new(sink: Sink) -> Self
 	{
-		self._music_speed.swap(Arc::new(speed.clamp(0.0, 2.0)));
-		self._music_update.swap(Arc::new(true));
+		audio_channel_sink {
+			sink,
+			content_level: 1.0,
+			startedAt: Instant::now(),
+			name: None,
+			duration: None,
+			fade: 1.0,
+			fadeStep: 0.0,
+		}
 	}
 
-	pub fn resumeAllAudio(&self, stream: &mut Option<deviceStream>)
+	fn fadeIn(&mut self, duration: Duration)
 	{
-		if let Some(stream) = stream
+		if (duration.is_zero())
 		{
-			stream._channel.iter().for_each(|(_, list)| {
-				list.iter().for_each(|sink| {
-					if (!sink.sink.empty())
-					{
-						sink.sink.play();
-					}
-				})
-			});
+			self.fade = 1.0;
+			self.fadeStep = 0.0;
+			return;
 		}
+		self.fade = 0.0;
+		self.fadeStep = 1.0 / duration.as_secs_f32();
 	}
 
-	pub fn executeAudioQueue(&self, stream: &mut Option<deviceStream>)
+	fn fadeOut(&mut self, duration: Duration)
 	{
-		if let Some(stream) = stream
+		if (duration.is_zero())
 		{
-			if (*self._music_pause.load_full() != *self._music_pause_state.load_full())
-			{
-				if (*self._music_pause.load_full())
-				{
-					stream
-						._channel
-						.get_mut(&audio_channel::MUSIC)
-						.unwrap()
-						.iter_mut()
-						.for_each(|sink| {
-							if (!sink.sink.empty())
-							{
-								sink.sink.stop();
-							}
-						});
-				}
-				let _ = *self
-					._music_pause_state
-					.swap(self._music_pause.load_full().clone());
-			}
+			self.fade = 0.0;
+			self.fadeStep = 0.0;
+			self.sink.stop();
+			return;
+		}
+		self.fadeStep = -1.0 / duration.as_secs_f32();
+	}
 
-			if (!*self._music_pause_state.load_full())
+	fn fadeUpdate(&mut self, elapsed: f32)
+	{
+		if (self.fadeStep == 0.0)
+		{
+			return;
+		}
+
+		self.fade += self.fadeStep * elapsed;
+		if (self.fade >= 1.0)
+		{
+			self.fade = 1.0;
+			self.fadeStep = 0.0;
+		}
+		else if (self.fade <= 0.0)
+		{
+			self.fade = 0.0;
+			self.fadeStep = 0.0;
+			self.sink.stop();
+		}
+	}
+
+	/// time before the end of the sound, if its duration is known
+	fn remaining(&self) -> Option<Duration>
+	{
+		self.duration.map(|duration| duration.saturating_sub(self.sink.get_pos()))
+	}
+
+	/// return a free voice, or a voice to steal following "stealing"
+	fn selectVoice(sinks: &mut Vec<audio_channel_sink>, stealing: audio_bus_stealing) -> Option<&mut audio_channel_sink>
+	{
+		let id = match sinks.iter().position(|x| x.sink.empty())
+		{
+			Some(id) => Some(id),
+			None => stealing.victim(sinks.iter().enumerate().map(|(id, x)| (id, x.startedAt, x.content_level))),
+		};
+
+		return id.and_then(move |id| sinks.get_mut(id));
+	}
+}
+
+/// same as rodio SpatialSink, but build on any Sink (so it work with null and offline stream)
+struct audio_spatial_sink
+{
+	pub sink: Sink,
+	pub content_level: f32,
+	pub startedAt: Instant,
+	pub bus: String,
+	pub emitter: Option<audio_emitter>,
+	// emitter, left ear, right ear
+	_positions: Arc<Mutex<[[f32; 3]; 3]>>,
+}
+
+impl audio_spatial_sink
+{
+	pub fn new(sink: Sink) -> Self
+	{
+		let (emitter, left, right) = audio_listener::default().computeEars(worldPosition::default(), 1.0);
+		audio_spatial_sink {
+			sink,
+			content_level: 1.0,
+			startedAt: Instant::now(),
+			bus: AUDIO_BUS_EFFECT.to_string(),
+			emitter: None,
+			_positions: Arc::new(Mutex::new([emitter, left, right])),
+		}
+	}
+
+	/// return a free voice of the shared pool while "bus" play less than "maxVoices" sounds, or one of its voices to steal following "stealing"
+	fn selectVoice<'a>(sinks: &'a mut Vec<audio_spatial_sink>, bus: &String, maxVoices: u8, stealing: audio_bus_stealing) -> Option<&'a mut audio_spatial_sink>
+	{
+		let playing: Vec<(usize, Instant, f32)> = sinks
+			.iter()
+			.enumerate()
+			.filter(|(_, x)| x.bus == *bus && !x.sink.empty())
+			.map(|(id, x)| (id, x.startedAt, x.content_level))
+			.collect();
+
+		let id = match sinks.iter().position(|x| x.sink.empty())
+		{
+			Some(id) if (playing.len() < maxVoices as usize) => Some(id),
+			_ => stealing.victim(playing.into_iter()),
+		};
+
+		return id.and_then(move |id| sinks.get_mut(id));
+	}
+
+	fn setPositions(&self, emitter: [f32; 3], left: [f32; 3], right: [f32; 3])
+	{
+		*self._positions.lock() = [emitter, left, right];
+	}
+
+	fn append(&self, source: Box<dyn Source<Item = f32> + Send>)
+	{
+		let positions = self._positions.clone();
+		let [emitter, left, right] = *positions.lock();
+		let source = Spatial::new(source, emitter, left, right).periodic_access(Duration::from_millis(10), move |spatial| {
+			let [emitter, left, right] = *positions.lock();
+			spatial.set_positions(emitter, left, right);
+		});
+		self.sink.append(source);
+	}
+}
+
+/// write 16 bits PCM wav file, header is completed on finish/drop
+struct audio_wav_writer
+{
+	_file: BufWriter<File>,
+	_dataSize: u32,
+}
+
+impl audio_wav_writer
+{
+	fn new(path: &String, channels: u16, sampleRate: u32) -> std::io::Result<Self>
+	{
+		let mut file = BufWriter::new(File::create(path)?);
+		file.write_all(b"RIFF")?;
+		file.write_all(&36u32.to_le_bytes())?;
+		file.write_all(b"WAVEfmt ")?;
+		file.write_all(&16u32.to_le_bytes())?;
+		file.write_all(&1u16.to_le_bytes())?;
+		file.write_all(&channels.to_le_bytes())?;
+		file.write_all(&sampleRate.to_le_bytes())?;
+		file.write_all(&(sampleRate * channels as u32 * 2).to_le_bytes())?;
+		file.write_all(&(channels * 2).to_le_bytes())?;
+		file.write_all(&16u16.to_le_bytes())?;
+		file.write_all(b"data")?;
+		file.write_all(&0u32.to_le_bytes())?;
+
+		return Ok(Self {
+			_file: file,
+			_dataSize: 0,
+		});
+	}
+
+	fn write(&mut self, sample: f32)
+	{
+		let sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+		if (self._file.write_all(&sample.to_le_bytes()).is_ok())
+		{
+			self._dataSize += 2;
+		}
+	}
+
+	fn finish(&mut self) -> std::io::Result<()>
+	{
+		self._file.seek(SeekFrom::Start(4))?;
+		self._file.write_all(&(36 + self._dataSize).to_le_bytes())?;
+		self._file.seek(SeekFrom::Start(40))?;
+		self._file.write_all(&self._dataSize.to_le_bytes())?;
+		self._file.seek(SeekFrom::End(0))?;
+		self._file.flush()
+	}
+}
+
+impl Drop for audio_wav_writer
+{
+	fn drop(&mut self)
+	{
+		let _ = self.finish();
+	}
+}
+
+/// mixer pulled by the engine instead of a sound card
+struct audio_offline
+{
+	_controller: Arc<DynamicMixerController<f32>>,
+	_mixer: DynamicMixer<f32>,
+	_writer: Option<audio_wav_writer>,
+	_lastPull: Instant,
+}
+
+impl audio_offline
+{
+	fn new(writer: Option<audio_wav_writer>) -> Self
+	{
+		let (controller, mixer) = rodio::dynamic_mixer::mixer::<f32>(OFFLINE_CHANNELS, OFFLINE_SAMPLERATE);
+		Self {
+			_controller: controller,
+			_mixer: mixer,
+			_writer: writer,
+			_lastPull: Instant::now(),
+		}
+	}
+
+	/// mix "duration" of sound, and write it if there is a writer
+	fn pull(&mut self, duration: Duration)
+	{
+		let frames = (duration.as_secs_f64() * OFFLINE_SAMPLERATE as f64) as u64;
+		for _ in 0..frames * OFFLINE_CHANNELS as u64
+		{
+			let sample = self._mixer.next().unwrap_or(0.0);
+			if let Some(writer) = &mut self._writer
+			{
+				writer.write(sample);
+			}
+		}
+		self._lastPull += Duration::from_secs_f64(frames as f64 / OFFLINE_SAMPLERATE as f64);
+	}
+}
+
+pub struct deviceStream
+{
+	/// None for null and offline stream
+	pub stream: Option<OutputStream>,
+	/// None for null and offline stream
+	pub handle: Option<OutputStreamHandle>,
+	_offline: Option<audio_offline>,
+	_history: Option<Vec<audio_played>>,
+	_channel: HashMap<String, Vec<audio_channel_sink>>,
+	_spatial: Vec<audio_spatial_sink>,
+	_ducking: HashMap<String, f32>,
+	_lastUpdate: Instant,
+	_isPaused: bool,
+}
+
+impl deviceStream
+{
+	/// true for null and offline stream
+	pub fn isVirtual(&self) -> bool
+	{
+		self._offline.is_some()
+	}
+
+	/// offline stream only : mix "duration" of sound into the wav file
+	/// null stream advance by itself in "executeAudioQueue"
+	pub fn render(&mut self, duration: Duration)
+	{
+		if let Some(offline) = &mut self._offline
+		{
+			offline.pull(duration);
+		}
+	}
+
+	/// offline stream only : complete the wav file, it's also done when the stream is dropped
+	pub fn finish(&mut self) -> std::io::Result<()>
+	{
+		if let Some(writer) = self._offline.as_mut().and_then(|x| x._writer.as_mut())
+		{
+			return writer.finish();
+		}
+		return Ok(());
+	}
+
+	/// null and offline stream only : return (and empty) the list of sounds started since the last call
+	pub fn history_take(&mut self) -> Vec<audio_played>
+	{
+		self._history.as_mut().map(|x| x.drain(0..).collect()).unwrap_or_default()
+	}
+
+	/// track name (music only) and current volume of every voice playing on "bus", spatial voices included
+	pub fn voices_get(&self, bus: &str) -> Vec<(Option<String>, f32)>
+	{
+		let mut returning: Vec<(Option<String>, f32)> = self
+			._channel
+			.get(bus)
+			.map(|sinks| sinks.iter().filter(|x| !x.sink.empty()).map(|x| (x.name.clone(), x.sink.volume())).collect())
+			.unwrap_or_default();
+		returning.extend(self._spatial.iter().filter(|x| x.bus == bus && !x.sink.empty()).map(|x| (None, x.sink.volume())));
+		return returning;
+	}
+
+	fn newSink(&self) -> Sink
+	{
+		if let Some(handle) = &self.handle
+		{
+			return Sink::try_new(handle).unwrap();
+		}
+
+		let (sink, output) = Sink::new_idle();
+		if let Some(offline) = &self._offline
+		{
+			offline._controller.add(output);
+		}
+		return sink;
+	}
+
+	/// null stream only : mix the time elapsed since the last pull, to make sounds progress
+	fn pullElapsed(&mut self)
+	{
+		if let Some(offline) = &mut self._offline
+		{
+			if (offline._writer.is_none())
+			{
+				offline.pull(offline._lastPull.elapsed());
+			}
+		}
+	}
+
+	fn historyPush(&mut self, played: audio_played)
+	{
+		if let Some(history) = &mut self._history
+		{
+			history.push(played);
+		}
+	}
+
+	/// create or remove voices of a bus to match maxVoices
+	fn syncChannel(&mut self, bus: &String, maxVoices: u8)
+	{
+		let missing = (maxVoices as usize).saturating_sub(self._channel.get(bus).map(|x| x.len()).unwrap_or(0));
+		let newsinks: Vec<audio_channel_sink> = (0..missing).map(|_| audio_channel_sink::new(self.newSink())).collect();
+
+		let sinks = self._channel.entry(bus.clone()).or_insert_with(|| vec![]);
+		sinks.extend(newsinks);
+		sinks.truncate(maxVoices as usize);
+	}
+
+	fn addSpatial(&mut self)
+	{
+		let sink = audio_spatial_sink::new(self.newSink());
+		self._spatial.push(sink);
+	}
+
+	fn checkPause(&mut self, globalpause: bool)
+	{
+		if (globalpause == self._isPaused)
+		{
+			return;
+		}
+
+		if (globalpause)
+		{
+			self._channel.iter_mut().for_each(|(_, sinks)| {
+				sinks.iter_mut().for_each(|sink| {
+					sink.sink.pause();
+				});
+			});
+			self._spatial.iter().for_each(|sink| {
+				sink.sink.pause();
+			});
+		}
+		else
+		{
+			self._channel.iter_mut().for_each(|(_, sinks)| {
+				sinks.iter_mut().for_each(|sink| {
+					sink.sink.play();
+				});
+			});
+			self._spatial.iter().for_each(|sink| {
+				sink.sink.play();
+			});
+		}
+		self._isPaused = globalpause;
+	}
+}
+
+pub struct ManagerAudio
+{
+	_loadedSound: DashMap<String, audio_storage>,
+	_queueEffect: Mutex<Vec<audio_queue>>,
+	_musicList: RwLock<Vec<String>>,
+	_buses: DashMap<String, audio_bus>,
+	_cache: Mutex<audio_cache>,
+
+	// playing order of _musicList, shuffled or not
+	_music_order: RwLock<Vec<usize>>,
+	// position inside _music_order, None if nothing have been played yet
+	_music_position: ArcSwap<Option<usize>>,
+	_music_request: Mutex<Option<audio_music_request>>,
+	_music_shuffle: ArcSwap<bool>,
+	_music_repeat: ArcSwap<audio_music_repeat>,
+	_music_crossfade: ArcSwap<Duration>,
+	_music_current: ArcSwap<Option<String>>,
+	_music_listeners: RwLock<Vec<Arc<dyn Fn(&audio_music_event) + Send + Sync>>>,
+	_music_speed: ArcSwap<f32>,
+	_music_update: ArcSwap<bool>,
+
+	_audiolevel_global: ArcSwap<f32>,
+
+	_global_pause: ArcSwap<bool>,
+	_music_pause: ArcSwap<bool>,
+	_music_pause_state: ArcSwap<bool>,
+	_effect_clear: ArcSwap<bool>,
+
+	_listener: ArcSwap<audio_listener>,
+	_listener_followCamera: ArcSwap<bool>,
+	_spatial_refDistance: ArcSwap<f32>,
+	_spatial_maxDistance: ArcSwap<f32>,
+}
+
+static SINGLETON: OnceLock<ManagerAudio> = OnceLock::new();
+
+impl ManagerAudio
+{
+	pub fn singleton() -> &'static Self
+	{
+		return SINGLETON.get_or_init(|| {
+			let buses = DashMap::new();
+			// 2 voices for crossfading
+			buses.insert(AUDIO_BUS_MUSIC.to_string(), audio_bus::new(2));
+			buses.insert(AUDIO_BUS_EFFECT.to_string(), audio_bus::new(8));
+
+			Self {
+				_loadedSound: Default::default(),
+				_queueEffect: Mutex::new(vec![]),
+				_musicList: RwLock::new(vec![]),
+				_buses: buses,
+				_cache: Mutex::new(audio_cache {
+					_sounds: Default::default(),
+					_tick: 0,
+					_size: 0,
+					_budget: CACHE_DEFAULT_BUDGET,
+				}),
+
+				_music_order: RwLock::new(vec![]),
+				_music_position: ArcSwap::new(Arc::new(None)),
+				_music_request: Mutex::new(None),
+				_music_shuffle: ArcSwap::new(Arc::new(false)),
+				_music_repeat: ArcSwap::new(Arc::new(audio_music_repeat::ALL)),
+				_music_crossfade: ArcSwap::new(Arc::new(Duration::from_secs(0))),
+				_music_current: ArcSwap::new(Arc::new(None)),
+				_music_listeners: RwLock::new(vec![]),
+				_music_speed: ArcSwap::new(Arc::new(1.0)),
+				_music_update: ArcSwap::new(Arc::new(false)),
+
+				_audiolevel_global: ArcSwap::new(Arc::new(1.0)),
+
+				_global_pause: ArcSwap::new(Arc::new(false)),
+				_music_pause: ArcSwap::new(Arc::new(false)),
+				_music_pause_state: ArcSwap::new(Arc::new(false)),
+				_effect_clear: ArcSwap::new(Arc::new(false)),
+
+				_listener: ArcSwap::new(Arc::new(audio_listener::default())),
+				_listener_followCamera: ArcSwap::new(Arc::new(true)),
+				_spatial_refDistance: ArcSwap::new(Arc::new(1.0)),
+				_spatial_maxDistance: ArcSwap::new(Arc::new(100.0)),
+			}
+		});
+	}
+
+	pub fn globalPause_set(&self, pause: bool, stream: &mut Option<deviceStream>)
+	{
+		if let Some(stream) = stream
+		{
+			self._global_pause.swap(Arc::new(pause));
+			stream.checkPause(pause);
+		}
+		else
+		{
+			self._global_pause.swap(Arc::new(true));
+		}
+	}
+
+	pub fn globalLevel_set(&self, level: f32)
+	{
+		self._audiolevel_global
+			.swap(Arc::new(level.clamp(0.0, 1.0)));
+	}
+
+	pub fn globalLevel_get(&self) -> f32
+	{
+		*self._audiolevel_global.load_full()
+	}
+
+	pub fn music_ispaused(&self) -> bool
+	{
+		**self._music_pause.load()
+	}
+
+	pub fn music_pause(&self)
+	{
+		self._music_pause.swap(Arc::new(true));
+	}
+
+	pub fn music_unpause(&self)
+	{
+		self._music_pause.swap(Arc::new(false));
+	}
+
+	pub fn musicLevel_set(&self, level: f32)
+	{
+		self.busLevel_set(AUDIO_BUS_MUSIC, level);
+	}
+
+	pub fn musicLevel_get(&self) -> f32
+	{
+		self.busLevel_get(AUDIO_BUS_MUSIC)
+	}
+
+	pub fn effectLevel_set(&self, level: f32)
+	{
+		self.busLevel_set(AUDIO_BUS_EFFECT, level);
+	}
+
+	pub fn effectLevel_get(&self) -> f32
+	{
+		self.busLevel_get(AUDIO_BUS_EFFECT)
+	}
+
+	/// add or replace a bus, voices are created at next "executeAudioQueue"
+	pub fn bus_set(&self, name: impl Into<String>, bus: audio_bus)
+	{
+		self._buses.insert(name.into(), bus);
+	}
+
+	pub fn bus_get(&self, name: impl Into<String>) -> Option<audio_bus>
+	{
+		self._buses.get(&name.into()).map(|x| x.clone())
+	}
+
+	pub fn bus_list(&self) -> Vec<String>
+	{
+		self._buses.iter().map(|x| x.key().clone()).collect()
+	}
+
+	pub fn busLevel_set(&self, name: impl Into<String>, level: f32)
+	{
+		if let Some(mut bus) = self._buses.get_mut(&name.into())
+		{
+			bus.level = level.clamp(0.0, 1.0);
+		}
+	}
+
+	pub fn busLevel_get(&self, name: impl Into<String>) -> f32
+	{
+		self._buses.get(&name.into()).map(|x| x.level).unwrap_or(0.0)
+	}
+
+	/// load global and buses levels from the config file (system/audio/)
+	/// buses must be defined before calling this
+	pub fn levels_loadFromConfig(&self)
+	{
+		let mut config = HConfigManager::singleton().get(HGEconfig::singleton().general_get().configName.clone());
+
+		let global = config
+			.getOrSetDefault("system/audio/global", JsonValue::from(self.globalLevel_get()))
+			.as_f64()
+			.unwrap_or(1.0) as f32;
+		self.globalLevel_set(global);
+
+		self._buses.iter_mut().for_each(|mut bus| {
+			let level = config
+				.getOrSetDefault(&format!("system/audio/bus/{}", bus.key()), JsonValue::from(bus.level))
+				.as_f64()
+				.unwrap_or(1.0) as f32;
+			bus.level = level.clamp(0.0, 1.0);
+		});
+
+		HTrace!("loaded audio levels config");
+
+		let _ = config.save();
+	}
+
+	/// save global and buses levels into the config file (system/audio/)
+	pub fn levels_saveToConfig(&self)
+	{
+		let mut config = HConfigManager::singleton().get(HGEconfig::singleton().general_get().configName.clone());
+
+		config.set("system/audio/global", self.globalLevel_get());
+		self._buses.iter().for_each(|bus| {
+			config.set(&format!("system/audio/bus/{}", bus.key()), bus.level);
+		});
+
+		let _ = config.save();
+	}
+
+	/// memory budget (in bytes) of the decoded effects cache, least recently used effects are removed to fit in it
+	pub fn cache_budget_set(&self, budget: usize)
+	{
+		let mut cache = self._cache.lock();
+		cache._budget = budget;
+		cache.evict(budget);
+	}
+
+	pub fn cache_budget_get(&self) -> usize
+	{
+		self._cache.lock()._budget
+	}
+
+	/// actual size (in bytes) of the decoded effects cache
+	pub fn cache_size_get(&self) -> usize
+	{
+		self._cache.lock()._size
+	}
+
+	pub fn cache_clear(&self)
+	{
+		self._cache.lock().clear();
+	}
+
+	/// set the listener used by spatialized effect, disable the camera follow
+	pub fn listener_set(&self, listener: audio_listener)
+	{
+		self._listener_followCamera.swap(Arc::new(false));
+		self._listener.swap(Arc::new(listener));
+	}
+
+	pub fn listener_get(&self) -> audio_listener
+	{
+		**self._listener.load()
+	}
+
+	/// if true (default), the listener follow the position and yaw of HGEMain camera
+	pub fn listener_followCamera(&self, follow: bool)
+	{
+		self._listener_followCamera.swap(Arc::new(follow));
+	}
+
+	/// refDistance : distance where the effect is played at full volume, attenuation start after it
+	/// maxDistance : distance where the effect is not audible anymore
+	pub fn spatialDistance_set(&self, refDistance: f32, maxDistance: f32)
+	{
+		let refDistance = refDistance.max(0.001);
+		self._spatial_refDistance.swap(Arc::new(refDistance));
+		self._spatial_maxDistance.swap(Arc::new(maxDistance.max(refDistance)));
+	}
+
+	pub fn getDeviceStream() -> Option<deviceStream>
+	{
+		if let Ok((stream, stream_handle)) = OutputStream::try_default()
+		{
+			return Some(Self::buildStream(Some(stream), Some(stream_handle), None));
+		}
+
+		Self::singleton()._global_pause.swap(Arc::new(true));
+		return None;
+	}
+
+	/// stream without sound card : sounds are mixed and discarded, following real time
+	/// the list of played sounds is available with deviceStream::history_take()
+	pub fn getNullStream() -> deviceStream
+	{
+		Self::buildStream(None, None, Some(audio_offline::new(None)))
+	}
+
+	/// stream rendering into a wav file (path is not relative to static), time only advance with deviceStream::render()
+	/// the list of played sounds is available with deviceStream::history_take()
+	pub fn getOfflineStream(path: impl Into<String>) -> anyhow::Result<deviceStream>
+	{
+		let writer = audio_wav_writer::new(&path.into(), OFFLINE_CHANNELS, OFFLINE_SAMPLERATE)?;
+		return Ok(Self::buildStream(None, None, Some(audio_offline::new(Some(writer)))));
+	}
+
+	pub fn loadFile(&self, name: impl Into<String>, path: impl Into<String>, localLevel: f32)
+	{
+		let name = name.into();
+		let path = path.into();
+
+		HTrace!("manager audio : loadFile {}", path);
+
+		if assetManager::singleton().checkFile(path.clone())
+		{
+			self._cache.lock().remove(&name);
+			self._loadedSound.insert(
+				name,
+				audio_storage {
+					path: path,
+					level: localLevel,
+				},
+			);
+		}
+	}
+
+	pub fn effect_play(&self, name: impl Into<String>)
+	{
+		self.effect_play_vol(name, 1.0);
+	}
+
+	pub fn effect_play_vol(&self, name: impl Into<String>, volume: f32)
+	{
+		self.effect_play_bus(name, AUDIO_BUS_EFFECT, volume);
+	}
+
+	/// play a effect on a specific bus
+	pub fn effect_play_bus(&self, name: impl Into<String>, bus: impl Into<String>, volume: f32)
+	{
+		self.effect_queue(name.into(), bus.into(), volume, None);
+	}
+
+	/// play a effect at a fixed position in the world, panned and attenuated relative to the listener
+	pub fn effect_play_at(&self, name: impl Into<String>, position: worldPosition) -> audio_emitter
+	{
+		self.effect_play_at_vol(name, position, 1.0)
+	}
+
+	pub fn effect_play_at_vol(&self, name: impl Into<String>, position: worldPosition, volume: f32) -> audio_emitter
+	{
+		self.effect_play_at_bus(name, AUDIO_BUS_EFFECT, position, volume)
+	}
+
+	pub fn effect_play_at_bus(&self, name: impl Into<String>, bus: impl Into<String>, position: worldPosition, volume: f32) -> audio_emitter
+	{
+		let emitter = audio_emitter::new(position, None);
+		self.effect_queue(name.into(), bus.into(), volume, Some(emitter.clone()));
+		return emitter;
+	}
+
+	/// play a effect that follow a position over time (like a chunk entity)
+	/// "follow" is called at each "executeAudioQueue", returning None stop the sound (entity dropped, etc)
+	pub fn effect_play_follow(
+		&self,
+		name: impl Into<String>,
+		volume: f32,
+		follow: impl Fn() -> Option<worldPosition> + Send + Sync + 'static,
+	) -> audio_emitter
+	{
+		let follow: Arc<dyn Fn() -> Option<worldPosition> + Send + Sync> = Arc::new(follow);
+		let emitter = audio_emitter::new(follow().unwrap_or_default(), Some(follow));
+		self.effect_queue(name.into(), AUDIO_BUS_EFFECT.to_string(), volume, Some(emitter.clone()));
+		return emitter;
+	}
+
+	/// stop every sound of every bus, except the music bus
+	pub fn effect_clearAll(&self)
+	{
+		self._effect_clear.swap(Arc::new(true));
+	}
+
+	pub fn music_add(&self, name: impl Into<String>)
+	{
+		let name = name.into();
+		let _ = namedThread!(move || {
+			Self::singleton()._musicList.write().push(name);
+		});
+	}
+
+	/// play a loaded sound as music, if it's inside the playlist, the playlist continue from it
+	pub fn music_play(&self, name: impl Into<String>)
+	{
+		*self._music_request.lock() = Some(audio_music_request::PLAY(name.into()));
+	}
+
+	pub fn music_next(&self)
+	{
+		*self._music_request.lock() = Some(audio_music_request::NEXT);
+	}
+
+	pub fn music_prev(&self)
+	{
+		*self._music_request.lock() = Some(audio_music_request::PREV);
+	}
+
+	/// name of the track currently playing (the one fading in, during a crossfade)
+	pub fn music_current(&self) -> Option<String>
+	{
+		(**self._music_current.load()).clone()
+	}
+
+	pub fn music_shuffle_set(&self, shuffle: bool)
+	{
+		self._music_shuffle.swap(Arc::new(shuffle));
+		let musiclist = self._musicList.read().clone();
+		self.musicOrderCheck(&musiclist, true);
+	}
+
+	pub fn music_shuffle_get(&self) -> bool
+	{
+		**self._music_shuffle.load()
+	}
+
+	pub fn music_repeat_set(&self, repeat: audio_music_repeat)
+	{
+		self._music_repeat.swap(Arc::new(repeat));
+	}
+
+	pub fn music_repeat_get(&self) -> audio_music_repeat
+	{
+		**self._music_repeat.load()
+	}
+
+	/// duration of the crossfade between two tracks, 0 to disable it
+	/// on natural track end, crossfade only work if the decoder can give the track duration
+	pub fn music_crossfade_set(&self, duration: Duration)
+	{
+		self._music_crossfade.swap(Arc::new(duration));
+	}
+
+	pub fn music_crossfade_get(&self) -> Duration
+	{
+		**self._music_crossfade.load()
+	}
+
+	/// add a callback called (on the audio thread) when a music track start or end
+	pub fn music_onEvent(&self, callback: impl Fn(&audio_music_event) + Send + Sync + 'static)
+	{
+		self._music_listeners.write().push(Arc::new(callback));
+	}
+
+	pub fn music_changeSpeed(&self, speed: f32)
+	{
+		self._music_speed.swap(Arc::new(speed.clamp(0.0, 2.0)));
+		self._music_update.swap(Arc::new(true));
+	}
+
+	pub fn resumeAllAudio(&self, stream: &mut Option<deviceStream>)
+	{
+		if let Some(stream) = stream
+		{
+			stream._channel.iter().for_each(|(_, list)| {
+				list.iter().for_each(|sink| {
+					if (!sink.sink.empty())
+					{
+						sink.sink.play();
+					}
+				})
+			});
+			stream._spatial.iter().for_each(|sink| {
+				if (!sink.sink.empty())
+				{
+					sink.sink.play();
+				}
+			});
+		}
+	}
+
+	pub fn executeAudioQueue(&self, stream: &mut Option<deviceStream>)
+	{
+		if let Some(stream) = stream
+		{
+			stream.pullElapsed();
+			self.syncBuses(stream);
+
+			self.musicCheckEnded(stream);
+
+			// paused music voices keep their track and position (paused every tick, a global resume play every voice)
+			let musicPause = *self._music_pause.load_full();
+			let musicResume = !musicPause && *self._music_pause_state.load_full();
+			if (musicPause || (musicResume && !stream._isPaused))
 			{
-				let musiclist = self._musicList.read();
-				if (musiclist.len() != 0)
+				if let Some(sinks) = stream._channel.get_mut(AUDIO_BUS_MUSIC)
 				{
-					if stream
-						._channel
-						.get(&audio_channel::MUSIC)
-						.unwrap()
-						.iter()
-						.find(|x| !x.sink.empty())
-						.is_none()
-					{
-						let mut nextid = *self._music_nextplayid.load_full();
-						if (nextid > musiclist.len())
+					sinks.iter_mut().filter(|sink| !sink.sink.empty()).for_each(|sink| {
+						if (musicPause)
 						{
-							nextid = 0;
+							sink.sink.pause();
 						}
-
-						if let Some(keymusic) = musiclist.get(nextid)
+						else
 						{
-							if let Some(storage) = self._loadedSound.get(keymusic)
-							{
-								//let file = BufReader::new(File::open(storage.path.clone()).unwrap());
-								if let Some(file) =
-									assetManager::singleton().readFile(storage.path.clone())
-								{
-									if let Ok(source) = Decoder::new(file)
-									{
-										source.total_duration();
-										self.sinkplay(
-											stream,
-											&audio_queue {
-												name: keymusic.clone(),
-												channel: audio_channel::MUSIC,
-												volume: 1.0,
-											},
-											source,
-											**self._audiolevel_music.load(),
-											storage.level,
-										);
-									}
-								}
-							}
+							sink.sink.play();
 						}
+					});
+				}
+			}
+			if (musicPause != *self._music_pause_state.load_full())
+			{
+				self._music_pause_state.swap(Arc::new(musicPause));
+			}
 
-						self._music_nextplayid.swap(Arc::new(nextid + 1));
-					}
+			if (!*self._music_pause_state.load_full())
+			{
+				self.musicUpdate(stream);
+			}
+
+			if (*self._effect_clear.load_full())
+			{
+				self._effect_clear.swap(Arc::new(false));
+				stream
+					._channel
+					.iter()
+					.filter(|(bus, _)| bus.as_str() != AUDIO_BUS_MUSIC)
+					.for_each(|(_, sinks)| {
+						sinks.iter().for_each(|x| {
+							x.sink.clear();
+						});
+					});
+				stream._spatial.iter_mut().for_each(|x| {
+					x.sink.clear();
+					x.emitter = None;
+				});
+			}
 
-					if (**self._music_update.load())
+			let toexecute: Vec<audio_queue> = self._queueEffect.lock().drain(0..).collect();
+			for data in toexecute
+			{
+				if let Some((source, level)) = self.loadSource(&data.name, false)
+				{
+					if (data.emitter.is_some())
+					{
+						self.spatialplay(stream, &data, source, level);
+					}
+					else
 					{
-						stream
-							._channel
-							.get(&audio_channel::MUSIC)
-							.unwrap()
-							.iter()
-							.for_each(|x| {
-								x.sink.set_speed(**self._music_speed.load());
-								x.sink.set_volume(
-									**self._audiolevel_global.load()
-										* **self._audiolevel_music.load()
-										* x.content_level,
-								);
-							});
+						let _ = self.sinkplay(stream, &data, source, level);
 					}
 				}
 			}
 
-			// cannot work because symphonia in rodio is borked, keep it for later
-			/*if(self._music_lastplay.load().elapsed() > self._music_nextStart.load_full().checked_sub(Duration::from_millis(500)).unwrap_or(Duration::from_secs(0)))
+			let levels = self.busLevels(stream);
+			self.spatialUpdate(stream, &levels);
+		}
+	}
+
+	///////////// PRIVATE
+
+	fn musicSendEvent(&self, event: audio_music_event)
+	{
+		match &event
+		{
+			audio_music_event::STARTED(name) =>
+			{
+				self._music_current.swap(Arc::new(Some(name.clone())));
+			}
+			audio_music_event::ENDED(name) =>
 			{
-				let mut lastid = *self._music_lastplayid.load_full() + 1;
-				let musiclist = self._musicList.read();
-				if(lastid > musiclist.len())
+				if ((**self._music_current.load()).as_ref() == Some(name))
 				{
-					lastid=0;
+					self._music_current.swap(Arc::new(None));
 				}
+			}
+		}
+
+		let listeners = self._music_listeners.read().clone();
+		for listener in listeners
+		{
+			listener(&event);
+		}
+	}
 
-				if let Some(keymusic) = musiclist.get(lastid)
+	/// send ENDED event for every music voice that finished
+	fn musicCheckEnded(&self, stream: &mut deviceStream)
+	{
+		let mut ended = vec![];
+		if let Some(sinks) = stream._channel.get_mut(AUDIO_BUS_MUSIC)
+		{
+			sinks.iter_mut().for_each(|sink| {
+				if (sink.name.is_some() && sink.sink.empty())
 				{
-					println!("plausing music : {}",keymusic);
-					if let Some(path) = self._loadedSound.get(keymusic)
-					{
-						let file = BufReader::new(File::open(path.value()).unwrap());
-						if let Ok(source) = Decoder::new(file)
-						{
-							println!("ddd {:?}",source.total_duration());
-							//durationofmusic = source.total_duration().unwrap();
-							/*self.sinkplay(stream, &audio_queue{
-								name: keymusic.clone(),
-								channel: audio_channel::MUSIC,
-								local_level: 1.0,
-							}, source);*/
-						}
-					}
+					ended.extend(sink.name.take());
+				}
+			});
+		}
 
+		for name in ended
+		{
+			self.musicSendEvent(audio_music_event::ENDED(name));
+		}
+	}
 
-					self._music_lastplay.swap(Arc::new(Instant::now()));
-					self._music_nextStart.swap(Arc::new(durationofmusic));
-					self._music_lastplayid.swap(Arc::new(lastid));
-				}
-			}*/
+	/// regenerate the playing order if the playlist changed (or if forced), keep the position on the current track
+	fn musicOrderCheck(&self, musiclist: &Vec<String>, force: bool)
+	{
+		let mut order = self._music_order.write();
+		if (!force && order.len() == musiclist.len())
+		{
+			return;
+		}
 
-			if (*self._effect_clear.load_full())
+		let current = (**self._music_position.load()).and_then(|position| order.get(position).copied());
+		*order = (0..musiclist.len()).collect();
+		if (**self._music_shuffle.load())
+		{
+			// Fisher-Yates
+			for i in (1..order.len()).rev()
 			{
-				self._effect_clear.swap(Arc::new(false));
-				stream
-					._channel
-					.get(&audio_channel::EFFECT)
-					.unwrap()
-					.iter()
-					.for_each(|x| {
-						x.sink.clear();
-					});
+				let j = (uuid::Uuid::new_v4().as_u128() % (i as u128 + 1)) as usize;
+				order.swap(i, j);
 			}
+		}
+		self._music_position.swap(Arc::new(current.and_then(|id| order.iter().position(|x| *x == id))));
+	}
 
-			let toexecute: Vec<audio_queue> = self._queueEffect.lock().drain(0..).collect();
-			for data in toexecute
+	/// move the position in the playlist and return the track to play
+	/// auto : the track ended by itself, follow repeat ONE
+	fn musicAdvance(&self, musiclist: &Vec<String>, step: isize, auto: bool) -> Option<String>
+	{
+		let order = self._music_order.read();
+		if (order.len() == 0)
+		{
+			return None;
+		}
+
+		let len = order.len() as isize;
+		let repeat = **self._music_repeat.load();
+		let position = match **self._music_position.load()
+		{
+			None => 0,
+			Some(position) if (auto && repeat == audio_music_repeat::ONE) => position as isize,
+			Some(position) => position as isize + step,
+		};
+
+		let position = if (position < 0 || position >= len)
+		{
+			if (repeat == audio_music_repeat::NONE)
+			{
+				return None;
+			}
+			position.rem_euclid(len)
+		}
+		else
+		{
+			position
+		};
+
+		self._music_position.swap(Arc::new(Some(position as usize)));
+		return order.get(position as usize).and_then(|id| musiclist.get(*id)).cloned();
+	}
+
+	fn musicUpdate(&self, stream: &mut deviceStream)
+	{
+		let musiclist = self._musicList.read().clone();
+		self.musicOrderCheck(&musiclist, false);
+
+		let crossfade = **self._music_crossfade.load();
+		let (haveCurrent, nearEnd) = stream
+			._channel
+			.get(AUDIO_BUS_MUSIC)
+			.and_then(|sinks| sinks.iter().find(|x| x.name.is_some() && !x.sink.empty() && x.fadeStep >= 0.0))
+			.map(|x| (true, !crossfade.is_zero() && x.remaining().map(|remaining| remaining <= crossfade).unwrap_or(false)))
+			.unwrap_or((false, false));
+
+		let request = self._music_request.lock().take();
+		let next = match request
+		{
+			Some(audio_music_request::PLAY(name)) =>
 			{
-				if let Some(storage) = self._loadedSound.get(&data.name)
+				if let Some(id) = musiclist.iter().position(|x| *x == name)
 				{
-					//let file = BufReader::new(File::open(storage.path.clone()).unwrap());
-					if let Some(file) = assetManager::singleton().readFile(storage.path.clone())
+					if let Some(position) = self._music_order.read().iter().position(|x| *x == id)
 					{
-						if let Ok(source) = Decoder::new(file)
-						{
-							self.sinkplay(
-								stream,
-								&data,
-								source,
-								**self._audiolevel_effect.load(),
-								storage.level,
-							);
-						}
+						self._music_position.swap(Arc::new(Some(position)));
+					}
+				}
+				Some(name)
+			}
+			Some(audio_music_request::NEXT) => self.musicAdvance(&musiclist, 1, false),
+			Some(audio_music_request::PREV) => self.musicAdvance(&musiclist, -1, false),
+			None if (!haveCurrent || nearEnd) => self.musicAdvance(&musiclist, 1, true),
+			None => None,
+		};
+
+		if let Some(next) = next
+		{
+			// keep the current track playing if the next one cannot be loaded
+			if let Some((source, level)) = self.loadSource(&next, true)
+			{
+				if let Some(sinks) = stream._channel.get_mut(AUDIO_BUS_MUSIC)
+				{
+					sinks.iter_mut().filter(|x| !x.sink.empty()).for_each(|x| {
+						x.fadeOut(crossfade);
+					});
+				}
+
+				let duration = source.total_duration();
+				let speed = **self._music_speed.load();
+				let data = audio_queue {
+					name: next.clone(),
+					bus: AUDIO_BUS_MUSIC.to_string(),
+					volume: 1.0,
+					emitter: None,
+				};
+				if let Some(sink) = self.sinkplay(stream, &data, source, level)
+				{
+					// voice stolen from a track still playing
+					if let Some(previous) = sink.name.replace(next.clone())
+					{
+						self.musicSendEvent(audio_music_event::ENDED(previous));
 					}
+					sink.duration = duration;
+					sink.fadeIn(crossfade);
+					sink.sink.set_speed(speed);
+					self.musicSendEvent(audio_music_event::STARTED(next));
 				}
 			}
 		}
+
+		if (**self._music_update.load())
+		{
+			if let Some(sinks) = stream._channel.get(AUDIO_BUS_MUSIC)
+			{
+				sinks.iter().for_each(|x| {
+					x.sink.set_speed(**self._music_speed.load());
+				});
+			}
+		}
 	}
 
-	fn sinkplay(
-		&self,
-		stream: &mut deviceStream,
-		data: &audio_queue,
-		source: Decoder<Cursor<Vec<u8>>>,
-		glevel: f32,
-		storage_level: f32,
-	)
+	fn effect_queue(&self, name: String, bus: String, volume: f32, emitter: Option<audio_emitter>)
+	{
+		#[cfg(target_os = "android")]
+		{
+			// on android, cancel playing of effect too
+			if (*self._music_pause.load_full())
+			{
+				return;
+			}
+		}
+
+		let _ = namedThread!(move || {
+			Self::singleton()._queueEffect.lock().push(audio_queue {
+				name: name,
+				bus: bus,
+				volume: volume.clamp(0.0, 2.0),
+				emitter: emitter,
+			});
+		});
+	}
+
+	fn buildStream(stream: Option<OutputStream>, handle: Option<OutputStreamHandle>, offline: Option<audio_offline>) -> deviceStream
+	{
+		let mut tmp = deviceStream {
+			stream,
+			handle,
+			_history: offline.as_ref().map(|_| vec![]),
+			_offline: offline,
+			_channel: Default::default(),
+			_spatial: vec![],
+			_ducking: Default::default(),
+			_lastUpdate: Instant::now(),
+			_isPaused: false,
+		};
+
+		Self::singleton().syncBuses(&mut tmp);
+
+		for _ in 0..SPATIAL_VOICES
+		{
+			tmp.addSpatial();
+		}
+
+		return tmp;
+	}
+
+	/// create voices of new buses, remove voices of deleted buses
+	fn syncBuses(&self, stream: &mut deviceStream)
+	{
+		self._buses.iter().for_each(|bus| {
+			stream.syncChannel(bus.key(), bus.maxVoices);
+		});
+		stream._channel.retain(|name, _| self._buses.contains_key(name));
+	}
+
+	/// return the source of a loaded sound and its local level
+	/// streaming : decode the file while playing it (music), else the sound is fully decoded and cached (effects)
+	fn loadSource(&self, name: &String, streaming: bool) -> Option<(Box<dyn Source<Item = i16> + Send>, f32)>
+	{
+		let (path, level) = self._loadedSound.get(name).map(|x| (x.path.clone(), x.level))?;
+
+		if (streaming)
+		{
+			let file = assetManager::singleton().readFileStream(path)?;
+			let source = Decoder::new(file).ok()?;
+			return Some((Box::new(source), level));
+		}
+
+		if let Some(decoded) = self._cache.lock().get(name)
+		{
+			return Some((Box::new(decoded.source()), level));
+		}
+
+		TimeStatsStorage::forceNow("A_decode");
+		let file = assetManager::singleton().readFile(path)?;
+		let decoded = audio_decoded::decode(Decoder::new(file).ok()?);
+		TimeStatsStorage::update("A_decode");
+
+		if (!self._cache.lock().insert(name.clone(), decoded.clone()))
+		{
+			HTrace!("manager audio : {} is too big to be cached", name);
+		}
+		return Some((Box::new(decoded.source()), level));
+	}
+
+	/// apply bus effects on a source
+	fn applyEffects(&self, bus: &String, source: Box<dyn Source<Item = i16> + Send>) -> Box<dyn Source<Item = f32> + Send>
 	{
-		let mut last = *stream._channelRun.get(&data.channel).unwrap_or(&0) as usize;
+		let mut returning: Box<dyn Source<Item = f32> + Send> = Box::new(source.convert_samples::<f32>());
 
-		let Some(test) = stream._channel.get_mut(&data.channel)
+		let Some(bus) = self._buses.get(bus)
 		else
 		{
-			return;
+			return returning;
 		};
-		let max = test.len();
-		if let Some(sink) = test.get_mut(last)
+
+		for effect in bus.effects.iter()
 		{
-			if (!sink.sink.empty())
+			returning = match effect
+			{
+				audio_bus_effect::LOWPASS(freq) => Box::new(returning.low_pass(*freq)),
+				audio_bus_effect::REVERB(delay, amplitude) => Box::new(returning.buffered().reverb(*delay, *amplitude)),
+			};
+		}
+
+		return returning;
+	}
+
+	/// update ducking, apply volume on all voices, and return the final level of each bus
+	fn busLevels(&self, stream: &mut deviceStream) -> HashMap<String, f32>
+	{
+		let elapsed = stream._lastUpdate.elapsed().as_secs_f32();
+		stream._lastUpdate = Instant::now();
+		let global = **self._audiolevel_global.load();
+
+		let mut active: HashSet<String> = Default::default();
+		stream._channel.iter().for_each(|(bus, sinks)| {
+			if (sinks.iter().any(|x| !x.sink.empty()))
 			{
-				sink.sink.stop();
+				active.insert(bus.clone());
 			}
-			sink.content_level = storage_level * data.volume;
-			sink.sink.set_volume(
-				*self._audiolevel_global.load_full() * glevel * storage_level * data.volume,
-			);
-			sink.sink.append(source);
-			if (sink.sink.is_paused())
+		});
+		stream._spatial.iter().for_each(|x| {
+			if (!x.sink.empty())
 			{
-				sink.sink.play();
+				active.insert(x.bus.clone());
+			}
+		});
+
+		let mut levels: HashMap<String, f32> = Default::default();
+		self._buses.iter().for_each(|bus| {
+			let target = bus
+				.duckedBy
+				.iter()
+				.filter(|(other, _)| active.contains(other))
+				.map(|(_, level)| level.clamp(0.0, 1.0))
+				.fold(1.0, f32::min);
+
+			let step = elapsed * DUCKING_SPEED;
+			let ducking = stream._ducking.entry(bus.key().clone()).or_insert(1.0);
+			if (target < *ducking)
+			{
+				*ducking = (*ducking - step).max(target);
+			}
+			else
+			{
+				*ducking = (*ducking + step).min(target);
+			}
+
+			let level = global * bus.level * *ducking;
+			if let Some(sinks) = stream._channel.get_mut(bus.key())
+			{
+				sinks.iter_mut().for_each(|x| {
+					x.fadeUpdate(elapsed);
+					x.sink.set_volume(level * x.content_level * x.fade);
+				});
+			}
+			levels.insert(bus.key().clone(), level);
+		});
+
+		return levels;
+	}
+
+	/// update listener, emitters positions and volumes of all spatial sinks
+	fn spatialUpdate(&self, stream: &mut deviceStream, levels: &HashMap<String, f32>)
+	{
+		if (**self._listener_followCamera.load())
+		{
+			if let Some(listener) = audio_listener::fromCamera()
+			{
+				self._listener.swap(Arc::new(listener));
+			}
+		}
+
+		let listener = **self._listener.load();
+		let refDistance = **self._spatial_refDistance.load();
+		let maxDistance = **self._spatial_maxDistance.load();
+
+		stream._spatial.iter_mut().for_each(|x| {
+			let Some(emitter) = &x.emitter
+			else
+			{
+				return;
+			};
+
+			if (x.sink.empty() || !emitter.update())
+			{
+				x.sink.clear();
+				x.emitter = None;
+				return;
+			}
+
+			let position = emitter.position_get();
+			let (emitterPos, left, right) = listener.computeEars(position, refDistance);
+			x.setPositions(emitterPos, left, right);
+			if (listener.distance(position) > maxDistance)
+			{
+				x.sink.set_volume(0.0);
+			}
+			else
+			{
+				x.sink.set_volume(levels.get(&x.bus).copied().unwrap_or(0.0) * x.content_level);
 			}
+		});
+	}
+
+	fn spatialplay(&self, stream: &mut deviceStream, data: &audio_queue, source: Box<dyn Source<Item = i16> + Send>, storage_level: f32)
+	{
+		let Some((maxVoices, stealing)) = self._buses.get(&data.bus).map(|x| (x.maxVoices, x.stealing))
+		else
+		{
+			return;
+		};
+
+		let Some(sink) = audio_spatial_sink::selectVoice(&mut stream._spatial, &data.bus, maxVoices, stealing)
+		else
+		{
+			return;
+		};
+
+		if (!sink.sink.empty())
+		{
+			sink.sink.stop();
+		}
+		sink.content_level = storage_level * data.volume;
+		sink.startedAt = Instant::now();
+		sink.bus = data.bus.clone();
+		sink.emitter = data.emitter.clone();
+		sink.append(self.applyEffects(&data.bus, source));
+		if (sink.sink.is_paused())
+		{
+			sink.sink.play();
+		}
+
+		let played = audio_played {
+			name: data.name.clone(),
+			bus: data.bus.clone(),
+			level: self.busLevel_get(&data.bus) * stream._ducking.get(&data.bus).copied().unwrap_or(1.0) * **self._audiolevel_global.load() * sink.content_level,
+			position: data.emitter.as_ref().map(|x| x.position_get()),
+		};
+		stream.historyPush(played);
+	}
+
+	fn sinkplay<'a>(&self, stream: &'a mut deviceStream, data: &audio_queue, source: Box<dyn Source<Item = i16> + Send>, storage_level: f32) -> Option<&'a mut audio_channel_sink>
+	{
+		let stealing = self._buses.get(&data.bus).map(|x| x.stealing)?;
+		let level = self._buses.get(&data.bus).map(|x| x.level).unwrap_or(0.0)
+			* stream._ducking.get(&data.bus).copied().unwrap_or(1.0)
+			* **self._audiolevel_global.load();
+		let source = self.applyEffects(&data.bus, source);
+
+		let sinks = stream._channel.get_mut(&data.bus)?;
+		let sink = audio_channel_sink::selectVoice(sinks, stealing)?;
+
+		if (!sink.sink.empty())
+		{
+			sink.sink.stop();
+		}
+		sink.content_level = storage_level * data.volume;
+		sink.startedAt = Instant::now();
+		sink.duration = None;
+		sink.fade = 1.0;
+		sink.fadeStep = 0.0;
+		sink.sink.set_volume(level * sink.content_level);
+		sink.sink.append(source);
+		if (sink.sink.is_paused())
+		{
+			sink.sink.play();
 		}
 
-		last += 1;
-		if (last >= max)
+		if let Some(history) = &mut stream._history
 		{
-			last = 0;
+			history.push(audio_played {
+				name: data.name.clone(),
+				bus: data.bus.clone(),
+				level: level * sink.content_level,
+				position: None,
+			});
 		}
 
-		stream._channelRun.insert(data.channel.clone(), last as u8);
+		return Some(sink);
 	}
 }
@@ -1,4 +1,5 @@
 use crate::components::cacheInfos::cacheInfos;
+use crate::HGEMain::HGEMain;
 use crate::HGEsubpass::HGEsubpassName;
 use crate::Pipeline::EnginePipelines;
 use crate::Pipeline::ManagerPipeline::ManagerPipeline;
@@ -34,6 +35,7 @@ pub struct HGE_shader_2Dsimple_def
 	pub uvcoord: [f32; 2],
 	pub color: [f32; 4],
 	pub color_blend_type: u32, // 0 = mul, 1 = add
+	pub clip: [f32; 4],        // left, top, right, bottom in pixel, disabled if right <= left
 }
 
 impl Default for HGE_shader_2Dsimple_def
@@ -47,6 +49,7 @@ impl Default for HGE_shader_2Dsimple_def
 			uvcoord: [0.0, 0.0],
 			color: [1.0, 1.0, 1.0, 1.0],
 			color_blend_type: 0,
+			clip: [0.0; 4],
 		}
 	}
 }
@@ -74,10 +77,79 @@ impl IntoVertexted<HGE_shader_2Dsimple> for HGE_shader_2Dsimple_def
 			uvcoord: self.uvcoord,
 			color: self.color,
 			color_blend_type: self.color_blend_type,
+			clip: self.clip,
 		});
 	}
 }
 
+/// alpha, scale and offset applied to every vertex of a group of contents (page transitions)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HGE_shader_2Dsimple_transform
+{
+	/// multiply the alpha of the color
+	pub alpha: f32,
+	/// scale around the center of the window
+	pub scale: f32,
+	/// move in window pixel, after the scale
+	pub offset: [f32; 2],
+}
+
+impl Default for HGE_shader_2Dsimple_transform
+{
+	fn default() -> Self
+	{
+		Self {
+			alpha: 1.0,
+			scale: 1.0,
+			offset: [0.0, 0.0],
+		}
+	}
+}
+
+impl HGE_shader_2Dsimple_transform
+{
+	/// apply on a vertex, "window" is the window size in pixel
+	pub fn apply(&self, vertex: &mut HGE_shader_2Dsimple, window: [f32; 2])
+	{
+		vertex.color[3] *= self.alpha;
+		self.applyPosition(&mut vertex.position, vertex.ispixel == 1, window);
+		self.applyClip(&mut vertex.clip, window);
+	}
+
+	pub(crate) fn applyPosition(&self, position: &mut [f32; 3], ispixel: bool, window: [f32; 2])
+	{
+		if (ispixel)
+		{
+			for axis in 0..2
+			{
+				let center = window[axis] / 2.0;
+				position[axis] = center + (position[axis] - center) * self.scale + self.offset[axis];
+			}
+		}
+		else
+		{
+			// vertex coordinates go from -1.0 to 1.0, centered on the window
+			for axis in 0..2
+			{
+				position[axis] = position[axis] * self.scale + self.offset[axis] * 2.0 / window[axis].max(1.0);
+			}
+		}
+	}
+
+	pub(crate) fn applyClip(&self, clip: &mut [f32; 4], window: [f32; 2])
+	{
+		// clip is in pixel, disabled if right <= left
+		if (clip[2] > clip[0])
+		{
+			for i in 0..4
+			{
+				let center = window[i % 2] / 2.0;
+				clip[i] = center + (clip[i] - center) * self.scale + self.offset[i % 2];
+			}
+		}
+	}
+}
+
 // struct internal, a changer en HGE_shader_2Dsimple_raw, remove pub ?
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Vertex, BufferContents)]
@@ -95,6 +167,8 @@ pub struct HGE_shader_2Dsimple
 	pub color: [f32; 4],
 	#[format(R32_UINT)]
 	pub color_blend_type: u32, // 0 = mul, 1 = add
+	#[format(R32G32B32A32_SFLOAT)]
+	pub clip: [f32; 4],
 }
 
 impl Default for HGE_shader_2Dsimple
@@ -108,6 +182,7 @@ impl Default for HGE_shader_2Dsimple
 			uvcoord: [0.0, 0.0],
 			color: [1.0, 1.0, 1.0, 1.0],
 			color_blend_type: 0,
+			clip: [0.0; 4],
 		}
 	}
 }
@@ -173,6 +248,16 @@ impl HGE_shader_2Dline_holder
 	{
 		self._content.remove(uuid);
 	}
+
+	pub fn transform_set(&self, uuids: &[Uuid], transform: HGE_shader_2Dsimple_transform)
+	{
+		self._content.transform_set(uuids, transform);
+	}
+
+	pub fn transform_remove(&self, uuids: &[Uuid])
+	{
+		self._content.transform_remove(uuids);
+	}
 }
 
 impl ShaderStructHolder for HGE_shader_2Dline_holder
@@ -213,6 +298,7 @@ impl ShaderStructHolder for HGE_shader_2Dline_holder
 pub struct HGE_shader_2Dsimple_holder
 {
 	_datas: DashMap<Uuid, ShaderDrawerImplStruct<Box<dyn IntoVertexted<HGE_shader_2Dsimple> + Send + Sync>>>,
+	_transforms: DashMap<Uuid, HGE_shader_2Dsimple_transform>,
 	_haveUpdate: AtomicBool,
 	_cacheDraw: ArcSwapOption<ShaderStructCache<HGE_shader_2Dsimple>>,
 }
@@ -231,11 +317,36 @@ impl HGE_shader_2Dsimple_holder
 		self._haveUpdate.store(true, Ordering::Release);
 	}
 
+	/// transform the vertices of these contents (submitted or not yet), until transform_remove
+	pub fn transform_set(&self, uuids: &[Uuid], transform: HGE_shader_2Dsimple_transform)
+	{
+		for uuid in uuids
+		{
+			self._transforms.insert(*uuid, transform);
+		}
+		self._haveUpdate.store(true, Ordering::Release);
+	}
+
+	pub fn transform_remove(&self, uuids: &[Uuid])
+	{
+		for uuid in uuids
+		{
+			self._transforms.remove(uuid);
+		}
+		self._haveUpdate.store(true, Ordering::Release);
+	}
+
 	fn compileData(&self) -> (Vec<HGE_shader_2Dsimple>, Vec<u32>, bool)
 	{
 		let mut vertex = Vec::new();
 		let mut indices = Vec::new();
 		let mut atleastone = false;
+		let mut window = [0.0, 0.0];
+		if (!self._transforms.is_empty())
+		{
+			let infos = HGEMain::singleton().getWindowInfos();
+			window = [infos.widthF, infos.heightF];
+		}
 
 		for content in self._datas.iter()
 		// (_,one)
@@ -243,14 +354,19 @@ impl HGE_shader_2Dsimple_holder
 			let mut stop = false;
 			let mut tmpvertex = Vec::new();
 			let oldindices = vertex.len() as u32;
+			let transform = self._transforms.get(content.key()).map(|x| *x);
 			for x in &content.vertex
 			{
-				let Some(unwraped) = x.IntoVertexted(false)
+				let Some(mut unwraped) = x.IntoVertexted(false)
 				else
 				{
 					stop = true;
 					break;
 				};
+				if let Some(transform) = &transform
+				{
+					transform.apply(&mut unwraped, window);
+				}
 				tmpvertex.push(unwraped);
 			}
 
@@ -275,6 +391,7 @@ impl ShaderStructHolder for HGE_shader_2Dsimple_holder
 	{
 		Self {
 			_datas: DashMap::new(),
+			_transforms: DashMap::new(),
 			_haveUpdate: AtomicBool::new(false),
 			_cacheDraw: Default::default(),
 		}
@@ -293,6 +410,7 @@ impl ShaderStructHolder for HGE_shader_2Dsimple_holder
 	fn reset(&self)
 	{
 		self._datas.clear();
+		self._transforms.clear();
 		self._haveUpdate.store(false, Ordering::Release);
 		self._cacheDraw.store(None);
 	}
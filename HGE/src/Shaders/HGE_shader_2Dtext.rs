@@ -0,0 +1,321 @@
+use crate::components::cacheInfos::cacheInfos;
+use crate::HGEMain::HGEMain;
+use crate::HGEsubpass::HGEsubpassName;
+use crate::Pipeline::EnginePipelines;
+use crate::Pipeline::ManagerPipeline::ManagerPipeline;
+use crate::Shaders::intoVertexed::IntoVertexted;
+use crate::Shaders::names;
+use crate::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dsimple_def, HGE_shader_2Dsimple_transform};
+use crate::Shaders::Manager::ManagerShaders;
+use crate::Shaders::ShaderDrawerImpl::ShaderDrawerImplStruct;
+use crate::Shaders::ShaderStruct::{ShaderStruct, ShaderStructHolder, ShaderStructHolder_utils};
+use crate::Shaders::ShaderStructCache::ShaderStructCache;
+use crate::Textures::Manager::ManagerTexture;
+use anyhow::anyhow;
+use arc_swap::ArcSwapOption;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+use vulkano::buffer::BufferContents;
+use vulkano::command_buffer::{AutoCommandBufferBuilder, SecondaryAutoCommandBuffer};
+use vulkano::pipeline::graphics::input_assembly::PrimitiveTopology;
+use vulkano::pipeline::graphics::vertex_input::Vertex;
+use vulkano::pipeline::PipelineBindPoint;
+use Htrace::HTraceError;
+
+/// texture of the signed distance field glyphs, see ManagerFont::setRenderMode
+pub const HGE_SHADER_2DTEXT_TEXTURE: &str = "font_sdf";
+/// descriptor set holding HGE_SHADER_2DTEXT_TEXTURE, bound as set 0 of the text2D pipeline
+pub const HGE_SHADER_2DTEXT_SET: &str = "HGE_text_set0";
+
+/// vertex of a signed distance field glyph, the field is read in the alpha of the texture (0.5 on the edge)
+#[derive(Clone, Debug)]
+pub struct HGE_shader_2Dtext_def
+{
+	pub position: [f32; 3],
+	pub ispixel: u32,
+	pub uvcoord: [f32; 2],
+	pub color: [f32; 4],
+	pub clip: [f32; 4], // left, top, right, bottom in pixel, disabled if right <= left
+	pub outline_color: [f32; 4],
+	pub outline_width: f32, // in distance unit (0.5 = whole spread), 0.0 to disable
+	pub shadow_color: [f32; 4],
+	pub shadow_offset: [f32; 2], // in pixel of the sdf texture, scaled with the text
+}
+
+impl Default for HGE_shader_2Dtext_def
+{
+	fn default() -> Self
+	{
+		Self {
+			position: [0.0, 0.0, 0.0],
+			ispixel: 0,
+			uvcoord: [0.0, 0.0],
+			color: [1.0, 1.0, 1.0, 1.0],
+			clip: [0.0; 4],
+			outline_color: [0.0; 4],
+			outline_width: 0.0,
+			shadow_color: [0.0; 4],
+			shadow_offset: [0.0; 2],
+		}
+	}
+}
+
+impl From<&HGE_shader_2Dsimple_def> for HGE_shader_2Dtext_def
+{
+	/// same glyph without outline and shadow
+	fn from(value: &HGE_shader_2Dsimple_def) -> Self
+	{
+		return Self {
+			position: value.position,
+			ispixel: value.ispixel,
+			uvcoord: value.uvcoord,
+			color: value.color,
+			clip: value.clip,
+			..Self::default()
+		};
+	}
+}
+
+impl IntoVertexted<HGE_shader_2Dtext> for HGE_shader_2Dtext_def
+{
+	fn IntoVertexted(&self, _: bool) -> Option<HGE_shader_2Dtext>
+	{
+		ManagerTexture::singleton().descriptorSet_getIdTexture([HGE_SHADER_2DTEXT_SET], HGE_SHADER_2DTEXT_TEXTURE)?;
+
+		return Some(HGE_shader_2Dtext {
+			position: self.position,
+			ispixel: self.ispixel,
+			uvcoord: self.uvcoord,
+			color: self.color,
+			clip: self.clip,
+			outline_color: self.outline_color,
+			outline_width: self.outline_width,
+			shadow_color: self.shadow_color,
+			shadow_offset: self.shadow_offset,
+		});
+	}
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Vertex, BufferContents)]
+pub struct HGE_shader_2Dtext
+{
+	#[format(R32G32B32_SFLOAT)]
+	pub position: [f32; 3],
+	#[format(R32_UINT)]
+	pub ispixel: u32,
+	#[format(R32G32_SFLOAT)]
+	pub uvcoord: [f32; 2],
+	#[format(R32G32B32A32_SFLOAT)]
+	pub color: [f32; 4],
+	#[format(R32G32B32A32_SFLOAT)]
+	pub clip: [f32; 4],
+	#[format(R32G32B32A32_SFLOAT)]
+	pub outline_color: [f32; 4],
+	#[format(R32_SFLOAT)]
+	pub outline_width: f32,
+	#[format(R32G32B32A32_SFLOAT)]
+	pub shadow_color: [f32; 4],
+	#[format(R32G32_SFLOAT)]
+	pub shadow_offset: [f32; 2],
+}
+
+impl HGE_shader_2Dtext
+{
+	pub fn transform(&mut self, transform: &HGE_shader_2Dsimple_transform, window: [f32; 2])
+	{
+		self.color[3] *= transform.alpha;
+		self.outline_color[3] *= transform.alpha;
+		self.shadow_color[3] *= transform.alpha;
+		transform.applyPosition(&mut self.position, self.ispixel == 1, window);
+		transform.applyClip(&mut self.clip, window);
+	}
+}
+
+impl ShaderStruct for HGE_shader_2Dtext
+{
+	/// the "HGE_2Dtext" shader is optional, without it only bitmap fonts can be used
+	fn createPipeline() -> anyhow::Result<()>
+	{
+		if ManagerShaders::singleton().get(names::text2D).is_none()
+		{
+			return Err(anyhow!("missing shader \"{}\", sdf text disabled", names::text2D));
+		}
+
+		ManagerPipeline::singleton().addFunc(
+			HGE_shader_2Dtext_holder::pipelineName(),
+			|renderpass, transparency| {
+				EnginePipelines::singleton().pipelineCreation(
+					names::text2D,
+					transparency,
+					renderpass,
+					HGEsubpassName::UI.getSubpassID(),
+					HGE_shader_2Dtext::per_vertex(),
+				)
+			},
+			PrimitiveTopology::TriangleList,
+			true,
+		);
+
+		return Ok(());
+	}
+}
+
+///////// Holder
+
+pub struct HGE_shader_2Dtext_holder
+{
+	_datas: DashMap<Uuid, ShaderDrawerImplStruct<Box<dyn IntoVertexted<HGE_shader_2Dtext> + Send + Sync>>>,
+	_transforms: DashMap<Uuid, HGE_shader_2Dsimple_transform>,
+	_haveUpdate: AtomicBool,
+	_cacheDraw: ArcSwapOption<ShaderStructCache<HGE_shader_2Dtext>>,
+}
+
+impl HGE_shader_2Dtext_holder
+{
+	pub fn insert(&self, uuid: cacheInfos, structure: ShaderDrawerImplStruct<impl IntoVertexted<HGE_shader_2Dtext> + Send + Sync + 'static>)
+	{
+		ShaderStructHolder_utils::insert(uuid.into(), structure, &self._datas);
+		self._haveUpdate.store(true, Ordering::Release);
+	}
+
+	pub fn remove(&self, uuid: cacheInfos)
+	{
+		if (self._datas.remove(&uuid.into()).is_some())
+		{
+			self._haveUpdate.store(true, Ordering::Release);
+		}
+	}
+
+	/// same as HGE_shader_2Dsimple_holder::transform_set
+	pub fn transform_set(&self, uuids: &[Uuid], transform: HGE_shader_2Dsimple_transform)
+	{
+		for uuid in uuids
+		{
+			self._transforms.insert(*uuid, transform);
+		}
+		self._haveUpdate.store(true, Ordering::Release);
+	}
+
+	pub fn transform_remove(&self, uuids: &[Uuid])
+	{
+		for uuid in uuids
+		{
+			self._transforms.remove(uuid);
+		}
+		self._haveUpdate.store(true, Ordering::Release);
+	}
+
+	fn compileData(&self) -> (Vec<HGE_shader_2Dtext>, Vec<u32>, bool)
+	{
+		let mut vertex = Vec::new();
+		let mut indices = Vec::new();
+		let mut atleastone = false;
+		let mut window = [0.0, 0.0];
+		if (!self._transforms.is_empty())
+		{
+			let infos = HGEMain::singleton().getWindowInfos();
+			window = [infos.widthF, infos.heightF];
+		}
+
+		for content in self._datas.iter()
+		{
+			let oldindices = vertex.len() as u32;
+			let transform = self._transforms.get(content.key()).map(|x| *x);
+			let Some(mut tmpvertex) = content.vertex.iter().map(|x| x.IntoVertexted(false)).collect::<Option<Vec<_>>>()
+			else
+			{
+				continue;
+			};
+			if let Some(transform) = &transform
+			{
+				tmpvertex.iter_mut().for_each(|x| x.transform(transform, window));
+			}
+
+			vertex.append(&mut tmpvertex);
+			indices.extend(content.indices.iter().map(|x| *x + oldindices));
+			atleastone = true;
+		}
+
+		return (vertex, indices, atleastone);
+	}
+}
+
+impl ShaderStructHolder for HGE_shader_2Dtext_holder
+{
+	fn init() -> Self
+	{
+		Self {
+			_datas: DashMap::new(),
+			_transforms: DashMap::new(),
+			_haveUpdate: AtomicBool::new(false),
+			_cacheDraw: Default::default(),
+		}
+	}
+
+	fn pipelineName() -> String
+	{
+		names::text2D.to_string()
+	}
+
+	fn pipelineNameResolve(&self) -> String
+	{
+		Self::pipelineName()
+	}
+
+	fn reset(&self)
+	{
+		self._datas.clear();
+		self._transforms.clear();
+		self._haveUpdate.store(false, Ordering::Release);
+		self._cacheDraw.store(None);
+	}
+
+	fn update(&self)
+	{
+		if (self._haveUpdate.compare_exchange(true, false, Ordering::Release, Ordering::Acquire).is_err())
+		{
+			return;
+		}
+
+		let (vertex, indices, atleastone) = self.compileData();
+		if (!atleastone)
+		{
+			self._cacheDraw.store(None);
+			// retry while the sdf texture is loading
+			self._haveUpdate.store(!self._datas.is_empty(), Ordering::Release);
+			return;
+		}
+
+		let mut newcache = ShaderStructCache::new();
+		newcache.update(vertex, indices);
+		self._cacheDraw.store(Some(Arc::new(newcache)));
+	}
+
+	fn draw(&self, cmdBuilder: &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>, pipelinename: String)
+	{
+		let Some(pipelineLayout) = ManagerPipeline::singleton().layoutGet(&pipelinename)
+		else
+		{
+			return;
+		};
+		if (ManagerShaders::singleton().push_constants(names::text2D, cmdBuilder, pipelineLayout.clone(), 0) == false)
+		{
+			return;
+		}
+
+		let Some(descriptorCache) = ManagerTexture::singleton().descriptorSet_getVulkanCache(HGE_SHADER_2DTEXT_SET)
+		else
+		{
+			return;
+		};
+		HTraceError!(cmdBuilder.bind_descriptor_sets(PipelineBindPoint::Graphics, pipelineLayout.clone(), 0, descriptorCache));
+
+		if let Some(cache) = &*self._cacheDraw.load()
+		{
+			cache.draw(cmdBuilder, pipelinename);
+		}
+	}
+}
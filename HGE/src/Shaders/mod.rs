@@ -3,6 +3,7 @@ use std::fmt::{Debug, Display, Formatter};
 pub mod Manager;
 pub mod HGE_shader_screen;
 pub mod HGE_shader_2Dsimple;
+pub mod HGE_shader_2Dtext;
 pub mod HGE_shader_3Dsimple;
 pub mod ShaderStruct;
 pub mod HGE_shader_3Dinstance;
@@ -17,6 +18,7 @@ pub enum names
 	simple3D,
 	instance3D,
 	simple2D,
+	text2D, // signed distance field text
 	screen, // simple shader of vec2 vertex
 }
 
@@ -29,6 +31,7 @@ impl names
 			names::simple3D => "HGE_3Dsimple",
 			names::instance3D => "HGE_3Dinstance",
 			names::simple2D => "HGE_2Dsimple",
+			names::text2D => "HGE_2Dtext",
 			names::screen => "HGE_screen",
 		}
 	}
@@ -4,14 +4,17 @@ use crate::Pipeline::ManagerPipeline::ManagerPipeline;
 use crate::Shaders::HGE_shader_3Dsimple::HGE_shader_3Dsimple_holder;
 use crate::Shaders::ShaderStruct::ShaderStructHolder;
 use crate::Textures::generate;
-use crate::Textures::textureLoader::{textureLoader_fromFile, textureLoader_fromRaw};
+use crate::Textures::textureLoader::{textureLoader_fromFile, textureLoader_fromKTX2, textureLoader_fromRaw};
 use crate::Textures::texturePart::texturePart;
 use crate::Textures::Order::Order;
 use crate::Textures::Orders::Order_load::Order_load;
 use crate::Textures::Orders::Order_loadPart::Order_loadPart;
+use crate::Textures::Orders::Order_partialTextureUpdate::Order_partialTextureUpdate;
+use crate::Textures::TextureAtlas::{texturePart_fromAtlas, TextureAtlas, TextureAtlas_insert};
 use crate::Textures::TextureDescriptor::TextureDescriptor;
 use crate::Textures::Textures::{Texture, TextureState, Texture_part};
 use crate::Textures::Types::TextureChannel;
+use anyhow::anyhow;
 use dashmap::DashMap;
 use parking_lot::{Mutex, RwLock};
 use rayon::iter::IntoParallelRefIterator;
@@ -23,10 +26,11 @@ use vulkano::buffer::{Buffer, BufferCreateInfo, BufferUsage};
 use vulkano::command_buffer::CopyBufferToImageInfo;
 use vulkano::descriptor_set::{DescriptorSet, WriteDescriptorSet};
 use vulkano::format::Format;
-use vulkano::image::sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerMipmapMode};
+use vulkano::image::sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerMipmapMode, LOD_CLAMP_NONE};
 use vulkano::image::view::{ImageView, ImageViewCreateInfo, ImageViewType};
 use vulkano::image::{Image, ImageAspects, ImageCreateInfo, ImageSubresourceRange, ImageType, ImageUsage};
 use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
+use image::RgbaImage;
 use HArcMut::HArcMut;
 use Htrace::HTrace;
 use Htrace::HTracer::HTracer;
@@ -41,6 +45,7 @@ pub struct ManagerTexture
 	_NbTotalLoadedTexture: RwLock<u32>,
 
 	_descriptorSets: DashMap<String, TextureDescriptor>,
+	_atlases: DashMap<String, TextureAtlas>,
 
 	// thread stuff
 	_threadLoading: Mutex<SingletonThread>,
@@ -208,6 +213,12 @@ impl ManagerTexture
 		self.texture_load(name.into(), Order_load::new(textureLoader_fromFile { path: texturePath.into() }), sampler);
 	}
 
+	/// compressed texture from a KTX2 file, see textureLoader_fromKTX2
+	pub fn addKTX2(&self, name: impl Into<String>, texturePath: impl Into<String>, sampler: Option<&str>)
+	{
+		self.texture_load(name.into(), Order_load::new(textureLoader_fromKTX2 { path: texturePath.into() }), sampler);
+	}
+
 	pub fn addRawPrioritize(&self, name: &str, textureRaw: Vec<u8>, width: u32, height: u32, sampler: Option<&str>)
 	{
 		self.texture_load(
@@ -240,7 +251,10 @@ impl ManagerTexture
 
 	pub fn texture_loadPart(&self, name: impl Into<String>, partLoader: impl texturePart + Sync + Send + 'static)
 	{
-		self.orderAdd(name.into(), vec![Box::new(Order_loadPart { from: Box::new(partLoader) })]);
+		self.orderAdd(name.into(), vec![Box::new(Order_loadPart {
+				from: Box::new(partLoader),
+				sameThread: false,
+			})]);
 	}
 
 	pub fn getPart(&self, name: impl Into<String>, part: impl Into<String>) -> Option<Texture_part>
@@ -253,6 +267,114 @@ impl ManagerTexture
 		}
 	}
 
+	/// resolve "texture" (whole texture) or "texture#part", return the texture name and the part
+	/// None if the texture or the part is not loaded yet
+	pub fn getPartFromName(&self, fullname: &str) -> Option<(String, Texture_part)>
+	{
+		let (name, partname) = match fullname.split_once('#')
+		{
+			Some((name, part)) => (name, Some(part)),
+			None => (fullname, None),
+		};
+		let texture = self.get(name)?;
+		if (texture.state == TextureState::CREATED)
+		{
+			return None;
+		}
+		if let Some(partname) = partname
+		{
+			return Some((name.to_string(), self.getPart(name, partname)?));
+		}
+		return Some((
+			name.to_string(),
+			Texture_part::new([[0.0, 0.0], [1.0, 1.0]], [texture.width?, texture.height?]),
+		));
+	}
+
+	/// create the texture "name" where images added with atlas_add are packed at runtime
+	/// using one atlas for many small images save descriptor slots and texture changes
+	pub fn atlas_create(&self, name: impl Into<String>, size: [u32; 2], sampler: Option<&str>)
+	{
+		let name = name.into();
+		let texture = generate::emptyTexture(size[0], size[1]);
+		self.texture_load(
+			name.clone(),
+			Order_load::newPrioritize(textureLoader_fromRaw {
+				raw: texture.to_vec(),
+				width: texture.width(),
+				height: texture.height(),
+				canReload: false,
+			}),
+			sampler,
+		);
+		self._atlases.insert(name, TextureAtlas::new(size));
+	}
+
+	/// pack "image" in the atlas "name" as the part "part" (replaced if existing), usable right after with getPart or "name#part"
+	/// when the atlas is full, it is repacked and grown if needed (parts move, all are updated)
+	pub fn atlas_add(&self, name: impl Into<String>, part: impl Into<String>, image: RgbaImage) -> anyhow::Result<Texture_part>
+	{
+		let name = name.into();
+		let part = part.into();
+		let (update, parts) = {
+			let Some(mut atlas) = self._atlases.get_mut(&name)
+			else
+			{
+				return Err(anyhow!("\"{}\" is not an atlas", name));
+			};
+			let update = match atlas.insert(part.clone(), image.clone())?
+			{
+				TextureAtlas_insert::PLACED(offset) => Order_partialTextureUpdate {
+					raw: image,
+					offset,
+					sameThread: true,
+				},
+				TextureAtlas_insert::REBUILT =>
+				{
+					HTrace!("ManagerTexture: atlas {} repacked in {:?}", name, atlas.size());
+					Order_partialTextureUpdate {
+						raw: atlas.compose(),
+						offset: [0, 0],
+						sameThread: true,
+					}
+				}
+			};
+			(update, atlas.parts())
+		};
+
+		let found = parts.get(&part).copied().ok_or(anyhow!("part \"{}\" not placed in atlas \"{}\"", part, name))?;
+		self.texture_update(
+			name,
+			vec![
+				Box::new(update),
+				Box::new(Order_loadPart {
+					from: Box::new(texturePart_fromAtlas { parts }),
+					sameThread: true,
+				}),
+			],
+		);
+		return Ok(found);
+	}
+
+	/// remove a part of the atlas "name", its space is reused when the atlas is repacked
+	pub fn atlas_remove(&self, name: impl Into<String>, part: &str) -> bool
+	{
+		let name = name.into();
+		let parts = match self._atlases.get_mut(&name)
+		{
+			Some(mut atlas) if atlas.remove(part) => atlas.parts(),
+			_ => return false,
+		};
+		self.texture_update(
+			name,
+			vec![Box::new(Order_loadPart {
+				from: Box::new(texturePart_fromAtlas { parts }),
+				sameThread: true,
+			})],
+		);
+		return true;
+	}
+
 	pub fn launchThreads(&self)
 	{
 		if let Some(mut t) = self._threadLoading.try_lock()
@@ -320,7 +442,8 @@ impl ManagerTexture
 					mag_filter: Filter::Nearest,
 					min_filter: Filter::Nearest,
 					address_mode: [SamplerAddressMode::Repeat; 3],
-					lod: 0.0..=0.0,
+					// every level, for the textures loaded with mipmaps
+					lod: 0.0..=LOD_CLAMP_NONE,
 					anisotropy: match device.enabled_features().sampler_anisotropy
 					{
 						true => Some(16.0),
@@ -355,6 +478,7 @@ impl ManagerTexture
 			_NbTotalTexture: RwLock::new(0),
 			_NbTotalLoadedTexture: RwLock::new(0),
 			_descriptorSets: Default::default(),
+			_atlases: DashMap::new(),
 			_threadLoading: Mutex::new(orderThread),
 			_threadUpdateDescriptorSets: Mutex::new(updateThread),
 			_haveOneOrderUpdate: RwLock::new(false),
@@ -12,6 +12,7 @@ pub struct Order_load
 {
 	pub from: Box<dyn textureLoader + Send + Sync>,
 	pub filter: Vec<Box<dyn Filter + Send + Sync>>,
+	/// mip levels generated on upload, 1 (default) for none, 0 for the full chain
 	pub mipmap: u32,
 	pub format: Format,
 	pub sameThread: bool,
@@ -47,6 +48,12 @@ impl Order_load
 		return self;
 	}
 	
+	/// number of mip levels, 0 for the full chain (down to 1x1), 1 to disable mipmapping
+	pub fn mipmap_set(mut self, levels: u32) -> Self
+	{
+		self.mipmap = levels;
+		return self;
+	}
 }
 
 impl Order for Order_load
@@ -61,25 +68,36 @@ impl Order for Order_load
 			return;
 		}
 		let mut data = resultLoad.unwrap();
-		for x in self.filter.iter() {
-			x.apply(&mut data.raw, data.width, data.height);
-		}
-		
-		let loadedInBuffer = RgbaImage::from_raw(data.width, data.height, data.raw);
-		if (loadedInBuffer.is_none())
+		if let Err(err) = data.prepare(&self.filter)
 		{
-			HTrace!("texture is blocked for : unable to load from_raw");
+			HTrace!("texture is blocked for : {}", err);
 			texture.state = TextureState::BLOCKED;
 			return;
 		}
-		let loadedInBuffer = loadedInBuffer.unwrap();
 		
-		texture.width = Some(loadedInBuffer.width());
-		texture.height = Some(loadedInBuffer.height());
-		texture.content = Some(loadedInBuffer.clone());
+		texture.format = self.format;
+		if let Some(compressed) = data.compressed
+		{
+			texture.format = compressed.format;
+			texture.content = None;
+			texture.compressed = Some(compressed);
+		}
+		else
+		{
+			let loadedInBuffer = RgbaImage::from_raw(data.width, data.height, data.raw);
+			if (loadedInBuffer.is_none())
+			{
+				HTrace!("texture is blocked for : unable to load from_raw");
+				texture.state = TextureState::BLOCKED;
+				return;
+			}
+			texture.content = loadedInBuffer;
+			texture.compressed = None;
+		}
+		texture.width = Some(data.width);
+		texture.height = Some(data.height);
 		texture.state = TextureState::LOADED;
 		texture.mipmap = self.mipmap;
-		texture.format = self.format;
 		texture.reloadLoader = None;
 		texture.clearable = data.clearable;
 		if (self.from.canReload())
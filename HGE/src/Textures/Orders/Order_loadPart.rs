@@ -1,3 +1,4 @@
+use Htrace::HTrace;
 use crate::Textures::Order::Order;
 use crate::Textures::texturePart::texturePart;
 use crate::Textures::Textures::Texture;
@@ -5,19 +6,21 @@ use crate::Textures::Textures::Texture;
 #[derive(Clone)]
 pub struct Order_loadPart
 {
-	pub from: Box<dyn texturePart + Send + Sync>
+	pub from: Box<dyn texturePart + Send + Sync>,
+	pub sameThread: bool,
 }
 
 impl Order for Order_loadPart
 {
 	fn exec(&self, texture: &mut Texture) {
-		if let Ok(result) = self.from.load(texture)
+		match self.from.load(texture)
 		{
-			texture.partUVCoord = result;
+			Ok(result) => texture.partUVCoord = result,
+			Err(err) => HTrace!("texture {} : parts not loaded : {}", texture.name, err),
 		}
 	}
 	
 	fn isSameThread(&self) -> bool {
-		false
+		self.sameThread
 	}
 }
@@ -1,5 +1,6 @@
 use Htrace::HTrace;
 use image::RgbaImage;
+use vulkano::format::Format;
 use crate::Textures::Filter::Filter;
 use crate::Textures::Order::Order;
 use crate::Textures::textureLoader::textureLoader;
@@ -41,21 +42,32 @@ impl Order for Order_reload
 			return;
 		}
 		let mut data = resultLoad.unwrap();
-		for x in self.filter.iter() {
-			x.apply(&mut data.raw, data.width, data.height);
-		}
-		
-		let loadedInBuffer = RgbaImage::from_raw(data.width,data.height,data.raw);
-		if (loadedInBuffer.is_none())
+		if let Err(err) = data.prepare(&self.filter)
 		{
-			HTrace!("texture is not reloaded because : unable to load from_raw");
+			HTrace!("texture is not reloaded because : {}", err);
 			return;
 		}
-		let loadedInBuffer = loadedInBuffer.unwrap();
 		
-		texture.width = Some(loadedInBuffer.width());
-		texture.height = Some(loadedInBuffer.height());
-		texture.content = Some(loadedInBuffer.clone());
+		if let Some(compressed) = data.compressed
+		{
+			texture.format = compressed.format;
+			texture.content = None;
+			texture.compressed = Some(compressed);
+		}
+		else
+		{
+			let loadedInBuffer = RgbaImage::from_raw(data.width,data.height,data.raw);
+			if (loadedInBuffer.is_none())
+			{
+				HTrace!("texture is not reloaded because : unable to load from_raw");
+				return;
+			}
+			texture.format = Format::R8G8B8A8_UNORM;
+			texture.content = loadedInBuffer;
+			texture.compressed = None;
+		}
+		texture.width = Some(data.width);
+		texture.height = Some(data.height);
 		texture.state = TextureState::LOADED;
 		
 	}
@@ -0,0 +1,236 @@
+use crate::Textures::texturePart::texturePart;
+use crate::Textures::Textures::{Texture, Texture_part};
+use anyhow::anyhow;
+use foldhash::{HashMap, HashMapExt};
+use image::{GenericImage, RgbaImage};
+
+/// result of TextureAtlas::insert
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextureAtlas_insert
+{
+	/// placed in free space at this offset, only this image must be written
+	PLACED([u32; 2]),
+	/// all images moved (repacked, maybe in a bigger size), the whole atlas must be written (see compose)
+	REBUILT,
+}
+
+/// runtime packer (skyline) of images in one texture, growing or repacking when full, see ManagerTexture::atlas_create
+/// images are kept to be able to repack
+pub struct TextureAtlas
+{
+	_size: [u32; 2],
+	_skyline: Vec<[u32; 3]>,
+	_images: HashMap<String, RgbaImage>,
+	_rects: HashMap<String, [u32; 4]>,
+}
+
+// space between images, avoid bleeding with linear samplers
+const TEXTUREATLAS_PADDING: u32 = 1;
+const TEXTUREATLAS_MAXSIZE: u32 = 8192;
+
+impl TextureAtlas
+{
+	pub fn new(size: [u32; 2]) -> Self
+	{
+		return TextureAtlas {
+			_size: size,
+			_skyline: vec![[0, 0, size[0]]],
+			_images: HashMap::new(),
+			_rects: HashMap::new(),
+		};
+	}
+
+	pub fn size(&self) -> [u32; 2]
+	{
+		return self._size;
+	}
+
+	/// left, top, width, height in pixel of an image
+	pub fn rect(&self, name: &str) -> Option<[u32; 4]>
+	{
+		return self._rects.get(name).copied();
+	}
+
+	/// add (or replace) an image, repack then grow (up to 8192) if there is no free space
+	pub fn insert(&mut self, name: impl Into<String>, image: RgbaImage) -> anyhow::Result<TextureAtlas_insert>
+	{
+		let name = name.into();
+		let (width, height) = image.dimensions();
+		if (width + TEXTUREATLAS_PADDING > TEXTUREATLAS_MAXSIZE || height + TEXTUREATLAS_PADDING > TEXTUREATLAS_MAXSIZE)
+		{
+			return Err(anyhow!("image \"{}\" ({}x{}) is bigger than the atlas max size", name, width, height));
+		}
+
+		// same size : written over the old one
+		if let Some(rect) = self._rects.get(&name).copied()
+		{
+			if (rect[2] == width && rect[3] == height)
+			{
+				self._images.insert(name, image);
+				return Ok(TextureAtlas_insert::PLACED([rect[0], rect[1]]));
+			}
+			self.remove(&name);
+		}
+
+		if let Some(position) = self.place(width, height)
+		{
+			self._rects.insert(name.clone(), [position[0], position[1], width, height]);
+			self._images.insert(name, image);
+			return Ok(TextureAtlas_insert::PLACED(position));
+		}
+
+		self._images.insert(name.clone(), image);
+		while (!self.repack())
+		{
+			if (self._size[0] >= TEXTUREATLAS_MAXSIZE && self._size[1] >= TEXTUREATLAS_MAXSIZE)
+			{
+				self._images.remove(&name);
+				self.repack();
+				return Err(anyhow!("atlas is full, cannot add \"{}\"", name));
+			}
+			// grow the smaller side, keeping the atlas close to a square
+			match self._size[0] <= self._size[1]
+			{
+				true => self._size[0] = (self._size[0] * 2).min(TEXTUREATLAS_MAXSIZE),
+				false => self._size[1] = (self._size[1] * 2).min(TEXTUREATLAS_MAXSIZE),
+			}
+		}
+		return Ok(TextureAtlas_insert::REBUILT);
+	}
+
+	/// remove an image, its space is only reused after a repack
+	pub fn remove(&mut self, name: &str) -> bool
+	{
+		self._rects.remove(name);
+		return self._images.remove(name).is_some();
+	}
+
+	/// place again all images (biggest first) at the current size, return false if they don't fit
+	pub fn repack(&mut self) -> bool
+	{
+		let mut names: Vec<_> = self._images.iter().map(|(name, image)| (name.clone(), image.width(), image.height())).collect();
+		names.sort_by(|a, b| b.2.cmp(&a.2).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0)));
+
+		self._skyline = vec![[0, 0, self._size[0]]];
+		self._rects.clear();
+		for (name, width, height) in names
+		{
+			let Some(position) = self.place(width, height)
+			else
+			{
+				return false;
+			};
+			self._rects.insert(name, [position[0], position[1], width, height]);
+		}
+		return true;
+	}
+
+	/// all images in one texture of the atlas size
+	pub fn compose(&self) -> RgbaImage
+	{
+		let mut returning = RgbaImage::new(self._size[0], self._size[1]);
+		for (name, rect) in self._rects.iter()
+		{
+			if let Some(image) = self._images.get(name)
+			{
+				let _ = returning.copy_from(image, rect[0], rect[1]);
+			}
+		}
+		return returning;
+	}
+
+	/// each image as a texture part
+	pub fn parts(&self) -> HashMap<String, Texture_part>
+	{
+		let width = self._size[0] as f32;
+		let height = self._size[1] as f32;
+		return self
+			._rects
+			.iter()
+			.map(|(name, rect)| {
+				(
+					name.clone(),
+					Texture_part::new(
+						[
+							[rect[0] as f32 / width, rect[1] as f32 / height],
+							[(rect[0] + rect[2]) as f32 / width, (rect[1] + rect[3]) as f32 / height],
+						],
+						[rect[2], rect[3]],
+					),
+				)
+			})
+			.collect();
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// lowest place on the skyline, the skyline is raised over it
+	fn place(&mut self, width: u32, height: u32) -> Option<[u32; 2]>
+	{
+		let width = width + TEXTUREATLAS_PADDING;
+		let height = height + TEXTUREATLAS_PADDING;
+
+		let mut best: Option<(u32, u32)> = None;
+		for segment in self._skyline.iter()
+		{
+			let x = segment[0];
+			if (x + width > self._size[0])
+			{
+				break;
+			}
+			let y = self._skyline.iter().filter(|other| other[0] < x + width && other[0] + other[2] > x).map(|other| other[1]).max().unwrap_or(0);
+			if (y + height > self._size[1])
+			{
+				continue;
+			}
+			if (best.map_or(true, |(bestX, bestY)| (y, x) < (bestY, bestX)))
+			{
+				best = Some((x, y));
+			}
+		}
+
+		let (x, y) = best?;
+		let mut skyline = Vec::new();
+		for segment in self._skyline.drain(..)
+		{
+			let end = segment[0] + segment[2];
+			// parts of the segment outside the new one are kept
+			if (segment[0] < x)
+			{
+				skyline.push([segment[0], segment[1], end.min(x) - segment[0]]);
+			}
+			if (end > x + width)
+			{
+				let start = segment[0].max(x + width);
+				skyline.push([start, segment[1], end - start]);
+			}
+		}
+		skyline.push([x, y + height, width]);
+		skyline.sort_by_key(|segment| segment[0]);
+		skyline.dedup_by(|next, previous| {
+			if (previous[1] == next[1])
+			{
+				previous[2] += next[2];
+				return true;
+			}
+			return false;
+		});
+		self._skyline = skyline;
+		return Some([x, y]);
+	}
+}
+
+// parts of an atlas, loaded when the atlas change
+#[derive(Clone)]
+pub(crate) struct texturePart_fromAtlas
+{
+	pub(crate) parts: HashMap<String, Texture_part>,
+}
+
+impl texturePart for texturePart_fromAtlas
+{
+	fn load(&self, _: &Texture) -> anyhow::Result<HashMap<String, Texture_part>>
+	{
+		return Ok(self.parts.clone());
+	}
+}
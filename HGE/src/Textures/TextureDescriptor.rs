@@ -15,14 +15,15 @@ use parking_lot::RwLock;
 use std::ops::{Range, RangeBounds};
 use std::sync::Arc;
 use vulkano::buffer::{Buffer, BufferCreateInfo, BufferUsage};
-use vulkano::command_buffer::{AutoCommandBufferBuilder, CopyBufferToImageInfo, SecondaryAutoCommandBuffer};
+use vulkano::command_buffer::{AutoCommandBufferBuilder, BlitImageInfo, BufferImageCopy, CopyBufferToImageInfo, ImageBlit, SecondaryAutoCommandBuffer};
 use vulkano::descriptor_set::{DescriptorSet, WriteDescriptorSet};
 use vulkano::format::Format;
+use vulkano::image::sampler::Filter;
 use vulkano::image::view::{ImageView, ImageViewCreateInfo, ImageViewType};
-use vulkano::image::{Image, ImageAspects, ImageCreateInfo, ImageSubresourceRange, ImageType, ImageUsage};
+use vulkano::image::{Image, ImageAspects, ImageCreateInfo, ImageSubresourceLayers, ImageSubresourceRange, ImageType, ImageUsage};
 use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
 use Htrace::HTrace;
-use Htrace::Type::Type::ERROR;
+use Htrace::Type::Type::{ERROR, WARNING};
 
 pub struct TextureDescriptor
 {
@@ -241,33 +242,75 @@ impl TextureDescriptor
 
 	fn texture_updateData(&self, texture: &Texture, id: u32)
 	{
-		let contentorigin = texture.content.clone().unwrap();
+		let process = Self::getProcess(&self._type);
+		let (width, height) = texture.getDim();
 
-		let contentadapted = match Self::getProcess(&self._type)
+		// compressed textures are uploaded as is, except in resized arrays
+		if let (TextureDescriptor_process::RAW, Some(compressed)) = (process, &texture.compressed)
+		{
+			self._contentAdapted.insert(
+				id,
+				TextureDescriptor_adaptedTexture {
+					x: width as u16,
+					y: height as u16,
+					format: compressed.format,
+					content: compressed.levels.clone(),
+					mipmap: compressed.levels.len() as u32,
+				},
+			);
+			return;
+		}
+
+		let contentorigin = match (&texture.content, &texture.compressed)
+		{
+			(Some(content), _) => content.clone(),
+			(None, Some(compressed)) => match compressed.decompress(width, height)
+			{
+				// a resized array hold every texture in one rgba image, use a RAW descriptor to keep the texture compressed
+				Ok(content) if matches!(process, TextureDescriptor_process::RESIZE(..)) =>
+				{
+					HTrace!((WARNING) "TextureDescriptor {} decompress texture {} ({:?}) to resize it", self._shaderName, texture.name, compressed.format);
+					content
+				}
+				Ok(content) => content,
+				Err(err) =>
+				{
+					HTrace!((ERROR) "TextureDescriptor {} cannot use texture {} : {}", self._shaderName, texture.name, err);
+					return;
+				}
+			},
+			(None, None) => return,
+		};
+
+		let contentadapted = match process
 		{
 			TextureDescriptor_process::RAW => TextureDescriptor_adaptedTexture {
 				x: contentorigin.width() as u16,
 				y: contentorigin.height() as u16,
-				content: contentorigin.as_raw().clone(),
+				format: Format::R8G8B8A8_UNORM,
+				content: vec![contentorigin.into_raw()],
+				mipmap: texture.mipmap,
 			},
-			TextureDescriptor_process::RESIZE(x, y) => self.texture_resize(contentorigin, *x, *y),
+			TextureDescriptor_process::RESIZE(x, y) => self.texture_resize(contentorigin, *x, *y, texture.mipmap),
 		};
 
 		self._contentAdapted.insert(id, contentadapted);
 	}
 
-	fn texture_resize(&self, origin: RgbaImage, x: u16, y: u16) -> TextureDescriptor_adaptedTexture
+	fn texture_resize(&self, origin: RgbaImage, x: u16, y: u16, mipmap: u32) -> TextureDescriptor_adaptedTexture
 	{
 		let mut returned = TextureDescriptor_adaptedTexture {
 			x: origin.width() as u16,
 			y: origin.height() as u16,
+			format: Format::R8G8B8A8_UNORM,
 			content: Vec::new(),
+			mipmap,
 		};
 
 		let tmp = DynamicImage::from(origin);
 		let tmp = tmp.resize_exact(x as u32, y as u32, FilterType::Triangle);
 
-		returned.content = tmp.as_rgba8().unwrap().as_raw().clone();
+		returned.content = vec![tmp.as_rgba8().unwrap().as_raw().clone()];
 
 		return returned;
 	}
@@ -290,7 +333,12 @@ impl TextureDescriptor
 			return Err(anyhow!("Image not adapted"));
 		};
 
-		return Self::generate_atlas_imageview(&self._type, texture.content.clone(), texture.x as u32, texture.y as u32, 1, cmdbuff);
+		let mipLevels = match texture.format
+		{
+			Format::R8G8B8A8_UNORM => generate::mipmapLevels(texture.x as u32, texture.y as u32, texture.mipmap),
+			_ => texture.content.len() as u32,
+		};
+		return Self::generate_atlas_imageview(&self._type, texture.format, texture.content.clone(), texture.x as u32, texture.y as u32, 1, mipLevels, cmdbuff);
 	}
 
 	fn update_resize(&self, x: u16, y: u16, cmdbuff: &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>) -> anyhow::Result<Arc<ImageView>>
@@ -301,11 +349,13 @@ impl TextureDescriptor
 			return Err(anyhow!("no texture link"));
 		}
 		let mut finalData: Vec<u8> = Vec::new();
-		for x in 0..nbmax
+		let mut mipLevels = 1;
+		for id in 0..nbmax
 		{
-			if let Some(content) = self._contentAdapted.get(&x)
+			if let Some(content) = self._contentAdapted.get(&id)
 			{
-				finalData.extend_from_slice(content.content.as_slice());
+				finalData.extend_from_slice(content.content[0].as_slice());
+				mipLevels = mipLevels.max(generate::mipmapLevels(x as u32, y as u32, content.mipmap));
 			}
 			else
 			{
@@ -313,18 +363,49 @@ impl TextureDescriptor
 			}
 		}
 
-		return Self::generate_atlas_imageview(&self._type, finalData, x as u32, y as u32, nbmax, cmdbuff);
+		return Self::generate_atlas_imageview(&self._type, Format::R8G8B8A8_UNORM, vec![finalData], x as u32, y as u32, nbmax, mipLevels, cmdbuff);
 	}
 
+	/// "levels" : data of the first mip levels (each one with all layers), the missing levels up to "mipLevels" are generated
 	fn generate_atlas_imageview<T: RangeBounds<u16>>(
 		ttype: &TextureDescriptor_type<T>,
-		finalData: Vec<u8>,
+		format: Format,
+		mut levels: Vec<Vec<u8>>,
 		x: u32,
 		y: u32,
 		mut nblayer: u32,
+		mipLevels: u32,
 		cmdbuff: &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>,
 	) -> anyhow::Result<Arc<ImageView>>
 	{
+		let mipLevels = match format
+		{
+			Format::R8G8B8A8_UNORM => mipLevels.max(1),
+			_ => levels.len() as u32,
+		};
+		let gpuMipmap = levels.len() < mipLevels as usize && HGEMain::singleton().getDevice().formatBlitable(format);
+		if (levels.len() < mipLevels as usize && !gpuMipmap)
+		{
+			levels = Self::mipmap_cpu(&levels[0], x, y, nblayer, mipLevels)?;
+		}
+
+		let mut regions = Vec::new();
+		let mut offset = 0;
+		for (level, data) in levels.iter().enumerate()
+		{
+			regions.push(BufferImageCopy {
+				buffer_offset: offset,
+				image_subresource: ImageSubresourceLayers {
+					aspects: ImageAspects::COLOR,
+					mip_level: level as u32,
+					array_layers: 0..nblayer,
+				},
+				image_extent: [(x >> level).max(1), (y >> level).max(1), 1],
+				..Default::default()
+			});
+			offset += data.len() as u64;
+		}
+
 		let upload_buffer = match Buffer::from_iter(
 			ManagerMemoryAllocator::singleton().get(),
 			BufferCreateInfo {
@@ -335,31 +416,68 @@ impl TextureDescriptor
 				memory_type_filter: MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
 				..Default::default()
 			},
-			finalData,
+			levels.concat(),
 		)
 		{
 			Ok(x) => x,
 			Err(err) => return Err(anyhow!("Cannot create buffer atlas : {}", err)),
 		};
 
+		let mut usage = ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED;
+		if (gpuMipmap)
+		{
+			usage |= ImageUsage::TRANSFER_SRC;
+		}
 		let atlas = Image::new(
 			ManagerMemoryAllocator::singleton().get(),
 			ImageCreateInfo {
 				image_type: ImageType::Dim2d,
-				format: Format::R8G8B8A8_UNORM,
+				format,
 				extent: [x, y, 1],
 				array_layers: nblayer,
-				usage: ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
+				mip_levels: mipLevels,
+				usage,
 				..ImageCreateInfo::default()
 			},
 			AllocationCreateInfo::default(),
 		)?;
 
-		if let Err(err) = cmdbuff.copy_buffer_to_image(CopyBufferToImageInfo::buffer_image(upload_buffer, atlas.clone()))
+		if let Err(err) = cmdbuff.copy_buffer_to_image(CopyBufferToImageInfo {
+			regions: regions.into_iter().collect(),
+			..CopyBufferToImageInfo::buffer_image(upload_buffer, atlas.clone())
+		})
 		{
 			return Err(anyhow!("Cannot create final atlas : {}", err));
 		}
 
+		// each missing level is blit from the previous one
+		for level in (levels.len() as u32)..mipLevels
+		{
+			let blit = ImageBlit {
+				src_subresource: ImageSubresourceLayers {
+					aspects: ImageAspects::COLOR,
+					mip_level: level - 1,
+					array_layers: 0..nblayer,
+				},
+				src_offsets: [[0, 0, 0], [(x >> (level - 1)).max(1), (y >> (level - 1)).max(1), 1]],
+				dst_subresource: ImageSubresourceLayers {
+					aspects: ImageAspects::COLOR,
+					mip_level: level,
+					array_layers: 0..nblayer,
+				},
+				dst_offsets: [[0, 0, 0], [(x >> level).max(1), (y >> level).max(1), 1]],
+				..Default::default()
+			};
+			if let Err(err) = cmdbuff.blit_image(BlitImageInfo {
+				regions: [blit].into(),
+				filter: Filter::Linear,
+				..BlitImageInfo::images(atlas.clone(), atlas.clone())
+			})
+			{
+				return Err(anyhow!("Cannot generate mip level {} : {}", level, err));
+			}
+		}
+
 		let viewtype = match ttype
 		{
 			TextureDescriptor_type::ONE(_) =>
@@ -374,10 +492,10 @@ impl TextureDescriptor
 			atlas,
 			ImageViewCreateInfo {
 				view_type: viewtype,
-				format: Format::R8G8B8A8_UNORM,
+				format,
 				subresource_range: ImageSubresourceRange {
 					aspects: ImageAspects::COLOR,
-					mip_levels: 0..1,
+					mip_levels: 0..mipLevels,
 					array_layers: 0..nblayer,
 				},
 				usage: ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
@@ -388,11 +506,30 @@ impl TextureDescriptor
 		return Ok(atlas);
 	}
 
+	// mip chain of each layer of an rgba level, returned by level (each one with all layers)
+	fn mipmap_cpu(data: &Vec<u8>, x: u32, y: u32, nblayer: u32, mipLevels: u32) -> anyhow::Result<Vec<Vec<u8>>>
+	{
+		let mut returning = vec![Vec::new(); mipLevels as usize];
+		for layer in data.chunks((x * y * 4) as usize).take(nblayer as usize)
+		{
+			let Some(image) = RgbaImage::from_raw(x, y, layer.to_vec())
+			else
+			{
+				return Err(anyhow!("invalid layer size for {}x{}", x, y));
+			};
+			for (level, image) in generate::mipmapChain(image, mipLevels).into_iter().enumerate()
+			{
+				returning[level].extend_from_slice(image.as_raw());
+			}
+		}
+		return Ok(returning);
+	}
+
 	fn generate_empty_descriptorCache<T: RangeBounds<u16>>(ttype: &TextureDescriptor_type<T>, shaderName: &String, shaderSetId: usize, samplerName: &String) -> Arc<DescriptorSet>
 	{
 		let mut combuilder = HGEMain::singleton().SecondaryCmdBuffer_generate();
 
-		let result = Self::generate_atlas_imageview(ttype, vec![0, 0, 0, 0], 1, 1, 1, &mut combuilder).unwrap();
+		let result = Self::generate_atlas_imageview(ttype, Format::R8G8B8A8_UNORM, vec![vec![0, 0, 0, 0]], 1, 1, 1, 1, &mut combuilder).unwrap();
 
 		let returned = DescriptorSet::new(
 			HGEMain::singleton().getDescAllocatorSet(),
@@ -1,5 +1,6 @@
 use std::ops::{Range, RangeBounds};
 use std::collections::Bound;
+use vulkano::format::Format;
 
 #[derive(Debug)]
 pub enum TextureDescriptor_type<T: RangeBounds<u16>>
@@ -74,5 +75,10 @@ pub struct TextureDescriptor_adaptedTexture
 {
 	pub(super) x: u16,
 	pub(super) y: u16,
-	pub(super) content: Vec<u8>
+	/// R8G8B8A8_UNORM, or the format of a compressed texture
+	pub(super) format: Format,
+	/// first level (the others are generated on upload), or all levels of a compressed texture
+	pub(super) content: Vec<Vec<u8>>,
+	/// mip levels wanted, 0 for the full chain
+	pub(super) mipmap: u32,
 }
@@ -1,3 +1,4 @@
+use crate::Textures::textureCompressed::Texture_compressed;
 use crate::Textures::Orders::Order_reload::Order_reload;
 use foldhash::HashMap;
 use image::RgbaImage;
@@ -6,8 +7,14 @@ use vulkano::format::Format;
 #[derive(Clone, Copy, Debug)]
 pub struct Texture_part
 {
+	/// left top, right bottom of the part in the texture
 	pub uvcoord: [[f32; 2]; 2],
+	/// size in pixel of the part as displayed (not rotated)
 	pub dim: [u32; 2],
+	/// stored turned 90° clockwise in the texture (TexturePacker)
+	pub rotated: bool,
+	/// for trimmed parts : left, top offset of the kept pixels inside the original image, original width, height
+	pub trim: Option<[u32; 4]>,
 }
 
 impl Default for Texture_part
@@ -17,10 +24,49 @@ impl Default for Texture_part
 		Texture_part {
 			uvcoord: [[0.0, 0.0], [1.0, 1.0]],
 			dim: [0, 0],
+			rotated: false,
+			trim: None,
 		}
 	}
 }
 
+impl Texture_part
+{
+	/// part neither rotated nor trimmed
+	pub fn new(uvcoord: [[f32; 2]; 2], dim: [u32; 2]) -> Self
+	{
+		return Texture_part {
+			uvcoord,
+			dim,
+			..Default::default()
+		};
+	}
+
+	/// rect of the original image covered by the kept pixels (left, top, right, bottom between 0.0 and 1.0), None if not trimmed
+	pub fn trimRect(&self) -> Option<[f32; 4]>
+	{
+		let [offsetX, offsetY, width, height] = self.trim?;
+		let [width, height] = [width.max(1) as f32, height.max(1) as f32];
+		return Some([
+			offsetX as f32 / width,
+			offsetY as f32 / height,
+			(offsetX + self.dim[0]) as f32 / width,
+			(offsetY + self.dim[1]) as f32 / height,
+		]);
+	}
+
+	/// uv of the displayed left top, right top, left bottom, right bottom corners
+	pub fn corners(&self) -> [[f32; 2]; 4]
+	{
+		let [[left, top], [right, bottom]] = self.uvcoord;
+		if (self.rotated)
+		{
+			return [[right, top], [right, bottom], [left, top], [left, bottom]];
+		}
+		return [[left, top], [right, top], [left, bottom], [right, bottom]];
+	}
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum TextureState
 {
@@ -43,9 +89,12 @@ pub struct Texture
 	pub name: String,
 
 	pub content: Option<RgbaImage>,
+	/// content kept compressed when the gpu support its format (content is then None)
+	pub compressed: Option<Texture_compressed>,
 	pub width: Option<u32>,
 	pub height: Option<u32>,
 	pub sampler: String,
+	/// mip levels generated on upload, 1 for none, 0 for the full chain (compressed textures use the levels of their file)
 	pub mipmap: u32,
 	pub format: Format,
 	pub partUVCoord: HashMap<String, Texture_part>,
@@ -61,6 +110,7 @@ impl Default for Texture
 		Texture {
 			name: "".to_string(),
 			content: None,
+			compressed: None,
 			width: None,
 			height: None,
 			sampler: "default".to_string(),
@@ -102,6 +152,7 @@ impl Texture
 		if (self.clearable)
 		{
 			self.content = None;
+			self.compressed = None;
 		}
 	}
 }
@@ -1,3 +1,4 @@
+use image::imageops::{self, FilterType};
 use image::{ImageBuffer, Rgba, RgbaImage};
 
 pub fn defaultTexture() -> ImageBuffer<Rgba<u8>, Vec<u8>>
@@ -39,3 +40,29 @@ pub fn emptyTexture(width: u32, height: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>>
 {
 	return RgbaImage::new(width, height);
 }
+
+/// number of mip levels of a texture, "wanted" at 0 for the full chain (down to 1x1)
+pub fn mipmapLevels(width: u32, height: u32, wanted: u32) -> u32
+{
+	let full = 32 - width.max(height).max(1).leading_zeros();
+	if (wanted == 0)
+	{
+		return full;
+	}
+	return wanted.min(full);
+}
+
+/// mip chain computed on the cpu (when the gpu cannot blit), the first level is the image itself
+pub fn mipmapChain(image: RgbaImage, levels: u32) -> Vec<RgbaImage>
+{
+	let mut returning = vec![image];
+	for level in 1..levels.max(1)
+	{
+		let previous = &returning[returning.len() - 1];
+		let width = (returning[0].width() >> level).max(1);
+		let height = (returning[0].height() >> level).max(1);
+		let next = imageops::resize(previous, width, height, FilterType::Triangle);
+		returning.push(next);
+	}
+	return returning;
+}
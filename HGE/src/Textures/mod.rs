@@ -3,8 +3,10 @@ pub mod Textures;
 pub mod Filter;
 pub mod generate;
 pub mod textureLoader;
+pub mod textureCompressed;
 pub mod Order;
 pub mod texturePart;
+pub mod TextureAtlas;
 pub mod Orders;
 pub mod TextureDescriptor;
 pub mod Types;
@@ -0,0 +1,168 @@
+use anyhow::anyhow;
+use image::RgbaImage;
+use vulkano::format::Format;
+
+/// block compressed content (BCn, ETC2/EAC or ASTC) uploaded as is to the gpu, see textureLoader_fromKTX2
+#[derive(Clone, Debug)]
+pub struct Texture_compressed
+{
+	pub format: Format,
+	/// each mip level, the biggest first
+	pub levels: Vec<Vec<u8>>,
+}
+
+impl Texture_compressed
+{
+	/// vulkan format from its VkFormat value, srgb variants are read as unorm like the rgba textures
+	pub fn formatFromVk(vkFormat: u32) -> Option<Format>
+	{
+		return match vkFormat
+		{
+			37 | 43 => Some(Format::R8G8B8A8_UNORM),
+			131 | 132 => Some(Format::BC1_RGB_UNORM_BLOCK),
+			133 | 134 => Some(Format::BC1_RGBA_UNORM_BLOCK),
+			135 | 136 => Some(Format::BC2_UNORM_BLOCK),
+			137 | 138 => Some(Format::BC3_UNORM_BLOCK),
+			139 => Some(Format::BC4_UNORM_BLOCK),
+			141 => Some(Format::BC5_UNORM_BLOCK),
+			145 | 146 => Some(Format::BC7_UNORM_BLOCK),
+			147 | 148 => Some(Format::ETC2_R8G8B8_UNORM_BLOCK),
+			149 | 150 => Some(Format::ETC2_R8G8B8A1_UNORM_BLOCK),
+			151 | 152 => Some(Format::ETC2_R8G8B8A8_UNORM_BLOCK),
+			153 => Some(Format::EAC_R11_UNORM_BLOCK),
+			155 => Some(Format::EAC_R11G11_UNORM_BLOCK),
+			157 | 158 => Some(Format::ASTC_4x4_UNORM_BLOCK),
+			159 | 160 => Some(Format::ASTC_5x4_UNORM_BLOCK),
+			161 | 162 => Some(Format::ASTC_5x5_UNORM_BLOCK),
+			163 | 164 => Some(Format::ASTC_6x5_UNORM_BLOCK),
+			165 | 166 => Some(Format::ASTC_6x6_UNORM_BLOCK),
+			167 | 168 => Some(Format::ASTC_8x5_UNORM_BLOCK),
+			169 | 170 => Some(Format::ASTC_8x6_UNORM_BLOCK),
+			171 | 172 => Some(Format::ASTC_8x8_UNORM_BLOCK),
+			173 | 174 => Some(Format::ASTC_10x5_UNORM_BLOCK),
+			175 | 176 => Some(Format::ASTC_10x6_UNORM_BLOCK),
+			177 | 178 => Some(Format::ASTC_10x8_UNORM_BLOCK),
+			179 | 180 => Some(Format::ASTC_10x10_UNORM_BLOCK),
+			181 | 182 => Some(Format::ASTC_12x10_UNORM_BLOCK),
+			183 | 184 => Some(Format::ASTC_12x12_UNORM_BLOCK),
+			_ => None,
+		};
+	}
+
+	/// width, height in pixel and size in bytes of one block (a pixel for rgba)
+	pub fn blockInfos(format: Format) -> Option<([u32; 2], u32)>
+	{
+		return match format
+		{
+			Format::R8G8B8A8_UNORM => Some(([1, 1], 4)),
+			Format::BC1_RGB_UNORM_BLOCK | Format::BC1_RGBA_UNORM_BLOCK | Format::BC4_UNORM_BLOCK => Some(([4, 4], 8)),
+			Format::ETC2_R8G8B8_UNORM_BLOCK | Format::ETC2_R8G8B8A1_UNORM_BLOCK | Format::EAC_R11_UNORM_BLOCK => Some(([4, 4], 8)),
+			Format::BC2_UNORM_BLOCK | Format::BC3_UNORM_BLOCK | Format::BC5_UNORM_BLOCK | Format::BC7_UNORM_BLOCK => Some(([4, 4], 16)),
+			Format::ETC2_R8G8B8A8_UNORM_BLOCK | Format::EAC_R11G11_UNORM_BLOCK => Some(([4, 4], 16)),
+			Format::ASTC_4x4_UNORM_BLOCK => Some(([4, 4], 16)),
+			Format::ASTC_5x4_UNORM_BLOCK => Some(([5, 4], 16)),
+			Format::ASTC_5x5_UNORM_BLOCK => Some(([5, 5], 16)),
+			Format::ASTC_6x5_UNORM_BLOCK => Some(([6, 5], 16)),
+			Format::ASTC_6x6_UNORM_BLOCK => Some(([6, 6], 16)),
+			Format::ASTC_8x5_UNORM_BLOCK => Some(([8, 5], 16)),
+			Format::ASTC_8x6_UNORM_BLOCK => Some(([8, 6], 16)),
+			Format::ASTC_8x8_UNORM_BLOCK => Some(([8, 8], 16)),
+			Format::ASTC_10x5_UNORM_BLOCK => Some(([10, 5], 16)),
+			Format::ASTC_10x6_UNORM_BLOCK => Some(([10, 6], 16)),
+			Format::ASTC_10x8_UNORM_BLOCK => Some(([10, 8], 16)),
+			Format::ASTC_10x10_UNORM_BLOCK => Some(([10, 10], 16)),
+			Format::ASTC_12x10_UNORM_BLOCK => Some(([12, 10], 16)),
+			Format::ASTC_12x12_UNORM_BLOCK => Some(([12, 12], 16)),
+			_ => None,
+		};
+	}
+
+	/// size in bytes of a level of "width" x "height" pixels, None for unsupported format or a size too big to address
+	pub fn levelSize(format: Format, width: u32, height: u32) -> Option<usize>
+	{
+		let ([blockWidth, blockHeight], bytes) = Self::blockInfos(format)?;
+		let size = (width.div_ceil(blockWidth) as u64)
+			.checked_mul(height.div_ceil(blockHeight) as u64)?
+			.checked_mul(bytes as u64)?;
+		return usize::try_from(size).ok();
+	}
+
+	/// cpu decompression of the first level, used when the gpu cannot sample the format or when the texture is resized
+	pub fn decompress(&self, width: u32, height: u32) -> anyhow::Result<RgbaImage>
+	{
+		let Some(data) = self.levels.first()
+		else
+		{
+			return Err(anyhow!("no level to decompress"));
+		};
+		let Some(size) = Self::levelSize(self.format, width, height)
+		else
+		{
+			return Err(anyhow!("unsupported format {:?}", self.format));
+		};
+		if (data.len() < size)
+		{
+			return Err(anyhow!("level of {} bytes too small for {}x{} {:?}", data.len(), width, height, self.format));
+		}
+		if (self.format == Format::R8G8B8A8_UNORM)
+		{
+			return RgbaImage::from_raw(width, height, data[0..size].to_vec()).ok_or(anyhow!("invalid rgba level"));
+		}
+
+		let (w, h) = (width as usize, height as usize);
+		let mut pixels = vec![0u32; w * h];
+		let result = match self.format
+		{
+			Format::BC1_RGB_UNORM_BLOCK | Format::BC1_RGBA_UNORM_BLOCK => texture2ddecoder::decode_bc1(data, w, h, &mut pixels),
+			Format::BC2_UNORM_BLOCK => texture2ddecoder::decode_bc2(data, w, h, &mut pixels),
+			Format::BC3_UNORM_BLOCK => texture2ddecoder::decode_bc3(data, w, h, &mut pixels),
+			Format::BC4_UNORM_BLOCK => texture2ddecoder::decode_bc4(data, w, h, &mut pixels),
+			Format::BC5_UNORM_BLOCK => texture2ddecoder::decode_bc5(data, w, h, &mut pixels),
+			Format::BC7_UNORM_BLOCK => texture2ddecoder::decode_bc7(data, w, h, &mut pixels),
+			Format::ETC2_R8G8B8_UNORM_BLOCK => texture2ddecoder::decode_etc2_rgb(data, w, h, &mut pixels),
+			Format::ETC2_R8G8B8A1_UNORM_BLOCK => texture2ddecoder::decode_etc2_rgba1(data, w, h, &mut pixels),
+			Format::ETC2_R8G8B8A8_UNORM_BLOCK => texture2ddecoder::decode_etc2_rgba8(data, w, h, &mut pixels),
+			Format::EAC_R11_UNORM_BLOCK => texture2ddecoder::decode_eacr(data, w, h, &mut pixels),
+			Format::EAC_R11G11_UNORM_BLOCK => texture2ddecoder::decode_eacrg(data, w, h, &mut pixels),
+			_ => match Self::blockInfos(self.format)
+			{
+				Some(([blockWidth, blockHeight], _)) if Self::isAstc(self.format) => texture2ddecoder::decode_astc(data, w, h, blockWidth as usize, blockHeight as usize, &mut pixels),
+				_ => return Err(anyhow!("no cpu decoder for {:?}", self.format)),
+			},
+		};
+		result.map_err(|err| anyhow!("cannot decompress {:?} : {}", self.format, err))?;
+
+		// decoded pixels are bgra
+		let opaque = self.format == Format::BC1_RGB_UNORM_BLOCK;
+		let mut raw = Vec::with_capacity(w * h * 4);
+		for pixel in pixels
+		{
+			let [b, g, r, a] = pixel.to_le_bytes();
+			raw.extend_from_slice(&[r, g, b, if (opaque) { 255 } else { a }]);
+		}
+		return RgbaImage::from_raw(width, height, raw).ok_or(anyhow!("invalid decompressed size"));
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn isAstc(format: Format) -> bool
+	{
+		return matches!(
+			format,
+			Format::ASTC_4x4_UNORM_BLOCK
+				| Format::ASTC_5x4_UNORM_BLOCK
+				| Format::ASTC_5x5_UNORM_BLOCK
+				| Format::ASTC_6x5_UNORM_BLOCK
+				| Format::ASTC_6x6_UNORM_BLOCK
+				| Format::ASTC_8x5_UNORM_BLOCK
+				| Format::ASTC_8x6_UNORM_BLOCK
+				| Format::ASTC_8x8_UNORM_BLOCK
+				| Format::ASTC_10x5_UNORM_BLOCK
+				| Format::ASTC_10x6_UNORM_BLOCK
+				| Format::ASTC_10x8_UNORM_BLOCK
+				| Format::ASTC_10x10_UNORM_BLOCK
+				| Format::ASTC_12x10_UNORM_BLOCK
+				| Format::ASTC_12x12_UNORM_BLOCK
+		);
+	}
+}
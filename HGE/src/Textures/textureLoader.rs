@@ -4,8 +4,12 @@ use dyn_clone::DynClone;
 use Htrace::HTrace;
 use image::{GenericImageView, ImageFormat};
 use image::io::Reader;
+use vulkano::format::Format;
 use crate::assetStreamReader::assetManager;
+use crate::HGEMain::HGEMain;
+use crate::Textures::Filter::Filter;
 use crate::Textures::Manager::ManagerTexture;
+use crate::Textures::textureCompressed::Texture_compressed;
 use crate::Textures::Textures::{Texture, TextureState};
 
 pub trait textureLoader: DynClone
@@ -30,7 +34,9 @@ pub struct textureLoader_normalized
 	pub raw: Vec<u8>,
 	pub width: u32,
 	pub height: u32,
-	pub clearable: bool
+	pub clearable: bool,
+	/// compressed data, raw is then empty
+	pub compressed: Option<Texture_compressed>,
 }
 
 impl textureLoader_normalized
@@ -38,11 +44,19 @@ impl textureLoader_normalized
 	pub fn fromTexture(texture: &Texture) -> Self
 	{
 		match &texture.content {
+			None if texture.compressed.is_some() => textureLoader_normalized {
+				raw: vec![],
+				width: texture.width.unwrap_or(1),
+				height: texture.height.unwrap_or(1),
+				clearable: texture.clearable,
+				compressed: texture.compressed.clone(),
+			},
 			None => textureLoader_normalized {
 				raw: vec![0,0,0,0],
 				width: 1,
 				height: 1,
 				clearable: true,
+				compressed: None,
 			},
 			Some(textureContent) =>
 				textureLoader_normalized {
@@ -50,9 +64,30 @@ impl textureLoader_normalized
 					width: textureContent.width(),
 					height: textureContent.height(),
 					clearable: texture.clearable,
+					compressed: None,
 				}
 		}
 	}
+	
+	/// apply the filters, compressed data is decompressed before when the gpu cannot sample its format or when there is filters
+	pub fn prepare(&mut self, filters: &[Box<dyn Filter + Send + Sync>]) -> anyhow::Result<()>
+	{
+		if let Some(compressed) = &self.compressed
+		{
+			if (filters.is_empty() && HGEMain::singleton().getDevice().formatSupported(compressed.format))
+			{
+				return Ok(());
+			}
+			HTrace!("texture {:?} decompressed on cpu", compressed.format);
+			self.raw = compressed.decompress(self.width, self.height)?.into_raw();
+			self.compressed = None;
+		}
+		
+		for x in filters.iter() {
+			x.apply(&mut self.raw, self.width, self.height);
+		}
+		return Ok(());
+	}
 }
 
 
@@ -85,6 +120,7 @@ impl textureLoader for textureLoader_fromFile
 			height: im.dimensions().1,
 			raw: im.into_bytes(),
 			clearable: true,
+			compressed: None,
 		});
 	}
 }
@@ -120,6 +156,7 @@ impl textureLoader for textureLoader_fromRaw
 			width: self.width.clone(),
 			height: self.height.clone(),
 			clearable: self.canReload,
+			compressed: None,
 		});
 	}
 	
@@ -130,6 +167,108 @@ impl textureLoader for textureLoader_fromRaw
 }
 
 
+/// KTX2 file (without supercompression) of BCn, ETC2/EAC, ASTC or rgba8 data with its mip levels
+/// the data stay compressed on the gpu when BuilderDevice::formatSupported, else it's decompressed on the cpu
+#[derive(Clone)]
+pub struct textureLoader_fromKTX2
+{
+	pub path: String,
+}
+
+// «KTX 20»\r\n\x1A\n
+const KTX2_IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+impl textureLoader_fromKTX2
+{
+	/// read a KTX2 file content, only 2D textures (no array, cubemap or 3D)
+	pub fn fromBytes(bytes: &[u8]) -> anyhow::Result<textureLoader_normalized>
+	{
+		if (bytes.len() < 80 || bytes[0..12] != KTX2_IDENTIFIER)
+		{
+			return Err(anyhow!("not a KTX2 file"));
+		}
+		let u32At = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+		let u64At = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+		
+		let vkFormat = u32At(12);
+		let width = u32At(20);
+		let height = u32At(24);
+		let (depth, layers, faces, levelCount, supercompression) = (u32At(28), u32At(32), u32At(36), u32At(40).max(1), u32At(44));
+		if (width == 0 || height == 0 || depth > 1 || layers > 1 || faces != 1)
+		{
+			return Err(anyhow!("only 2D KTX2 textures are supported ({}x{}x{}, {} layers, {} faces)", width, height, depth, layers, faces));
+		}
+		if (supercompression != 0)
+		{
+			return Err(anyhow!("KTX2 supercompression {} is not supported", supercompression));
+		}
+		let Some(format) = Texture_compressed::formatFromVk(vkFormat)
+		else
+		{
+			return Err(anyhow!("KTX2 format {} is not supported", vkFormat));
+		};
+		
+		let mut levels = Vec::new();
+		for level in 0..levelCount
+		{
+			let index = 80 + level as usize * 24;
+			if (index + 24 > bytes.len())
+			{
+				return Err(anyhow!("KTX2 level index {} is truncated", level));
+			}
+			let (offset, length) = (u64At(index) as usize, u64At(index + 8) as usize);
+			let levelDim = |dim: u32| dim.checked_shr(level).unwrap_or(0).max(1);
+			let Some(expected) = Texture_compressed::levelSize(format, levelDim(width), levelDim(height))
+			else
+			{
+				return Err(anyhow!("KTX2 level {} is too big ({}x{})", level, levelDim(width), levelDim(height)));
+			};
+			if (length < expected || offset.checked_add(length).map_or(true, |end| end > bytes.len()))
+			{
+				return Err(anyhow!("KTX2 level {} is invalid ({} bytes at {}, {} expected)", level, length, offset, expected));
+			}
+			levels.push(bytes[offset..offset + expected].to_vec());
+		}
+		
+		// rgba is loaded like the other images, its mip chain is generated again
+		if (format == Format::R8G8B8A8_UNORM)
+		{
+			return Ok(textureLoader_normalized {
+				raw: levels.swap_remove(0),
+				width,
+				height,
+				clearable: true,
+				compressed: None,
+			});
+		}
+		
+		return Ok(textureLoader_normalized {
+			raw: vec![],
+			width,
+			height,
+			clearable: true,
+			compressed: Some(Texture_compressed {
+				format,
+				levels,
+			}),
+		});
+	}
+}
+
+impl textureLoader for textureLoader_fromKTX2
+{
+	fn load(&self) -> anyhow::Result<textureLoader_normalized>
+	{
+		let fileread = match assetManager::singleton().readFile(self.path.clone()) {
+			None => return Err(anyhow!("cannot load : {}",self.path)),
+			Some(x) => x
+		};
+		
+		HTrace!("load ktx2 : {}", self.path);
+		return Self::fromBytes(&fileread.into_inner()).map_err(|err| anyhow!("cannot load : {} because {}",self.path,err));
+	}
+}
+
 #[derive(Clone)]
 pub struct textureLoader_fromCopy
 {
@@ -4,6 +4,7 @@ use anyhow::anyhow;
 use csv::ReaderBuilder;
 use dyn_clone::DynClone;
 use foldhash::{HashMap, HashMapExt};
+use Hconfig::serde_json::Value as JsonValue;
 
 pub trait texturePart: DynClone
 {
@@ -35,19 +36,22 @@ impl texturePart for texturePart_FromCSV
 		let mut csv = ReaderBuilder::new().from_reader(fileread);
 		//let mut csv = csv::Reader::from_path(Path::new(format!("{}/{}",Paths::singleton().getStatic(),self.path).as_str()))?;
 		let mut array = HashMap::new();
-		for result in csv.records()
+		for (line, result) in csv.records().enumerate()
 		{
-			if (result.is_err())
+			// line 1 is the header
+			let cols = result.map_err(|err| anyhow!("{} line {} : {}", self.path, line + 2, err))?;
+			if (cols.len() < 8)
 			{
-				continue;
+				return Err(anyhow!("{} line {} : expected 8 columns, found {}", self.path, line + 2, cols.len()));
 			}
+			let number = |index: usize| -> anyhow::Result<f32> {
+				cols[index].trim().parse::<f32>().map_err(|_| anyhow!("{} line {} : column {} \"{}\" is not a number", self.path, line + 2, index + 1, &cols[index]))
+			};
 
-			let cols = result.unwrap();
-
-			let width = cols[3].parse::<f32>().unwrap_or(0.0) as f32;
-			let height = cols[4].parse::<f32>().unwrap_or(0.0) as f32;
-			let posx = cols[5].parse::<f32>().unwrap_or(0.0);
-			let posy = cols[6].parse::<f32>().unwrap_or(0.0);
+			let width = number(3)?;
+			let height = number(4)?;
+			let posx = number(5)?;
+			let posy = number(6)?;
 
 			let uv = [
 				[
@@ -61,13 +65,251 @@ impl texturePart for texturePart_FromCSV
 			];
 			array.insert(
 				cols[7].to_string(),
-				Texture_part {
-					uvcoord: uv,
-					dim: [width as u32, height as u32],
-				},
+				Texture_part::new(uv, [width as u32, height as u32]),
 			);
 			//println!("{} : {:?}",cols[7].to_string(),uv);
 		}
 		return Ok(array);
 	}
 }
+
+/// TexturePacker json export ("JSON (Hash)" or "JSON (Array)"), rotated and trimmed frames included
+#[derive(Clone)]
+pub struct texturePart_FromTexturePacker
+{
+	pub path: String,
+}
+
+impl texturePart for texturePart_FromTexturePacker
+{
+	fn load(&self, texture: &Texture) -> anyhow::Result<HashMap<String, Texture_part>>
+	{
+		let json = texturePart_json::read(&self.path)?;
+		let frames = texturePart_json::frames(&json, texturePart_json::textureSize(texture)?).map_err(|err| anyhow!("{} : {}", self.path, err))?;
+		return Ok(frames.into_iter().map(|(name, part, _)| (name, part)).collect());
+	}
+}
+
+/// Aseprite json export (hash or array), each frame is a part named by its "filename"
+/// with "tags", the frames of each tag are also named "tag#index" (index from 0 inside the tag, e.g. "walk#2")
+#[derive(Clone)]
+pub struct texturePart_FromAseprite
+{
+	pub path: String,
+	pub tags: bool,
+}
+
+impl texturePart for texturePart_FromAseprite
+{
+	fn load(&self, texture: &Texture) -> anyhow::Result<HashMap<String, Texture_part>>
+	{
+		let json = texturePart_json::read(&self.path)?;
+		let frames = texturePart_json::frames(&json, texturePart_json::textureSize(texture)?).map_err(|err| anyhow!("{} : {}", self.path, err))?;
+
+		let mut array = HashMap::new();
+		if (self.tags)
+		{
+			for (tag, from, to) in texturePart_json::tags(&json, frames.len()).map_err(|err| anyhow!("{} : {}", self.path, err))?
+			{
+				for (index, (_, part, _)) in frames[from..=to].iter().enumerate()
+				{
+					array.insert(format!("{}#{}", tag, index), *part);
+				}
+			}
+		}
+		array.extend(frames.into_iter().map(|(name, part, _)| (name, part)));
+		return Ok(array);
+	}
+}
+
+/// uniform grid : "rows" x "cols" cells after a "margin" around the texture and "spacing" between cells
+/// parts are named "{prefix}{index}", index going left to right then top to bottom from 0
+#[derive(Clone)]
+pub struct texturePart_FromGrid
+{
+	pub rows: u32,
+	pub cols: u32,
+	pub margin: u32,
+	pub spacing: u32,
+	pub prefix: String,
+}
+
+impl texturePart for texturePart_FromGrid
+{
+	fn load(&self, texture: &Texture) -> anyhow::Result<HashMap<String, Texture_part>>
+	{
+		let [textureWidth, textureHeight] = texturePart_json::textureSize(texture)?;
+		if (self.rows == 0 || self.cols == 0)
+		{
+			return Err(anyhow!("grid of {}x{} cells", self.cols, self.rows));
+		}
+
+		let usedWidth = (self.margin * 2 + self.spacing * (self.cols - 1)) as f32;
+		let usedHeight = (self.margin * 2 + self.spacing * (self.rows - 1)) as f32;
+		let cellWidth = ((textureWidth - usedWidth) / self.cols as f32).floor();
+		let cellHeight = ((textureHeight - usedHeight) / self.rows as f32).floor();
+		if (cellWidth < 1.0 || cellHeight < 1.0)
+		{
+			return Err(anyhow!("texture {}x{} too small for {}x{} cells with margin {} and spacing {}", textureWidth, textureHeight, self.cols, self.rows, self.margin, self.spacing));
+		}
+
+		let mut array = HashMap::new();
+		for row in 0..self.rows
+		{
+			for col in 0..self.cols
+			{
+				let left = (self.margin + col * self.spacing) as f32 + col as f32 * cellWidth;
+				let top = (self.margin + row * self.spacing) as f32 + row as f32 * cellHeight;
+				array.insert(
+					format!("{}{}", self.prefix, row * self.cols + col),
+					Texture_part::new(
+						[
+							[left / textureWidth, top / textureHeight],
+							[(left + cellWidth) / textureWidth, (top + cellHeight) / textureHeight],
+						],
+						[cellWidth as u32, cellHeight as u32],
+					),
+				);
+			}
+		}
+		return Ok(array);
+	}
+}
+
+/// frames of TexturePacker/Aseprite json exports, shared with SpriteAnimator
+pub struct texturePart_json {}
+
+impl texturePart_json
+{
+	pub fn read(path: &str) -> anyhow::Result<JsonValue>
+	{
+		let Some(fileread) = assetManager::singleton().readFile(path)
+		else
+		{
+			return Err(anyhow!("cannot load : {}", path));
+		};
+		return Hconfig::serde_json::from_reader(fileread).map_err(|err| anyhow!("{} : {}", path, err));
+	}
+
+	/// "meta.size" of the export
+	pub fn sheetSize(json: &JsonValue) -> anyhow::Result<[f32; 2]>
+	{
+		let size = &json["meta"]["size"];
+		return match (size["w"].as_f64(), size["h"].as_f64())
+		{
+			(Some(w), Some(h)) if w > 0.0 && h > 0.0 => Ok([w as f32, h as f32]),
+			_ => Err(anyhow!("missing meta.size")),
+		};
+	}
+
+	/// frames in order (as is for an array, by the number in their name for a hash) : name, part, duration in ms (Aseprite)
+	pub fn frames(json: &JsonValue, textureSize: [f32; 2]) -> anyhow::Result<Vec<(String, Texture_part, Option<u64>)>>
+	{
+		let mut named: Vec<(String, &JsonValue)> = match &json["frames"]
+		{
+			JsonValue::Array(frames) => frames
+				.iter()
+				.enumerate()
+				.map(|(index, frame)| (frame["filename"].as_str().map(|x| x.to_string()).unwrap_or(index.to_string()), frame))
+				.collect(),
+			JsonValue::Object(frames) => frames.iter().map(|(name, frame)| (name.clone(), frame)).collect(),
+			_ => return Err(anyhow!("missing frames")),
+		};
+		if (json["frames"].is_object())
+		{
+			named.sort_by_key(|(name, _)| (Self::nameNumber(name), name.clone()));
+		}
+
+		let mut returning = Vec::new();
+		for (name, frame) in named
+		{
+			let part = Self::part(frame, textureSize).map_err(|err| anyhow!("frame \"{}\" : {}", name, err))?;
+			returning.push((name, part, frame["duration"].as_u64()));
+		}
+		return Ok(returning);
+	}
+
+	/// Aseprite "meta.frameTags" : name, first and last frame
+	pub fn tags(json: &JsonValue, nbFrames: usize) -> anyhow::Result<Vec<(String, usize, usize)>>
+	{
+		let Some(tags) = json["meta"]["frameTags"].as_array()
+		else
+		{
+			return Ok(vec![]);
+		};
+
+		let mut returning = Vec::new();
+		for tag in tags
+		{
+			let (Some(name), Some(from), Some(to)) = (tag["name"].as_str(), tag["from"].as_u64(), tag["to"].as_u64())
+			else
+			{
+				return Err(anyhow!("invalid frame tag {}", tag));
+			};
+			if (from > to || to as usize >= nbFrames)
+			{
+				return Err(anyhow!("tag \"{}\" : frames {}..{} out of {}", name, from, to, nbFrames));
+			}
+			returning.push((name.to_string(), from as usize, to as usize));
+		}
+		return Ok(returning);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	fn textureSize(texture: &Texture) -> anyhow::Result<[f32; 2]>
+	{
+		let width = texture.width.ok_or(anyhow!("texture not loaded"))? as f32;
+		let height = texture.height.ok_or(anyhow!("texture not loaded"))? as f32;
+		return Ok([width, height]);
+	}
+
+	// last number of a frame name ("walk 12.aseprite" -> 12)
+	fn nameNumber(name: &str) -> u64
+	{
+		return name.rsplit(|x: char| !x.is_ascii_digit()).find(|x| !x.is_empty()).and_then(|x| x.parse().ok()).unwrap_or(0);
+	}
+
+	// "frame" is the rect in the texture (width and height swapped when rotated), "spriteSourceSize" the kept rect inside "sourceSize"
+	fn part(frame: &JsonValue, textureSize: [f32; 2]) -> anyhow::Result<Texture_part>
+	{
+		let [x, y, w, h] = Self::rect(&frame["frame"]).ok_or(anyhow!("invalid \"frame\" rect"))?;
+		let rotated = frame["rotated"].as_bool().unwrap_or(false);
+		let (sheetWidth, sheetHeight) = if (rotated) { (h, w) } else { (w, h) };
+		if (x + sheetWidth > textureSize[0] as u32 || y + sheetHeight > textureSize[1] as u32)
+		{
+			return Err(anyhow!("rect {:?} outside of the texture {:?}", [x, y, sheetWidth, sheetHeight], textureSize));
+		}
+
+		let trim = match frame["trimmed"].as_bool().unwrap_or(false)
+		{
+			false => None,
+			true =>
+			{
+				let [offsetX, offsetY, _, _] = Self::rect(&frame["spriteSourceSize"]).ok_or(anyhow!("trimmed without \"spriteSourceSize\""))?;
+				let source = &frame["sourceSize"];
+				let (Some(sourceWidth), Some(sourceHeight)) = (source["w"].as_u64(), source["h"].as_u64())
+				else
+				{
+					return Err(anyhow!("trimmed without \"sourceSize\""));
+				};
+				Some([offsetX, offsetY, sourceWidth as u32, sourceHeight as u32])
+			}
+		};
+
+		return Ok(Texture_part {
+			uvcoord: [
+				[x as f32 / textureSize[0], y as f32 / textureSize[1]],
+				[(x + sheetWidth) as f32 / textureSize[0], (y + sheetHeight) as f32 / textureSize[1]],
+			],
+			dim: [w, h],
+			rotated,
+			trim,
+		});
+	}
+
+	fn rect(value: &JsonValue) -> Option<[u32; 4]>
+	{
+		return Some([value["x"].as_u64()? as u32, value["y"].as_u64()? as u32, value["w"].as_u64()? as u32, value["h"].as_u64()? as u32]);
+	}
+}
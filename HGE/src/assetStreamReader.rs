@@ -1,6 +1,6 @@
 
 use std::fs::File;
-use std::io::{Cursor, Read, Seek};
+use std::io::{BufReader, Cursor, Read, Seek};
 use std::sync::OnceLock;
 use parking_lot::RwLock;
 use crate::Paths::Paths;
@@ -19,6 +19,7 @@ use android_activity::AndroidApp;
 
 
 pub trait SeekRead: Seek + Read + Send + Sync {}
+impl<T: Seek + Read + Send + Sync> SeekRead for T {}
 
 pub struct assetManager
 {
@@ -27,6 +28,9 @@ pub struct assetManager
 	
 	#[cfg(not(target_os = "android"))]
 	assetManager: RwLock<Option<()>>,
+	
+	#[cfg(target_os = "android")]
+	app: RwLock<Option<AndroidApp>>,
 }
 
 static SINGLETON: OnceLock<assetManager> = OnceLock::new();
@@ -37,7 +41,9 @@ impl assetManager
 	{
 		return SINGLETON.get_or_init(|| {
 			Self{
-				assetManager: RwLock::new(None)
+				assetManager: RwLock::new(None),
+				#[cfg(target_os = "android")]
+				app: RwLock::new(None),
 			}
 		});
 	}
@@ -46,6 +52,14 @@ impl assetManager
 	pub fn setApp(&self,app: &AndroidApp)
 	{
 		*self.assetManager.write() = Some(app.asset_manager());
+		*self.app.write() = Some(app.clone());
+	}
+	
+	/// android app defined by setApp (used for soft keyboard)
+	#[cfg(target_os = "android")]
+	pub fn getApp(&self) -> Option<AndroidApp>
+	{
+		return self.app.read().clone();
 	}
 	
 	pub fn readAllFileInDir(&self,path: impl Into<String>) -> Vec<String>
@@ -137,6 +151,27 @@ impl assetManager
 		return None;
 	}
 	
+	/// open a file without reading it entirely, for streaming big file (like music)
+	/// on android, asset cannot be streamed and are read entirely
+	pub fn readFileStream(&self, path: impl Into<String>) -> Option<Box<dyn SeekRead>>
+	{
+		let path = path.into();
+		
+		#[cfg(target_os = "android")]
+		if self.assetManager.read().is_some()
+		{
+			return self.readFile(path).map(|x| Box::new(x) as Box<dyn SeekRead>);
+		}
+		
+		let fullpath = format!("{}/{}",Paths::singleton().getStatic(),path);
+		HTrace!("assetManager readFileStream {}",fullpath);
+		if let Ok(tmp) = File::open(fullpath)
+		{
+			return Some(Box::new(BufReader::new(tmp)));
+		}
+		return None;
+	}
+	
 	pub fn copyFile(&self, path: impl Into<String>, otherpath: impl Into<String>)
 	{
 		let path = path.into();
@@ -49,6 +49,11 @@ impl cacheInfos
 		self.needUpdate
 	}
 	
+	pub fn getUuid(&self) -> Uuid
+	{
+		self.uuid
+	}
+	
 	/// return true if absent OR needupdate
 	pub fn isNotShow(&self) -> bool
 	{
@@ -30,7 +30,11 @@ pub enum event_type
 	EACH_TICK,
 	WINREFRESH,
 	ENTER,
-	EXIT
+	EXIT,
+	FOCUS,
+	BLUR,
+	/// end of a cycle of a SpriteAnimator (or its end in ONCE mode)
+	ANIMATION_END
 }
 
 impl event_type
@@ -277,6 +277,22 @@ impl interfacePosition
 		}
 	}
 	
+	/// resolve parents and dynamic content, and return window pixel coordinates
+	pub fn convertToPixel(&self) -> [f32; 2]
+	{
+		let tmp = self.solveWithParent();
+		match tmp._type
+		{
+			PixelType::PERCENT => {
+				let window = HGEMain::singleton().getWindowInfos();
+				return [tmp.getXraw() * window.widthF, tmp.getYraw() * window.heightF];
+			},
+			PixelType::PIXEL => {
+				return [tmp.getXraw(), tmp.getYraw()];
+			}
+		}
+	}
+
 	#[deprecated]
 	/// add XY, resolve and reset dynamic content if setted
 	pub fn addXY(&self, width: f32, height: f32) -> Self
@@ -17,6 +17,7 @@ pub mod color;
 pub mod hideable;
 pub mod window;
 pub mod cacheInfos;
+pub mod spriteAnimator;
 pub mod system;
 
 pub trait HGEC_base<T>: Clone + Debug + Send + Sync + Default
@@ -0,0 +1,278 @@
+use crate::Textures::texturePart::texturePart_json;
+use crate::Textures::Manager::ManagerTexture;
+use crate::Textures::Textures::Texture_part;
+use anyhow::anyhow;
+use std::time::{Duration, Instant};
+use Hconfig::serde_json::Value as JsonValue;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpriteAnimator_mode
+{
+	/// restart at the first frame after the last one
+	LOOP,
+	/// go back from the last frame to the first one, and so on
+	PINGPONG,
+	/// stop on the last frame
+	ONCE,
+}
+
+/// one image of a sprite animation
+#[derive(Clone, Debug)]
+pub struct SpriteAnimator_frame
+{
+	pub texture: String,
+	/// left top, right top, left bottom, right bottom
+	pub uvcoord: [[f32; 2]; 4],
+	pub duration: Duration,
+	/// trimmed frames only cover this rect of the plane (left, top, right, bottom between 0.0 and 1.0)
+	pub trim: Option<[f32; 4]>,
+}
+
+impl SpriteAnimator_frame
+{
+	pub fn new(texture: impl Into<String>, uvcoord: [[f32; 2]; 4], duration: Duration) -> Self
+	{
+		return SpriteAnimator_frame {
+			texture: texture.into(),
+			uvcoord,
+			duration,
+			trim: None,
+		};
+	}
+
+	pub fn fromPart(texture: impl Into<String>, part: Texture_part, duration: Duration) -> Self
+	{
+		return SpriteAnimator_frame {
+			texture: texture.into(),
+			uvcoord: part.corners(),
+			duration,
+			trim: part.trimRect(),
+		};
+	}
+}
+
+/// flipbook of texture parts played on a Plane (see Plane::setSpriteAnimator)
+/// the plane event ANIMATION_END is triggered at the end of each cycle (or once at the end in ONCE mode)
+#[derive(Clone)]
+pub struct SpriteAnimator
+{
+	_frames: Vec<SpriteAnimator_frame>,
+	_mode: SpriteAnimator_mode,
+	_start: Instant,
+	_pausedAt: Option<Duration>,
+	_lastFrame: Option<usize>,
+	_lastCycle: u64,
+	_isEnded: bool,
+}
+
+impl SpriteAnimator
+{
+	pub fn new(frames: Vec<SpriteAnimator_frame>, mode: SpriteAnimator_mode) -> Self
+	{
+		return SpriteAnimator {
+			_frames: frames,
+			_mode: mode,
+			_start: Instant::now(),
+			_pausedAt: None,
+			_lastFrame: None,
+			_lastCycle: 0,
+			_isEnded: false,
+		};
+	}
+
+	/// frames from the parts (already loaded) of a texture, all shown for 1/fps second
+	pub fn fromParts(texture: impl Into<String>, parts: &[&str], fps: f32, mode: SpriteAnimator_mode) -> anyhow::Result<Self>
+	{
+		let texture = texture.into();
+		let duration = Duration::from_secs_f32(1.0 / fps.max(0.001));
+		let mut frames = Vec::new();
+		for part in parts
+		{
+			let Some(found) = ManagerTexture::singleton().getPart(&texture, *part)
+			else
+			{
+				return Err(anyhow!("part \"{}\" of texture \"{}\" is not loaded", part, texture));
+			};
+			frames.push(SpriteAnimator_frame::fromPart(&texture, found, duration));
+		}
+		return Ok(Self::new(frames, mode));
+	}
+
+	/// frames of an Aseprite json export (hash or array) with their durations, "tag" limit to a tag (its direction set the mode)
+	pub fn fromAseprite(texture: impl Into<String>, path: impl Into<String>, tag: Option<&str>) -> anyhow::Result<Self>
+	{
+		return Self::fromAsepriteJson(texture, &texturePart_json::read(&path.into())?, tag);
+	}
+
+	pub fn fromAsepriteJson(texture: impl Into<String>, json: &JsonValue, tag: Option<&str>) -> anyhow::Result<Self>
+	{
+		let texture = texture.into();
+		let mut frames = Vec::new();
+		for (name, part, duration) in texturePart_json::frames(json, texturePart_json::sheetSize(json)?)?
+		{
+			let duration = duration.ok_or(anyhow!("frame \"{}\" : missing duration", name))?;
+			frames.push(SpriteAnimator_frame::fromPart(&texture, part, Duration::from_millis(duration)));
+		}
+
+		let Some(tag) = tag
+		else
+		{
+			return Ok(Self::new(frames, SpriteAnimator_mode::LOOP));
+		};
+		let Some((_, from, to)) = texturePart_json::tags(json, frames.len())?.into_iter().find(|(name, _, _)| name == tag)
+		else
+		{
+			return Err(anyhow!("missing tag \"{}\"", tag));
+		};
+		let direction = json["meta"]["frameTags"].as_array().and_then(|tags| tags.iter().find(|x| x["name"].as_str() == Some(tag))).and_then(|x| x["direction"].as_str());
+		let mut frames: Vec<_> = frames.drain(from..=to).collect();
+		let mode = match direction
+		{
+			Some("pingpong") => SpriteAnimator_mode::PINGPONG,
+			Some("reverse") =>
+			{
+				frames.reverse();
+				SpriteAnimator_mode::LOOP
+			}
+			_ => SpriteAnimator_mode::LOOP,
+		};
+		return Ok(Self::new(frames, mode));
+	}
+
+	/// frames of a TexturePacker json export (hash or array, rotated frames supported), all shown for 1/fps second
+	pub fn fromTexturePacker(texture: impl Into<String>, path: impl Into<String>, fps: f32, mode: SpriteAnimator_mode) -> anyhow::Result<Self>
+	{
+		return Self::fromTexturePackerJson(texture, &texturePart_json::read(&path.into())?, fps, mode);
+	}
+
+	pub fn fromTexturePackerJson(texture: impl Into<String>, json: &JsonValue, fps: f32, mode: SpriteAnimator_mode) -> anyhow::Result<Self>
+	{
+		let texture = texture.into();
+		let duration = Duration::from_secs_f32(1.0 / fps.max(0.001));
+		let frames = texturePart_json::frames(json, texturePart_json::sheetSize(json)?)?
+			.into_iter()
+			.map(|(_, part, _)| SpriteAnimator_frame::fromPart(&texture, part, duration))
+			.collect();
+		return Ok(Self::new(frames, mode));
+	}
+
+	pub fn setMode(&mut self, mode: SpriteAnimator_mode)
+	{
+		self._mode = mode;
+	}
+
+	pub fn getMode(&self) -> SpriteAnimator_mode
+	{
+		return self._mode;
+	}
+
+	/// change the duration of one frame, do nothing if "index" is out of the frames
+	pub fn setFrameDuration(&mut self, index: usize, duration: Duration)
+	{
+		if let Some(frame) = self._frames.get_mut(index)
+		{
+			frame.duration = duration;
+		}
+	}
+
+	pub fn frames(&self) -> &Vec<SpriteAnimator_frame>
+	{
+		return &self._frames;
+	}
+
+	/// restart from the first frame
+	pub fn play(&mut self)
+	{
+		self._start = Instant::now();
+		self._pausedAt = None;
+		self._lastFrame = None;
+		self._lastCycle = 0;
+		self._isEnded = false;
+	}
+
+	pub fn pause(&mut self)
+	{
+		if (self._pausedAt.is_none())
+		{
+			self._pausedAt = Some(self._start.elapsed());
+		}
+	}
+
+	pub fn resume(&mut self)
+	{
+		if let Some(pausedAt) = self._pausedAt.take()
+		{
+			self._start = Instant::now() - pausedAt;
+		}
+	}
+
+	/// true when a ONCE animation reached its last frame
+	pub fn isEnded(&self) -> bool
+	{
+		return self._isEnded;
+	}
+
+	/// frame index shown after "elapsed", with the number of completed cycles and if a ONCE animation is ended
+	pub fn frameAt(&self, elapsed: Duration) -> (usize, u64, bool)
+	{
+		let sequence = self.sequence();
+		let cycle: Duration = sequence.iter().map(|x| self._frames[*x].duration).sum();
+		if (sequence.is_empty() || cycle.is_zero())
+		{
+			return (sequence.last().copied().unwrap_or(0), 0, self._mode == SpriteAnimator_mode::ONCE);
+		}
+
+		let cycles = (elapsed.as_nanos() / cycle.as_nanos()) as u64;
+		if (self._mode == SpriteAnimator_mode::ONCE && cycles > 0)
+		{
+			return (sequence[sequence.len() - 1], 1, true);
+		}
+
+		let mut remaining = Duration::from_nanos((elapsed.as_nanos() % cycle.as_nanos()) as u64);
+		for index in sequence.iter()
+		{
+			let duration = self._frames[*index].duration;
+			if (remaining < duration)
+			{
+				return (*index, cycles, false);
+			}
+			remaining -= duration;
+		}
+		return (sequence[sequence.len() - 1], cycles, false);
+	}
+
+	/// frame to apply if it changed since the last tick, and true if a cycle ended (or the ONCE animation)
+	pub(crate) fn tick(&mut self) -> (Option<&SpriteAnimator_frame>, bool)
+	{
+		if (self._isEnded)
+		{
+			return (None, false);
+		}
+
+		let elapsed = self._pausedAt.unwrap_or_else(|| self._start.elapsed());
+		let (index, cycles, isEnded) = self.frameAt(elapsed);
+		let cycleEnded = cycles != self._lastCycle;
+		self._lastCycle = cycles;
+		self._isEnded = isEnded;
+		if (self._lastFrame == Some(index))
+		{
+			return (None, cycleEnded);
+		}
+		self._lastFrame = Some(index);
+		return (self._frames.get(index), cycleEnded);
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// frame indexes of one cycle
+	fn sequence(&self) -> Vec<usize>
+	{
+		let count = self._frames.len();
+		let mut returning: Vec<usize> = (0..count).collect();
+		if (self._mode == SpriteAnimator_mode::PINGPONG && count > 2)
+		{
+			returning.extend((1..count - 1).rev());
+		}
+		return returning;
+	}
+}
@@ -8,7 +8,8 @@ use std::time::{Duration, Instant};
 
 pub struct TimeStatsStorage
 {
-	_datas: DashMap<String, RwLock<TimeStats>>
+	_datas: DashMap<String, RwLock<TimeStats>>,
+	_values: DashMap<String, u128>
 }
 static SINGLETON: OnceLock<TimeStatsStorage> = OnceLock::new();
 
@@ -18,6 +19,7 @@ impl TimeStatsStorage
 	{
 		return SINGLETON.get_or_init(|| Self {
 			_datas: Default::default(),
+			_values: Default::default(),
 		});
 	}
 	
@@ -51,6 +53,19 @@ impl TimeStatsStorage
 		Self::singleton()._datas.get_mut(&key).unwrap().write().putElapsed();
 	}
 	
+	/**
+	 * set a raw value stats (size, count, etc), not a duration
+	 */
+	pub fn setValue(key: impl Into<String>, value: u128)
+	{
+		Self::singleton()._values.insert(key.into(), value);
+	}
+	
+	pub fn getValue(key: impl Into<String>) -> Option<u128>
+	{
+		return Self::singleton()._values.get(&key.into()).map(|x| *x);
+	}
+	
 	pub fn get<'a>() -> Iter<'a, String, RwLock<TimeStats>, RandomState, DashMap<String, RwLock<TimeStats>>>
 	{
 		return Self::singleton()._datas.iter();
@@ -62,6 +77,9 @@ impl Display for TimeStatsStorage {
 		self._datas.iter().for_each(|k| {
 			let _ = write!(f, "| {} = {}", k.key(), &*k.read());
 		});
+		self._values.iter().for_each(|k| {
+			let _ = write!(f, "| {} = {}", k.key(), k.value());
+		});
 		
 		return Ok(());
 	}
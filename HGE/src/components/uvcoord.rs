@@ -7,10 +7,24 @@ pub struct uvcoord
 	pub top: f32,
 	pub right: f32,
 	pub bottom: f32,
+	/// stored turned 90° clockwise in the texture
+	pub rotated: bool,
 }
 
 impl uvcoord
 {
+	/// not rotated uv rect
+	pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self
+	{
+		uvcoord{
+			left,
+			top,
+			right,
+			bottom,
+			rotated: false,
+		}
+	}
+	
 	pub fn toArray2(&self) -> [[f32; 2]; 2]
 	{
 		[[self.left,self.top],[self.right,self.bottom]]
@@ -18,6 +32,15 @@ impl uvcoord
 	
 	pub fn toArray4(&self) -> [[f32; 2]; 4]
 	{
+		if(self.rotated)
+		{
+			return [
+				[self.right,self.top],
+				[self.right,self.bottom],
+				[self.left,self.top],
+				[self.left,self.bottom]
+			];
+		}
 		[
 			[self.left,self.top],
 			[self.right,self.top],
@@ -41,6 +64,7 @@ impl Default for uvcoord
 			top: 0.0,
 			right: 1.0,
 			bottom: 1.0,
+			rotated: false,
 		}
 	}
 }
@@ -53,6 +77,7 @@ impl From<Texture_part> for uvcoord
 			top: value.uvcoord[0][1],
 			right: value.uvcoord[1][0],
 			bottom: value.uvcoord[1][1],
+			rotated: value.rotated,
 		}
 	}
 }
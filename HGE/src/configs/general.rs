@@ -25,6 +25,10 @@ pub struct HGEconfig_general
 	pub isSteamdeck: bool,
 	/// set true if the running device is android
 	pub isAndroid: bool,
+	/// route arrow keys, tab, enter and gamepad d-pad/A to ManagerInterface focus navigation
+	/// gamepads are only read by the sdl front, winit have no gamepad support
+	/// always enabled on steamdeck
+	pub uiNavigation: bool,
 	pub defaultShaderLoader: Option<Arc<dyn Fn() + Sync + Send>>,
 	pub fonts: HGEconfig_general_font,
 	pub debug_showTimer: bool,
@@ -41,6 +45,7 @@ impl Default for HGEconfig_general
 			configName: "HGE".to_string(),
 			isSteamdeck: false,
 			isAndroid: false,
+			uiNavigation: false,
 			defaultShaderLoader: None,
 			fonts: HGEconfig_general_font {
 				path_fileUser: "".to_string(),
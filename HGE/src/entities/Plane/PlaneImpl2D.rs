@@ -25,6 +25,28 @@ impl Plane<interfacePosition>
 		self._pos[3] = bottomRight;
 		self._cacheinfos.setNeedUpdate(true);
 	}
+
+	///////////////// PRIVATE ////////////////
+
+	// vertex "i", moved on the part covered by a trimmed sprite frame
+	fn spriteTrimmed(&self, i: usize) -> interfacePosition
+	{
+		let Some([left, top, right, bottom]) = self._spriteTrim
+		else
+		{
+			return self._pos[i].clone();
+		};
+
+		let [u, v] = [[left, top], [right, top], [left, bottom], [right, bottom]][i];
+		let corners = self._pos.clone().map(|x| x.convertToPixel());
+		let lerp = |a: [f32; 2], b: [f32; 2], t: f32| [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t];
+		let [x, y] = lerp(lerp(corners[0], corners[1], u), lerp(corners[2], corners[3], u), v);
+
+		let mut pos = interfacePosition::new_pixel_z(0, 0, self._pos[i].getZ());
+		pos.setX(x);
+		pos.setY(y);
+		return pos;
+	}
 }
 
 impl UiPageContent for Plane<interfacePosition>
@@ -42,6 +64,20 @@ impl UiPageContent for Plane<interfacePosition>
 	{
 		self._hitbox.clone()
 	}
+
+	fn layout_place(&mut self, leftTop: interfacePosition, bottomRight: interfacePosition)
+	{
+		self.setSquare(leftTop, bottomRight);
+	}
+
+	fn layout_clip(&mut self, clip: Option<[f32; 4]>)
+	{
+		if (self._clip != clip)
+		{
+			self._clip = clip;
+			self._cacheinfos.setNeedUpdate(true);
+		}
+	}
 }
 
 impl ShaderDrawerImpl for Plane<interfacePosition>
@@ -115,7 +151,7 @@ impl ShaderDrawerImplReturn<HGE_shader_2Dsimple_def> for Plane<interfacePosition
 		let mut vecstruct = Vec::new();
 		for i in 0..4
 		{
-			let mut tmp = self._pos[i].clone();
+			let mut tmp = self.spriteTrimmed(i);
 			self._components.computeVertex(&mut tmp);
 
 			vecstruct.push(HGE_shader_2Dsimple_def {
@@ -125,10 +161,12 @@ impl ShaderDrawerImplReturn<HGE_shader_2Dsimple_def> for Plane<interfacePosition
 				uvcoord: self._uvcoord.map(|x| x[i]).unwrap_or(textureuvcoord[i]),
 				color: self._color.map(|x| x[i].blend(texturecolor)).unwrap_or(texturecolor).toArray(),
 				color_blend_type: color_blend_type,
+				clip: self._clip.unwrap_or([0.0; 4]),
 			});
 		}
 
-		if let Some(vec) = &self._posHitbox
+		// a trimmed sprite frame keep the hitbox of the whole square
+		if let Some(vec) = self._posHitbox.as_ref().or(self._spriteTrim.map(|_| &self._pos))
 		{
 			let mut hitboxvec = Vec::new();
 			for i in 0..4
@@ -173,7 +211,11 @@ impl event_trait for Plane<interfacePosition>
 {
 	fn event_trigger(&mut self, eventtype: event_type) -> bool
 	{
-		let update = self._events.clone().trigger(eventtype, self);
+		let mut update = self._events.clone().trigger(eventtype, self);
+		if (eventtype == event_type::EACH_TICK && self.spriteTick())
+		{
+			update = true;
+		}
 		if (eventtype == event_type::WINREFRESH)
 		{
 			self._cacheinfos.setNeedUpdate(true);
@@ -187,7 +229,7 @@ impl event_trait for Plane<interfacePosition>
 
 	fn event_have(&self, eventtype: event_type) -> bool
 	{
-		self._events.have(eventtype)
+		self._events.have(eventtype) || (eventtype == event_type::EACH_TICK && self._sprite.is_some())
 	}
 }
 
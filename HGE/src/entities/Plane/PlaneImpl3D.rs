@@ -164,7 +164,11 @@ impl event_trait for Plane<worldPosition>
 {
 	fn event_trigger(&mut self, eventtype: event_type) -> bool
 	{
-		let update = self._events.clone().trigger(eventtype, self);
+		let mut update = self._events.clone().trigger(eventtype, self);
+		if (eventtype == event_type::EACH_TICK && self.spriteTick())
+		{
+			update = true;
+		}
 		if (eventtype == event_type::WINREFRESH)
 		{
 			self._cacheinfos.setNeedUpdate(true);
@@ -175,7 +179,7 @@ impl event_trait for Plane<worldPosition>
 
 	fn event_have(&self, eventtype: event_type) -> bool
 	{
-		self._events.have(eventtype)
+		self._events.have(eventtype) || (eventtype == event_type::EACH_TICK && self._sprite.is_some())
 	}
 }
 
@@ -1,6 +1,6 @@
 use std::default::Default;
 use crate::components::corners::corner4;
-use crate::components::event::event;
+use crate::components::event::{event, event_type};
 use crate::components::{Components, HGEC_origin, HGEC_rotation, HGEC_scale};
 use crate::components::cacheInfos::cacheInfos;
 use crate::Interface::UiHitbox::UiHitbox;
@@ -8,6 +8,7 @@ use crate::components::color::color;
 use crate::components::offset::offset;
 use crate::components::rotations::rotation;
 use crate::components::scale::scale;
+use crate::components::spriteAnimator::SpriteAnimator;
 use crate::entities::utils::entities_utils;
 
 pub mod PlaneImpl2D;
@@ -23,9 +24,12 @@ pub struct Plane<A>
 	_posHitbox: Option<[A; 4]>,
 	_uvcoord: Option<[[f32; 2]; 4]>,
 	_color: Option<[color; 4]>,
+	_clip: Option<[f32; 4]>,
 	_hitbox: UiHitbox,
 	_events: event<Plane<A>>,
-	_cacheinfos: cacheInfos
+	_cacheinfos: cacheInfos,
+	_sprite: Option<SpriteAnimator>,
+	_spriteTrim: Option<[f32; 4]>,
 }
 
 impl<A> Plane<A>
@@ -41,9 +45,12 @@ impl<A> Plane<A>
 			_posHitbox: None,
 			_uvcoord: None,
 			_color: None, //[[1.0, 1.0, 1.0, 1.0], [1.0, 1.0, 1.0, 1.0], [1.0, 1.0, 1.0, 1.0], [1.0, 1.0, 1.0, 1.0]],
+			_clip: None,
 			_hitbox: UiHitbox::new(),
 			_events: event::new(),
 			_cacheinfos: cacheInfos::default(),
+			_sprite: None,
+			_spriteTrim: None,
 		};
 	}
 	
@@ -132,6 +139,53 @@ impl<A> Plane<A>
 		self._posHitbox = Some(newpos.intoArray());
 		self._cacheinfos.setNeedUpdate(true);
 	}
+	
+	/// play a sprite animation on the texture of the plane (replacing its texture and uv), None to stop it
+	/// advanced by the event EACH_TICK (triggered by UiPage for 2D planes)
+	/// trimmed frames are drawn on their part of the square (2D planes only)
+	pub fn setSpriteAnimator(&mut self, animator: Option<SpriteAnimator>)
+	{
+		self._sprite = animator;
+		self._spriteTrim = None;
+		self._cacheinfos.setNeedUpdate(true);
+		if let Some(animator) = &mut self._sprite
+		{
+			animator.play();
+		}
+		self.spriteTick();
+	}
+	
+	pub fn spriteAnimator_mut(&mut self) -> Option<&mut SpriteAnimator>
+	{
+		self._sprite.as_mut()
+	}
+	
+	/// apply the current frame of the sprite animation, trigger ANIMATION_END at the end of a cycle
+	/// return true if the plane changed
+	pub(crate) fn spriteTick(&mut self) -> bool
+	{
+		let Some(animator) = &mut self._sprite else {return false};
+		let (frame, isCycleEnded) = animator.tick();
+		let frame = frame.cloned();
+		
+		let mut changed = false;
+		if let Some(frame) = frame
+		{
+			if (self._components.texture().getName().as_deref() != Some(frame.texture.as_str()))
+			{
+				self._components.texture_mut().set(frame.texture);
+			}
+			self._uvcoord = Some(frame.uvcoord);
+			self._spriteTrim = frame.trim;
+			self._cacheinfos.setNeedUpdate(true);
+			changed = true;
+		}
+		if (isCycleEnded && self._events.clone().trigger(event_type::ANIMATION_END, self))
+		{
+			changed = true;
+		}
+		return changed;
+	}
 }
 
 impl<A> entities_utils for Plane<A>
@@ -146,9 +200,12 @@ where A: HGEC_origin,
 			_posHitbox: self._posHitbox.clone(),
 			_uvcoord: self._uvcoord.clone(),
 			_color: self._color.clone(),
+			_clip: self._clip,
 			_hitbox: self._hitbox.clone(),
 			_events: self._events.clone(),
 			_cacheinfos: cacheInfos::default(),
+			_sprite: self._sprite.clone(),
+			_spriteTrim: self._spriteTrim,
 		};
 	}
 }
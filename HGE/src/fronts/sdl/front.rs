@@ -2,22 +2,35 @@ use crate::configs::HGEconfig::HGEconfig;
 use crate::fronts::sdl::sdl_UserDefinedEventOverride::sdl_UserDefinedEventOverride;
 use crate::fronts::EngineEvent::EngineEvent;
 use crate::HGEMain::HGEMain;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::UiPage::{UiFocus_direction, UiTextEvent, UiTextKey};
+use sdl2::controller::{Button, GameController};
 use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::mouse::MouseButton;
 use sdl2::video::Window;
-use sdl2::{Sdl, VideoSubsystem};
+use sdl2::{GameControllerSubsystem, Sdl, VideoSubsystem};
+use std::collections::HashMap;
 use std::sync::Arc;
 use vulkano::instance::{Instance, InstanceExtensions};
 use vulkano::swapchain::Surface;
 use Hconfig::serde_json::Value as JsonValue;
 use Hconfig::HConfigManager::HConfigManager;
 
+// pixel scrolled by one mouse wheel step
+const WHEEL_LINE_PIXEL: f32 = 40.0;
+
 pub struct HGEsdl
 {
 	_sdl_context: Sdl,
 	_sdl_video: VideoSubsystem,
+	_sdl_controller: Option<GameControllerSubsystem>,
+	// sdl only send controller events for opened controllers, keyed by instance id
+	_controllers: HashMap<u32, GameController>,
 	_events: EngineEvent,
 	_window: Option<Window>,
 	_instance_extensions: Option<InstanceExtensions>,
+	_textInputActive: bool,
 }
 
 impl HGEsdl
@@ -26,13 +39,18 @@ impl HGEsdl
 	{
 		let sdl_context = sdl2::init().unwrap();
 		let video_subsystem = sdl_context.video().unwrap();
+		// already plugged controllers are sent as ControllerDeviceAdded
+		let controller_subsystem = sdl_context.game_controller().ok();
 
 		Self {
 			_sdl_context: sdl_context,
 			_sdl_video: video_subsystem,
+			_sdl_controller: controller_subsystem,
+			_controllers: HashMap::new(),
 			_events: EngineEvent::new(),
 			_window: None,
 			_instance_extensions: None,
+			_textInputActive: false,
 		}
 	}
 
@@ -79,6 +97,8 @@ impl HGEsdl
 				}
 			}
 
+			self.textInputUpdate();
+
 			if let Some(event) = &mut userEvent
 			{
 				println!("dfsdf");
@@ -138,6 +158,80 @@ impl HGEsdl
 				self._events.window_eventClose();
 				return true;
 			}
+			Event::TextInput { text, .. } =>
+			{
+				ManagerInterface::singleton().textInput(UiTextEvent::TEXT(text.clone()));
+			}
+			Event::TextEditing { text, .. } =>
+			{
+				ManagerInterface::singleton().textInput(UiTextEvent::PREEDIT(text.clone()));
+			}
+			Event::MouseWheel { x, y, .. } =>
+			{
+				ManagerInterface::singleton().scrollUpdate(-*x as f32 * WHEEL_LINE_PIXEL, -*y as f32 * WHEEL_LINE_PIXEL);
+			}
+			// touch is also sent as mouse events by sdl
+			Event::MouseButtonDown {
+				mouse_btn: MouseButton::Left, x, y, ..
+			} =>
+			{
+				Self::dragUpdate(*x, *y, true);
+			}
+			Event::MouseMotion { mousestate, x, y, .. } =>
+			{
+				if (mousestate.left())
+				{
+					Self::dragUpdate(*x, *y, true);
+				}
+			}
+			Event::MouseButtonUp {
+				mouse_btn: MouseButton::Left, x, y, ..
+			} =>
+			{
+				Self::dragUpdate(*x, *y, false);
+			}
+			Event::KeyDown { keycode: Some(keycode), keymod, .. } =>
+			{
+				if (ManagerInterface::singleton().textInput_isWanted() && Self::textInput(*keycode, *keymod))
+				{
+					return false;
+				}
+
+				let direction = match *keycode
+				{
+					Keycode::Up => Some(UiFocus_direction::UP),
+					Keycode::Down => Some(UiFocus_direction::DOWN),
+					Keycode::Left => Some(UiFocus_direction::LEFT),
+					Keycode::Right => Some(UiFocus_direction::RIGHT),
+					Keycode::Tab => Some(UiFocus_direction::NEXT),
+					_ => None,
+				};
+				let activate = *keycode == Keycode::Return || *keycode == Keycode::KpEnter;
+				Self::uiNavigation(direction, activate);
+			}
+			Event::ControllerDeviceAdded { which, .. } =>
+			{
+				if let Some(controller) = self._sdl_controller.as_ref().and_then(|x| x.open(*which).ok())
+				{
+					self._controllers.insert(controller.instance_id(), controller);
+				}
+			}
+			Event::ControllerDeviceRemoved { which, .. } =>
+			{
+				self._controllers.remove(which);
+			}
+			Event::ControllerButtonDown { button, .. } =>
+			{
+				let direction = match *button
+				{
+					Button::DPadUp => Some(UiFocus_direction::UP),
+					Button::DPadDown => Some(UiFocus_direction::DOWN),
+					Button::DPadLeft => Some(UiFocus_direction::LEFT),
+					Button::DPadRight => Some(UiFocus_direction::RIGHT),
+					_ => None,
+				};
+				Self::uiNavigation(direction, *button == Button::A);
+			}
 			_ =>
 			{}
 		}
@@ -145,6 +239,76 @@ impl HGEsdl
 		return false;
 	}
 
+	// send editing keys to the focused text content (typed text come from Event::TextInput)
+	fn textInput(keycode: Keycode, keymod: Mod) -> bool
+	{
+		let shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+		let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD | Mod::LGUIMOD | Mod::RGUIMOD);
+		let textkey = match keycode
+		{
+			Keycode::Backspace => UiTextKey::BACKSPACE,
+			Keycode::Delete => UiTextKey::DELETE,
+			Keycode::Left => UiTextKey::LEFT,
+			Keycode::Right => UiTextKey::RIGHT,
+			Keycode::Home => UiTextKey::HOME,
+			Keycode::End => UiTextKey::END,
+			Keycode::Return | Keycode::KpEnter => UiTextKey::SUBMIT,
+			Keycode::A if ctrl => UiTextKey::SELECT_ALL,
+			Keycode::C if ctrl => UiTextKey::COPY,
+			Keycode::X if ctrl => UiTextKey::CUT,
+			Keycode::V if ctrl => UiTextKey::PASTE,
+			_ => return false,
+		};
+		return ManagerInterface::singleton().textInput(UiTextEvent::KEY(textkey, shift));
+	}
+
+	// start or stop sdl text input (IME, soft keyboard) when a text content take or lose the focus
+	fn textInputUpdate(&mut self)
+	{
+		let wanted = ManagerInterface::singleton().textInput_isWanted();
+		if (wanted == self._textInputActive)
+		{
+			return;
+		}
+		self._textInputActive = wanted;
+
+		if (wanted)
+		{
+			self._sdl_video.text_input().start();
+		}
+		else
+		{
+			self._sdl_video.text_input().stop();
+		}
+	}
+
+	// mouse or touch drag, corrected for HDPI and orientation like the winit front
+	fn dragUpdate(x: i32, y: i32, pressed: bool)
+	{
+		let mut x = x as f64;
+		let mut y = y as f64;
+		HGEMain::singleton().getWindowCorrectedMousePos(&mut x, &mut y);
+		ManagerInterface::singleton().dragUpdate(x.max(0.0) as u16, y.max(0.0) as u16, pressed);
+	}
+
+	fn uiNavigation(direction: Option<UiFocus_direction>, activate: bool)
+	{
+		let configBind = HGEconfig::singleton().general_get();
+		if (!configBind.uiNavigation && !configBind.isSteamdeck)
+		{
+			return;
+		}
+
+		if let Some(direction) = direction
+		{
+			ManagerInterface::singleton().navigate(direction);
+		}
+		if (activate)
+		{
+			ManagerInterface::singleton().activate();
+		}
+	}
+
 	fn getSurface(&self, instance: Arc<Instance>) -> Arc<Surface>
 	{
 		return unsafe {
@@ -1,18 +1,30 @@
+use crate::configs::HGEconfig::HGEconfig;
 use crate::fronts::winit::front::HGEwinit;
 use crate::fronts::winit::winit_UserDefinedEventOverride::winit_UserDefinedEventOverride;
 use crate::HGEMain::HGEMain;
+use crate::Interface::ManagerInterface::ManagerInterface;
+use crate::Interface::UiPage::{UiFocus_direction, UiTextEvent, UiTextKey};
+#[cfg(target_os = "android")]
+use crate::assetStreamReader::assetManager;
 use winit::application::ApplicationHandler;
-use winit::event::{DeviceEvent, DeviceId, ElementState, WindowEvent};
+use winit::event::{DeviceEvent, DeviceId, ElementState, Ime, KeyEvent, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow};
-use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::keyboard::{Key, KeyCode, NamedKey, PhysicalKey};
 use winit::window::WindowId;
 use Htrace::HTrace;
 use Htrace::Type::Type;
 
+// pixel scrolled by one mouse wheel step
+const WHEEL_LINE_PIXEL: f32 = 40.0;
+
 pub struct internalWinitState<'a>
 {
 	events: Option<Box<&'a mut dyn winit_UserDefinedEventOverride>>,
 	winit_root: &'a mut HGEwinit,
+	imeAllowed: bool,
+	imeComposing: bool,
+	pointerPosition: [f64; 2],
+	pointerPressed: bool,
 }
 
 impl<'a> internalWinitState<'a>
@@ -25,6 +37,121 @@ impl<'a> internalWinitState<'a>
 		Self {
 			events: userEvents.map(|e| Box::new(e as &'a mut dyn winit_UserDefinedEventOverride)),
 			winit_root,
+			imeAllowed: false,
+			imeComposing: false,
+			pointerPosition: [0.0, 0.0],
+			pointerPressed: false,
+		}
+	}
+
+	///////////////// PRIVATE ////////////////
+
+	// mouse or touch drag (raw window position), used by scrolling contents
+	fn pointerUpdate(&mut self, x: f64, y: f64, pressed: bool)
+	{
+		self.pointerPosition = [x, y];
+		self.pointerPressed = pressed;
+		let mut x = x;
+		let mut y = y;
+		HGEMain::singleton().getWindowCorrectedMousePos(&mut x, &mut y);
+		ManagerInterface::singleton().dragUpdate(x.max(0.0) as u16, y.max(0.0) as u16, pressed);
+	}
+
+	fn uiNavigation(key: KeyCode)
+	{
+		let direction = match key
+		{
+			KeyCode::ArrowUp => UiFocus_direction::UP,
+			KeyCode::ArrowDown => UiFocus_direction::DOWN,
+			KeyCode::ArrowLeft => UiFocus_direction::LEFT,
+			KeyCode::ArrowRight => UiFocus_direction::RIGHT,
+			KeyCode::Tab => UiFocus_direction::NEXT,
+			KeyCode::Enter | KeyCode::NumpadEnter =>
+			{
+				ManagerInterface::singleton().activate();
+				return;
+			}
+			_ => return,
+		};
+		ManagerInterface::singleton().navigate(direction);
+	}
+
+	// send editing keys and typed text to the focused text content
+	fn textInput(&self, input: &KeyEvent) -> bool
+	{
+		let inputs = self.winit_root.Inputs_get();
+		let isPressed = |keys: [KeyCode; 2]| keys.iter().any(|x| inputs.getKeyboardState(*x) == ElementState::Pressed);
+		let shift = isPressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+		let ctrl = isPressed([KeyCode::ControlLeft, KeyCode::ControlRight]) || isPressed([KeyCode::SuperLeft, KeyCode::SuperRight]);
+
+		let textkey = match &input.logical_key
+		{
+			Key::Named(NamedKey::Backspace) => Some(UiTextKey::BACKSPACE),
+			Key::Named(NamedKey::Delete) => Some(UiTextKey::DELETE),
+			Key::Named(NamedKey::ArrowLeft) => Some(UiTextKey::LEFT),
+			Key::Named(NamedKey::ArrowRight) => Some(UiTextKey::RIGHT),
+			Key::Named(NamedKey::Home) => Some(UiTextKey::HOME),
+			Key::Named(NamedKey::End) => Some(UiTextKey::END),
+			Key::Named(NamedKey::Enter) => Some(UiTextKey::SUBMIT),
+			Key::Character(char) if ctrl => match char.to_lowercase().as_str()
+			{
+				"a" => Some(UiTextKey::SELECT_ALL),
+				"c" => Some(UiTextKey::COPY),
+				"x" => Some(UiTextKey::CUT),
+				"v" => Some(UiTextKey::PASTE),
+				_ => None,
+			},
+			_ => None,
+		};
+
+		if let Some(textkey) = textkey
+		{
+			return ManagerInterface::singleton().textInput(UiTextEvent::KEY(textkey, shift));
+		}
+
+		// text is given by Ime::Commit during composition
+		if (ctrl || self.imeComposing)
+		{
+			return false;
+		}
+
+		match &input.text
+		{
+			Some(text) if !text.is_empty() && !text.chars().any(|c| c.is_control()) =>
+			{
+				ManagerInterface::singleton().textInput(UiTextEvent::TEXT(text.to_string()))
+			}
+			_ => false,
+		}
+	}
+
+	// open or close IME (and soft keyboard on android) when a text content take or lose the focus
+	fn textInputUpdate(&mut self)
+	{
+		let wanted = ManagerInterface::singleton().textInput_isWanted();
+		if (wanted == self.imeAllowed)
+		{
+			return;
+		}
+		self.imeAllowed = wanted;
+		self.imeComposing = false;
+
+		if let Some(window) = self.winit_root.getWindow()
+		{
+			window.set_ime_allowed(wanted);
+		}
+
+		#[cfg(target_os = "android")]
+		if let Some(app) = assetManager::singleton().getApp()
+		{
+			if (wanted)
+			{
+				app.show_soft_input(true);
+			}
+			else
+			{
+				app.hide_soft_input(false);
+			}
 		}
 	}
 }
@@ -98,7 +225,79 @@ impl<'a> ApplicationHandler<()> for internalWinitState<'a>
 				{
 					let inputsC = self.winit_root.Inputs_getmut();
 					inputsC.updateFromKeyboard(key, input.state);
+
+					if (input.state == ElementState::Pressed)
+					{
+						let mut used = false;
+						if (ManagerInterface::singleton().textInput_isWanted())
+						{
+							used = self.textInput(input);
+						}
+
+						let configBind = HGEconfig::singleton().general_get();
+						if (!used && (configBind.uiNavigation || configBind.isSteamdeck))
+						{
+							Self::uiNavigation(key);
+						}
+					}
+				}
+			}
+			WindowEvent::Ime(ime) => match ime
+			{
+				Ime::Preedit(text, _) =>
+				{
+					self.imeComposing = !text.is_empty();
+					ManagerInterface::singleton().textInput(UiTextEvent::PREEDIT(text.clone()));
 				}
+				Ime::Commit(text) =>
+				{
+					self.imeComposing = false;
+					ManagerInterface::singleton().textInput(UiTextEvent::TEXT(text.clone()));
+				}
+				Ime::Enabled | Ime::Disabled => (),
+			},
+			WindowEvent::MouseWheel { delta, .. } =>
+			{
+				match delta
+				{
+					MouseScrollDelta::LineDelta(x, y) =>
+					{
+						ManagerInterface::singleton().scrollUpdate(-x * WHEEL_LINE_PIXEL, -y * WHEEL_LINE_PIXEL);
+					}
+					MouseScrollDelta::PixelDelta(pos) =>
+					{
+						ManagerInterface::singleton().scrollUpdate(-pos.x as f32, -pos.y as f32);
+					}
+				}
+			}
+			WindowEvent::CursorMoved { position, .. } =>
+			{
+				if (self.pointerPressed)
+				{
+					self.pointerUpdate(position.x, position.y, true);
+				}
+				else
+				{
+					self.pointerPosition = [position.x, position.y];
+				}
+			}
+			WindowEvent::MouseInput {
+				state,
+				button: MouseButton::Left,
+				..
+			} =>
+			{
+				let [x, y] = self.pointerPosition;
+				self.pointerUpdate(x, y, *state == ElementState::Pressed);
+			}
+			WindowEvent::Touch(touch) =>
+			{
+				let pressed = match touch.phase
+				{
+					TouchPhase::Started | TouchPhase::Moved => true,
+					TouchPhase::Ended | TouchPhase::Cancelled => false,
+				};
+				self.pointerUpdate(touch.location.x, touch.location.y, pressed);
 			}
 			WindowEvent::Resized(winsize) =>
 			{
@@ -162,6 +361,7 @@ impl<'a> ApplicationHandler<()> for internalWinitState<'a>
 		}
 
 		self.winit_root.event_mut().runService();
+		self.textInputUpdate();
 		if let Some(window) = self.winit_root.getWindow()
 		{
 			window.request_redraw();
@@ -0,0 +1,262 @@
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use HGE::components::worldPosition::worldPosition;
+use HGE::ManagerAudio::{audio_bus, audio_bus_stealing, audio_music_event, audio_music_repeat, deviceStream, ManagerAudio, AUDIO_BUS_EFFECT, AUDIO_BUS_MUSIC};
+use HGE::Paths::{Paths, Paths_define};
+
+// ManagerAudio is a singleton, tests changing its levels or playlist must not run at the same time
+static AUDIO_LOCK: Mutex<()> = Mutex::new(());
+
+// "durationMs" of 440hz mono sine, 16 bits PCM
+fn writeTestWav(path: PathBuf, durationMs: u32)
+{
+	let samplerate: u32 = 44100;
+	let samples: Vec<i16> = (0..samplerate * durationMs / 1000)
+		.map(|i| ((i as f32 * 440.0 * std::f32::consts::TAU / samplerate as f32).sin() * 8000.0) as i16)
+		.collect();
+	let datasize = samples.len() as u32 * 2;
+
+	let mut file = File::create(path).unwrap();
+	file.write_all(b"RIFF").unwrap();
+	file.write_all(&(36 + datasize).to_le_bytes()).unwrap();
+	file.write_all(b"WAVEfmt ").unwrap();
+	file.write_all(&16u32.to_le_bytes()).unwrap();
+	file.write_all(&1u16.to_le_bytes()).unwrap();
+	file.write_all(&1u16.to_le_bytes()).unwrap();
+	file.write_all(&samplerate.to_le_bytes()).unwrap();
+	file.write_all(&(samplerate * 2).to_le_bytes()).unwrap();
+	file.write_all(&2u16.to_le_bytes()).unwrap();
+	file.write_all(&16u16.to_le_bytes()).unwrap();
+	file.write_all(b"data").unwrap();
+	file.write_all(&datasize.to_le_bytes()).unwrap();
+	samples.iter().for_each(|x| file.write_all(&x.to_le_bytes()).unwrap());
+}
+
+// "beep" (0.1s) and "long" (2s) effects
+fn setup() -> &'static ManagerAudio
+{
+	let base = std::env::temp_dir().join("HGE_test_audio");
+	std::fs::create_dir_all(base.join("static")).unwrap();
+	writeTestWav(base.join("static").join("beep.wav"), 100);
+	writeTestWav(base.join("static").join("long.wav"), 2000);
+	Paths::define(Paths_define {
+		base: Some(base.display().to_string()),
+		..Default::default()
+	});
+
+	let audio = ManagerAudio::singleton();
+	audio.loadFile("beep", "beep.wav", 0.5);
+	audio.loadFile("long", "long.wav", 1.0);
+	return audio;
+}
+
+// effects and playlist are queued from other threads, let them land and let the null stream progress
+fn step(audio: &ManagerAudio, stream: &mut Option<deviceStream>, waitMs: u64)
+{
+	std::thread::sleep(Duration::from_millis(waitMs));
+	audio.executeAudioQueue(stream);
+}
+
+// names of the tracks started since the last call
+fn musicPlayed(stream: &mut Option<deviceStream>) -> Vec<String>
+{
+	return stream.as_mut().unwrap().history_take().into_iter().filter(|x| x.bus == AUDIO_BUS_MUSIC).map(|x| x.name).collect();
+}
+
+#[test]
+fn nullStream_history()
+{
+	let _lock = AUDIO_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+	let audio = setup();
+	audio.effectLevel_set(0.5);
+
+	let mut stream = Some(ManagerAudio::getNullStream());
+	audio.effect_play("beep");
+	// effect queue is filled from another thread
+	std::thread::sleep(Duration::from_millis(100));
+	audio.executeAudioQueue(&mut stream);
+
+	let history = stream.as_mut().unwrap().history_take();
+	assert_eq!(history.len(), 1);
+	assert_eq!(history[0].name, "beep");
+	assert_eq!(history[0].bus, AUDIO_BUS_EFFECT);
+	assert!((history[0].level - 0.25).abs() < 0.001);
+	assert!(audio.cache_size_get() > 0);
+
+	// history is emptied by history_take
+	audio.executeAudioQueue(&mut stream);
+	assert_eq!(stream.as_mut().unwrap().history_take().len(), 0);
+}
+
+#[test]
+fn bus_levels_ducking()
+{
+	let _lock = AUDIO_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+	let audio = setup();
+	audio.globalLevel_set(0.5);
+	audio.bus_set("test_dialog", audio_bus::new(2));
+	let mut ambient = audio_bus::new(2);
+	ambient.level = 0.8;
+	ambient.duckedBy = vec![("test_dialog".to_string(), 0.25)];
+	audio.bus_set("test_ambient", ambient);
+
+	let mut stream = Some(ManagerAudio::getNullStream());
+	audio.effect_play_bus("long", "test_ambient", 1.0);
+	step(audio, &mut stream, 100);
+
+	// global * bus * local level * volume, nothing ducked yet
+	let history: Vec<_> = stream.as_mut().unwrap().history_take().into_iter().filter(|x| x.bus == "test_ambient").collect();
+	assert_eq!(history.len(), 1);
+	assert!((history[0].level - 0.4).abs() < 0.001);
+
+	audio.effect_play_bus("long", "test_dialog", 0.5);
+	step(audio, &mut stream, 100);
+	let history: Vec<_> = stream.as_mut().unwrap().history_take().into_iter().filter(|x| x.bus == "test_dialog").collect();
+	assert_eq!(history.len(), 1);
+	assert!((history[0].level - 0.25).abs() < 0.001);
+
+	// ducking move at 4 per second, so 0.25 is reached after ~0.2s
+	step(audio, &mut stream, 400);
+	let voices = stream.as_ref().unwrap().voices_get("test_ambient");
+	assert_eq!(voices.len(), 1);
+	assert!((voices[0].1 - 0.1).abs() < 0.001);
+
+	// sounds started while ducked are reported with the ducked level
+	audio.effect_play_bus("beep", "test_ambient", 1.0);
+	step(audio, &mut stream, 100);
+	let history: Vec<_> = stream.as_mut().unwrap().history_take().into_iter().filter(|x| x.bus == "test_ambient").collect();
+	assert_eq!(history.len(), 1);
+	assert!((history[0].level - 0.05).abs() < 0.001);
+
+	audio.globalLevel_set(1.0);
+}
+
+#[test]
+fn bus_spatial_voices()
+{
+	let _lock = AUDIO_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+	let audio = setup();
+	let mut refuse = audio_bus::new(2);
+	refuse.stealing = audio_bus_stealing::REFUSE;
+	audio.bus_set("test_spatial_refuse", refuse);
+	audio.bus_set("test_spatial_oldest", audio_bus::new(1));
+
+	// spatial sounds follow maxVoices and stealing of their bus
+	let mut stream = Some(ManagerAudio::getNullStream());
+	for _ in 0..3
+	{
+		audio.effect_play_at_bus("long", "test_spatial_refuse", worldPosition::default(), 1.0);
+	}
+	step(audio, &mut stream, 50);
+	let history = stream.as_mut().unwrap().history_take();
+	assert_eq!(history.iter().filter(|x| x.bus == "test_spatial_refuse").count(), 2);
+	assert_eq!(stream.as_ref().unwrap().voices_get("test_spatial_refuse").len(), 2);
+
+	// stealing only take voices of its own bus
+	audio.effect_play_at_bus("long", "test_spatial_oldest", worldPosition::default(), 1.0);
+	step(audio, &mut stream, 50);
+	audio.effect_play_at_bus("long", "test_spatial_oldest", worldPosition::default(), 1.0);
+	step(audio, &mut stream, 50);
+	let history = stream.as_mut().unwrap().history_take();
+	assert_eq!(history.iter().filter(|x| x.bus == "test_spatial_oldest").count(), 2);
+	assert_eq!(stream.as_ref().unwrap().voices_get("test_spatial_oldest").len(), 1);
+	assert_eq!(stream.as_ref().unwrap().voices_get("test_spatial_refuse").len(), 2);
+}
+
+#[test]
+fn music_playlist_crossfade()
+{
+	let _lock = AUDIO_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+	let audio = setup();
+	audio.music_unpause();
+	audio.music_repeat_set(audio_music_repeat::ALL);
+	audio.music_shuffle_set(false);
+	audio.music_crossfade_set(Duration::ZERO);
+	audio.musicLevel_set(1.0);
+	for name in ["m1", "m2", "m3"]
+	{
+		audio.loadFile(name, "long.wav", 1.0);
+		audio.music_add(name);
+	}
+
+	// the playlist start by itself
+	let mut stream = Some(ManagerAudio::getNullStream());
+	step(audio, &mut stream, 150);
+	assert_eq!(musicPlayed(&mut stream), vec!["m1"]);
+	assert_eq!(audio.music_current(), Some("m1".to_string()));
+
+	// next / prev, wrapping with repeat ALL
+	audio.music_next();
+	step(audio, &mut stream, 50);
+	audio.music_prev();
+	step(audio, &mut stream, 50);
+	audio.music_prev();
+	step(audio, &mut stream, 50);
+	audio.music_next();
+	step(audio, &mut stream, 50);
+	assert_eq!(musicPlayed(&mut stream), vec!["m2", "m1", "m3", "m1"]);
+
+	// shuffle keep the current track, a full cycle play every track once
+	audio.music_shuffle_set(true);
+	let mut played = vec![];
+	for _ in 0..3
+	{
+		audio.music_next();
+		step(audio, &mut stream, 50);
+		played.extend(musicPlayed(&mut stream));
+	}
+	assert_eq!(played.last(), Some(&"m1".to_string()));
+	played.sort();
+	assert_eq!(played, vec!["m1", "m2", "m3"]);
+	audio.music_shuffle_set(false);
+
+	// repeat NONE stop at the end of the playlist
+	audio.music_play("m3");
+	step(audio, &mut stream, 50);
+	audio.music_repeat_set(audio_music_repeat::NONE);
+	audio.music_next();
+	step(audio, &mut stream, 50);
+	assert_eq!(musicPlayed(&mut stream), vec!["m3"]);
+	assert_eq!(audio.music_current(), Some("m3".to_string()));
+	audio.music_prev();
+	step(audio, &mut stream, 50);
+	assert_eq!(musicPlayed(&mut stream), vec!["m2"]);
+	audio.music_repeat_set(audio_music_repeat::ALL);
+
+	// pause keep the track and its position, without ENDED event
+	let events = Arc::new(Mutex::new(vec![]));
+	let eventsListener = events.clone();
+	audio.music_onEvent(move |event| eventsListener.lock().unwrap().push(event.clone()));
+	audio.music_pause();
+	step(audio, &mut stream, 50);
+	step(audio, &mut stream, 300);
+	audio.music_unpause();
+	step(audio, &mut stream, 50);
+	assert!(!events.lock().unwrap().iter().any(|x| matches!(x, audio_music_event::ENDED(_))));
+	assert_eq!(musicPlayed(&mut stream), Vec::<String>::new());
+	assert_eq!(audio.music_current(), Some("m2".to_string()));
+	let voices = stream.as_ref().unwrap().voices_get(AUDIO_BUS_MUSIC);
+	assert_eq!(voices.iter().filter(|(track, _)| track.as_deref() == Some("m2")).count(), 1);
+
+	// during a crossfade, both fades move at the same speed
+	audio.music_crossfade_set(Duration::from_secs(1));
+	audio.music_next();
+	step(audio, &mut stream, 50);
+	step(audio, &mut stream, 400);
+	assert_eq!(musicPlayed(&mut stream), vec!["m3"]);
+	assert_eq!(audio.music_current(), Some("m3".to_string()));
+	let voices = stream.as_ref().unwrap().voices_get(AUDIO_BUS_MUSIC);
+	let volume = |name: &str| voices.iter().find(|(track, _)| track.as_deref() == Some(name)).map(|(_, volume)| *volume).unwrap();
+	assert!(volume("m3") > 0.2 && volume("m3") < 0.8);
+	assert!((volume("m3") + volume("m2") - 1.0).abs() < 0.01);
+
+	audio.music_crossfade_set(Duration::ZERO);
+	audio.music_pause();
+	step(audio, &mut stream, 0);
+}
@@ -0,0 +1,854 @@
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+use HGE::components::cacheInfos::cacheInfos;
+use HGE::components::color::color;
+use HGE::components::enums::{ALIGN_H, ALIGN_V};
+use HGE::components::event::{event_trait, event_type};
+use HGE::components::interfacePosition::interfacePosition;
+use HGE::components::spriteAnimator::{SpriteAnimator, SpriteAnimator_frame, SpriteAnimator_mode};
+use HGE::entities::Plane::Plane;
+use HGE::Interface::FontSdf::FontSdf;
+use HGE::Interface::ManagerFont::{ManagerFont, MANAGERFONT_SHRINK_MINSCALE};
+use HGE::Interface::ManagerInterface::ManagerInterface;
+use HGE::Interface::UiButton::UiButton;
+use HGE::Interface::UiCheckbox::UiCheckbox;
+use HGE::Interface::UiDropdown::UiDropdown;
+use HGE::Interface::ManagerLocale::ManagerLocale;
+use HGE::Interface::UiLayout::{UiLayout, UiLayout_direction, UiLayout_padding, UiLayout_type};
+use HGE::Interface::UiMarkup::UiMarkup;
+use HGE::Interface::UiOverlay::UiOverlay_options;
+use HGE::Interface::TextEffect::{TextEffect, TextEffect_glyph};
+use HGE::Interface::TextMarkup::{TextMarkup, TextMarkup_segment, TextMarkup_size};
+use HGE::Interface::UiHitbox::UiHitbox;
+use HGE::Interface::UiPanel9Slice::{UiPanel9Slice, UiPanel9Slice_mode};
+use HGE::Interface::UiRadioGroup::UiRadioGroup;
+use HGE::Interface::UiPage::{UiFocus_direction, UiPage, UiPageContent, UiScrollEvent};
+use HGE::Interface::UiScrollView::UiScrollView;
+use HGE::Interface::UiSlider::UiSlider;
+use HGE::Interface::UiSpinner::UiSpinner;
+use HGE::Interface::Text::{Extra, TextSize};
+use HGE::Interface::UiTextInput::{UiTextInput, UiTextInput_validation};
+use HGE::Interface::UiTheme::UiTheme;
+use HGE::Interface::UiTransition::{UiTransition, UiTransition_easing, UiTransition_type};
+use HGE::Shaders::HGE_shader_2Dsimple::{HGE_shader_2Dsimple, HGE_shader_2Dsimple_transform};
+use HGE::Shaders::ShaderDrawerImpl::{ShaderDrawerImpl, ShaderDrawerImplReturn};
+use HGE::Textures::Textures::Texture_part;
+use ab_glyph::FontArc;
+use glyph_brush::{OwnedSection, OwnedText};
+use glyph_brush_layout::FontId;
+use Hconfig::serde_json::json;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn planeSquare(layout: &mut UiLayout, index: usize) -> [f32; 4]
+{
+	let content: &mut dyn UiPageContent = &mut **layout.content_mut(index).unwrap();
+	let plane = content.downcast_mut::<Plane<interfacePosition>>().unwrap();
+	let pos = plane.getVertexPos();
+	return [pos[0].getX(), pos[0].getY(), pos[3].getX(), pos[3].getY()];
+}
+
+#[test]
+fn layout_flex_grow()
+{
+	let mut layout = UiLayout::new(UiLayout_type::FLEX(UiLayout_direction::HORIZONTAL), interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(300, 100));
+	layout.setPadding(UiLayout_padding::same(10.0));
+	layout.setGap(10.0);
+	layout.add(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(50, 0));
+	layout.addGrow(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(0, 0), 1.0);
+	layout.addGrow(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(0, 40), 1.0);
+	layout.layout_update();
+
+	assert_eq!(planeSquare(&mut layout, 0), [10.0, 10.0, 60.0, 90.0]);
+	assert_eq!(planeSquare(&mut layout, 1), [70.0, 10.0, 175.0, 90.0]);
+	assert_eq!(planeSquare(&mut layout, 2), [185.0, 10.0, 290.0, 50.0]);
+}
+
+#[test]
+fn layout_stack_align()
+{
+	let mut layout = UiLayout::new(UiLayout_type::STACK(UiLayout_direction::VERTICAL), interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(100, 100));
+	layout.setAlign(ALIGN_H::CENTER, ALIGN_V::BOTTOM);
+	layout.add(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(20, 20));
+	layout.add(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(40, 30));
+	layout.layout_update();
+
+	assert_eq!(planeSquare(&mut layout, 0), [40.0, 50.0, 60.0, 70.0]);
+	assert_eq!(planeSquare(&mut layout, 1), [30.0, 70.0, 70.0, 100.0]);
+}
+
+#[test]
+fn layout_grid_cells()
+{
+	let mut layout = UiLayout::new(UiLayout_type::GRID(2), interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(210, 210));
+	layout.setGap(10.0);
+	for _ in 0..3
+	{
+		layout.add(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(0, 0));
+	}
+	layout.layout_update();
+
+	assert_eq!(planeSquare(&mut layout, 0), [0.0, 0.0, 100.0, 100.0]);
+	assert_eq!(planeSquare(&mut layout, 1), [110.0, 0.0, 210.0, 100.0]);
+	assert_eq!(planeSquare(&mut layout, 2), [0.0, 110.0, 100.0, 210.0]);
+}
+
+// focusable content with a fixed hitbox, without drawing
+#[derive(Clone)]
+struct focusBox
+{
+	hitbox: UiHitbox,
+	focused: bool,
+	cacheinfos: cacheInfos,
+}
+
+impl focusBox
+{
+	fn new(x: f32, y: f32) -> Self
+	{
+		let mut hitbox = UiHitbox::new();
+		hitbox.updateFromPoint(x, y);
+		hitbox.updateFromPoint(x + 50.0, y + 20.0);
+		return focusBox {
+			hitbox,
+			focused: false,
+			cacheinfos: cacheInfos::default(),
+		};
+	}
+}
+
+impl event_trait for focusBox
+{
+	fn event_trigger(&mut self, eventtype: event_type) -> bool
+	{
+		match eventtype
+		{
+			event_type::FOCUS => self.focused = true,
+			event_type::BLUR => self.focused = false,
+			_ => return false,
+		}
+		return true;
+	}
+}
+
+impl ShaderDrawerImpl for focusBox
+{
+	fn cache_mustUpdate(&self) -> bool
+	{
+		false
+	}
+
+	fn cache_infos(&self) -> &cacheInfos
+	{
+		&self.cacheinfos
+	}
+
+	fn cache_infos_mut(&mut self) -> &mut cacheInfos
+	{
+		&mut self.cacheinfos
+	}
+
+	fn cache_submit(&mut self) {}
+
+	fn cache_remove(&mut self) {}
+}
+
+impl UiPageContent for focusBox
+{
+	fn getHitbox(&self) -> UiHitbox
+	{
+		self.hitbox.clone()
+	}
+
+	fn isFocusable(&self) -> bool
+	{
+		true
+	}
+}
+
+fn isFocused(page: &UiPage, name: &str) -> bool
+{
+	let content = page.get(name).unwrap();
+	let content = content.get();
+	let content: &dyn UiPageContent = &**content;
+	return content.downcast_ref::<focusBox>().unwrap().focused;
+}
+
+#[test]
+fn focus_navigation()
+{
+	let mut page = UiPage::new();
+	page.add("topLeft", focusBox::new(0.0, 0.0));
+	page.add("topRight", focusBox::new(200.0, 0.0));
+	page.add("bottomLeft", focusBox::new(0.0, 100.0));
+
+	// first navigation focus the first content (top to bottom, left to right)
+	assert!(page.eventNavigate(UiFocus_direction::DOWN));
+	assert_eq!(page.focus_get(), Some("topLeft".to_string()));
+	assert!(isFocused(&page, "topLeft"));
+
+	assert!(page.eventNavigate(UiFocus_direction::RIGHT));
+	assert_eq!(page.focus_get(), Some("topRight".to_string()));
+	assert!(!isFocused(&page, "topLeft"));
+	assert!(isFocused(&page, "topRight"));
+
+	// nothing on the right
+	assert!(!page.eventNavigate(UiFocus_direction::RIGHT));
+
+	assert!(page.eventNavigate(UiFocus_direction::DOWN));
+	assert_eq!(page.focus_get(), Some("bottomLeft".to_string()));
+
+	page.focusOrder_set(vec!["bottomLeft".to_string(), "topRight".to_string()]);
+	assert!(page.eventNavigate(UiFocus_direction::NEXT));
+	assert_eq!(page.focus_get(), Some("topRight".to_string()));
+	assert!(page.eventNavigate(UiFocus_direction::NEXT));
+	assert_eq!(page.focus_get(), Some("bottomLeft".to_string()));
+}
+
+fn isNestedFocused(page: &UiPage, name: &str, index: usize) -> bool
+{
+	let content = page.get(name).unwrap();
+	let content = content.get();
+	let content: &dyn UiPageContent = &**content;
+	return content.childs()[index].downcast_ref::<focusBox>().unwrap().focused;
+}
+
+#[test]
+fn focus_nested()
+{
+	let mut layout = UiLayout::new(UiLayout_type::STACK(UiLayout_direction::HORIZONTAL), interfacePosition::new_pixel(0, 100), interfacePosition::new_pixel(300, 150));
+	layout.add(focusBox::new(0.0, 100.0), interfacePosition::new_pixel(50, 20));
+	layout.add(focusBox::new(200.0, 100.0), interfacePosition::new_pixel(50, 20));
+
+	let mut page = UiPage::new();
+	page.add("top", focusBox::new(0.0, 0.0));
+	page.add("menu", layout);
+
+	// contents inside a container are reached by navigation, named "container/index"
+	assert!(page.eventNavigate(UiFocus_direction::DOWN));
+	assert_eq!(page.focus_get(), Some("top".to_string()));
+	assert!(page.eventNavigate(UiFocus_direction::DOWN));
+	assert_eq!(page.focus_get(), Some("menu/0".to_string()));
+	assert!(isNestedFocused(&page, "menu", 0));
+	assert!(page.eventNavigate(UiFocus_direction::RIGHT));
+	assert_eq!(page.focus_get(), Some("menu/1".to_string()));
+	assert!(!isNestedFocused(&page, "menu", 0));
+	assert!(isNestedFocused(&page, "menu", 1));
+	assert!(!page.focus_set("menu/2"));
+
+	// activation reach a button nested in a scroll view
+	let clicked = Arc::new(AtomicU32::new(0));
+	let sub_clicked = clicked.clone();
+	let mut button = UiButton::new();
+	button.setClickedFn(move |_| {
+		sub_clicked.fetch_add(1, Ordering::Relaxed);
+	});
+	let mut view = UiScrollView::new(interfacePosition::new_pixel(0, 200), interfacePosition::new_pixel(100, 300));
+	view.setContent(button, interfacePosition::new_pixel(0, 0));
+	page.add("view", view);
+
+	assert!(page.focus_set("view/0"));
+	assert!(!isNestedFocused(&page, "menu", 1));
+	assert!(page.eventActivate());
+	assert_eq!(clicked.load(Ordering::Relaxed), 1);
+
+	page.remove("view");
+	assert_eq!(page.focus_get(), None);
+}
+
+#[test]
+fn textInput_validation()
+{
+	let numeric = UiTextInput_validation::NUMERIC;
+	assert!(numeric.isValid(""));
+	assert!(numeric.isValid("-"));
+	assert!(numeric.isValid("-12.5"));
+	assert!(!numeric.isValid("1.2.3"));
+	assert!(!numeric.isValid("1-2"));
+	assert!(!numeric.isValid("12a"));
+
+	let regex = UiTextInput_validation::REGEX(regex::Regex::new("^[a-zA-Z_]{0,12}$").unwrap());
+	assert!(regex.isValid("player_one"));
+	assert!(!regex.isValid("player one"));
+}
+
+#[test]
+fn textInput_withoutFonts()
+{
+	let mut input = UiTextInput::new(interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(200, 40));
+	input.setTextSize(TextSize::FIX(20.0));
+	input.setValue("héllo wörld");
+	input.selectAll();
+
+	// fonts are not loaded, text measurement only return the origin
+	input.cache_submit();
+	assert_eq!(input.getSelection(), Some((0, 11)));
+}
+
+#[test]
+fn scrollView_wheel_and_drag()
+{
+	let mut view = UiScrollView::new(interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(100, 100));
+	view.setContent(Plane::<interfacePosition>::new(), interfacePosition::new_pixel(0, 300));
+	view.layout_update();
+	assert_eq!(view.getScrollMax(), [0.0, 200.0]);
+
+	let planeTop = |view: &mut UiScrollView| {
+		let content: &mut dyn UiPageContent = &mut **view.content_mut().unwrap();
+		let plane = content.downcast_mut::<Plane<interfacePosition>>().unwrap();
+		let pos = plane.getVertexPos();
+		[pos[0].getY(), pos[3].getY()]
+	};
+
+	assert!(view.event_scroll(&UiScrollEvent::WHEEL([0.0, 50.0])));
+	view.layout_update();
+	assert_eq!(planeTop(&mut view), [-50.0, 250.0]);
+
+	// clamped to the content size
+	view.event_scroll(&UiScrollEvent::WHEEL([0.0, 1000.0]));
+	view.layout_update();
+	assert_eq!(view.getScroll(), [0.0, 200.0]);
+	assert!(!view.event_scroll(&UiScrollEvent::WHEEL([0.0, 10.0])));
+
+	// dragging up show what is below, so dragging down go back up
+	assert!(view.event_scroll(&UiScrollEvent::DRAG_START([50.0, 20.0])));
+	assert!(view.event_scroll(&UiScrollEvent::DRAG_MOVE([50.0, 80.0])));
+	assert!(view.event_scroll(&UiScrollEvent::DRAG_END));
+	view.layout_update();
+	assert_eq!(planeTop(&mut view), [-140.0, 160.0]);
+}
+
+#[test]
+fn slider_values()
+{
+	let mut slider = UiSlider::new(interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(100, 20));
+	slider.setRange(0.0, 10.0, 2.0);
+	slider.setValue(3.2);
+	assert_eq!(slider.getValue(), 4.0);
+	slider.setValue(42.0);
+	assert_eq!(slider.getValue(), 10.0);
+}
+
+// widgets are placed away from the mouse (0,0), so CLICKED act like a keyboard/gamepad activation
+fn widgetSquare() -> (interfacePosition, interfacePosition)
+{
+	return (interfacePosition::new_pixel(200, 200), interfacePosition::new_pixel(300, 260));
+}
+
+#[test]
+fn checkbox_toggle()
+{
+	let (leftTop, bottomRight) = widgetSquare();
+	let mut checkbox = UiCheckbox::new(leftTop, bottomRight, "sound");
+	assert!(!checkbox.isChecked());
+	assert!(checkbox.event_trigger(event_type::CLICKED));
+	assert!(checkbox.isChecked());
+
+	// held down, no toggle until released
+	assert!(!checkbox.event_trigger(event_type::CLICKED));
+	assert!(checkbox.isChecked());
+	checkbox.event_trigger(event_type::IDLE);
+	assert!(checkbox.event_trigger(event_type::CLICKED));
+	assert!(!checkbox.isChecked());
+}
+
+#[test]
+fn radioGroup_selection()
+{
+	let (leftTop, bottomRight) = widgetSquare();
+	let mut radio = UiRadioGroup::new(leftTop, bottomRight, vec!["easy".to_string(), "normal".to_string(), "hard".to_string()]);
+	assert_eq!(radio.getSelected(), None);
+
+	// activation select the next option, only one selected at a time
+	radio.event_trigger(event_type::CLICKED);
+	assert_eq!(radio.getSelected(), Some(0));
+	radio.event_trigger(event_type::IDLE);
+	radio.event_trigger(event_type::CLICKED);
+	assert_eq!(radio.getSelected(), Some(1));
+	assert_eq!(radio.getSelectedLabel(), Some(&"normal".to_string()));
+	radio.event_trigger(event_type::IDLE);
+	radio.setSelected(Some(2));
+	radio.event_trigger(event_type::CLICKED);
+	assert_eq!(radio.getSelected(), Some(0));
+
+	radio.setSelected(Some(3));
+	assert_eq!(radio.getSelected(), None);
+}
+
+#[test]
+fn dropdown_selection()
+{
+	let (leftTop, bottomRight) = widgetSquare();
+	let mut dropdown = UiDropdown::new(leftTop, bottomRight, vec!["low".to_string(), "high".to_string()]);
+
+	// activation select the next option without opening the popup
+	dropdown.event_trigger(event_type::CLICKED);
+	assert_eq!(dropdown.getSelected(), Some(0));
+	assert!(!dropdown.isOpen());
+	dropdown.event_trigger(event_type::IDLE);
+	dropdown.event_trigger(event_type::CLICKED);
+	assert_eq!(dropdown.getSelectedLabel(), Some(&"high".to_string()));
+	dropdown.event_trigger(event_type::IDLE);
+	dropdown.event_trigger(event_type::CLICKED);
+	assert_eq!(dropdown.getSelected(), Some(0));
+	assert!(!dropdown.isOpen());
+}
+
+#[test]
+fn spinner_bounds()
+{
+	let (leftTop, bottomRight) = widgetSquare();
+	let mut spinner = UiSpinner::new(leftTop, bottomRight);
+	spinner.setRange(0.0, 3.0, 1.0);
+	spinner.setValue(2.0);
+	assert!(spinner.increment(1));
+	assert_eq!(spinner.getValue(), 3.0);
+	assert!(!spinner.increment(1));
+	assert_eq!(spinner.getValue(), 3.0);
+
+	spinner.setLoop(true);
+	assert!(spinner.increment(1));
+	assert_eq!(spinner.getValue(), 0.0);
+	assert!(spinner.increment(-1));
+	assert_eq!(spinner.getValue(), 3.0);
+}
+
+fn sliderValue(page: &UiPage, name: &str) -> f32
+{
+	let content = page.get(name).unwrap();
+	let content = content.get();
+	let content: &dyn UiPageContent = &**content;
+	return content.downcast_ref::<UiSlider>().unwrap().getValue();
+}
+
+fn spinnerValue(page: &UiPage, name: &str) -> f32
+{
+	let content = page.get(name).unwrap();
+	let content = content.get();
+	let content: &dyn UiPageContent = &**content;
+	return content.downcast_ref::<UiSpinner>().unwrap().getValue();
+}
+
+#[test]
+fn navigate_leftRight_values()
+{
+	let (leftTop, bottomRight) = widgetSquare();
+	let mut slider = UiSlider::new(leftTop, bottomRight);
+	slider.setRange(0.0, 10.0, 2.0);
+	slider.setValue(8.0);
+	let mut spinner = UiSpinner::new(interfacePosition::new_pixel(200, 300), interfacePosition::new_pixel(300, 340));
+	spinner.setRange(0.0, 5.0, 1.0);
+
+	let mut page = UiPage::new();
+	page.add("slider", slider);
+	page.add("spinner", spinner);
+
+	// left/right change the focused value, and keep the focus on it at the ends
+	assert!(page.focus_set("slider"));
+	assert!(page.eventNavigate(UiFocus_direction::RIGHT));
+	assert_eq!(sliderValue(&page, "slider"), 10.0);
+	assert!(page.eventNavigate(UiFocus_direction::RIGHT));
+	assert_eq!(sliderValue(&page, "slider"), 10.0);
+	assert!(page.eventNavigate(UiFocus_direction::LEFT));
+	assert_eq!(sliderValue(&page, "slider"), 8.0);
+	assert_eq!(page.focus_get(), Some("slider".to_string()));
+
+	assert!(page.focus_set("spinner"));
+	assert!(page.eventNavigate(UiFocus_direction::LEFT));
+	assert_eq!(spinnerValue(&page, "spinner"), 0.0);
+	assert!(page.eventNavigate(UiFocus_direction::RIGHT));
+	assert!(page.eventNavigate(UiFocus_direction::RIGHT));
+	assert_eq!(spinnerValue(&page, "spinner"), 2.0);
+}
+
+#[test]
+fn theme_classes()
+{
+	let theme = json!({
+		"default": { "background": "#336699", "padding": 6 },
+		"button": { "backgroundHover": [1.0, 0.0, 0.0], "textSize": 20 },
+		"danger": { "extends": "button", "background": "#ff000080" }
+	});
+	UiTheme::singleton().loadJson(&theme).unwrap();
+
+	let danger = UiTheme::singleton().get("danger");
+	assert_eq!(danger.background.toArray(), color::from([255u8, 0, 0, 128]).toArray());
+	assert_eq!(danger.backgroundHover.toArray(), [1.0, 0.0, 0.0, 1.0]);
+	assert_eq!(danger.padding, 6.0);
+	// unknown class fallback to "default"
+	assert_eq!(UiTheme::singleton().get("missing").background.toArray(), color::from([0x33u8, 0x66, 0x99, 255]).toArray());
+
+	// invalid theme are refused and the current one is kept
+	assert!(UiTheme::singleton().loadJson(&json!({ "a": { "extends": "b" }, "b": { "extends": "a" } })).is_err());
+	assert!(UiTheme::singleton().loadJson(&json!({ "a": { "colour": "#000000" } })).is_err());
+	assert!(UiTheme::singleton().have("danger"));
+
+	// buttons and widgets keep their class, and are restyled on WINREFRESH after a theme change
+	let mut button = UiButton::new();
+	button.setStyleClass("danger");
+	assert_eq!(button.getStyle().unwrap().backgroundHover.toArray(), [1.0, 0.0, 0.0, 1.0]);
+	let mut slider = UiSlider::new(interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(100, 20));
+	slider.setStyleClass("danger");
+
+	let mut style = UiTheme::singleton().get("danger");
+	style.padding = 10.0;
+	UiTheme::singleton().set("danger", style);
+	assert_eq!(button.getStyle().unwrap().padding, 6.0);
+	button.event_trigger(event_type::WINREFRESH);
+	slider.event_trigger(event_type::WINREFRESH);
+	assert_eq!(button.getStyle().unwrap().padding, 10.0);
+	assert_eq!(slider.getStyle().padding, 10.0);
+	assert!(UiButton::new().getStyle().is_none());
+}
+
+#[test]
+fn panel9Slice_quads()
+{
+	let part = Texture_part::new([[0.0, 0.0], [1.0, 1.0]], [30, 30]);
+	let mut panel = UiPanel9Slice::new(interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(100, 60));
+	panel.setTexturePart("panel", part);
+	panel.setInsets([10.0; 4]);
+
+	let quads = panel.cache_get().unwrap();
+	assert_eq!(quads.vertex.len(), 9 * 4);
+	assert_eq!(quads.indices.len(), 9 * 6);
+	// right top of the left top corner
+	assert_eq!(quads.vertex[1].uvcoord, [10.0 / 30.0, 0.0]);
+
+	// 25 pixels of middle with 10 pixels tiles : 2 full tiles and a half one
+	panel.setSquare(interfacePosition::new_pixel(0, 0), interfacePosition::new_pixel(45, 30));
+	panel.setMode(UiPanel9Slice_mode::TILE);
+	let quads = panel.cache_get().unwrap();
+	assert_eq!(quads.vertex.len(), 5 * 3 * 4);
+	assert!((quads.vertex[3 * 4 + 1].uvcoord[0] - 0.5).abs() < 0.0001);
+}
+
+#[test]
+fn markup_page()
+{
+	static TICKS: AtomicU32 = AtomicU32::new(0);
+	ManagerInterface::singleton().UiHandlerAdd("markupTick", |content| {
+		TICKS.fetch_add(1, Ordering::Relaxed);
+		content.downcast_mut::<Plane<interfacePosition>>().is_some()
+	});
+
+	let page = UiMarkup::loadJson(&json!({
+		"content": [
+			{ "name": "bg", "type": "Plane", "leftTop": { "percent": [0, 0] }, "bottomRight": { "percent": [1, 1] }, "color": "#000000", "events": { "EACH_TICK": "markupTick" } },
+			{ "name": "list", "type": "UiLayout", "layout": "FLEX", "leftTop": [0, 0], "bottomRight": [100, 100, 10], "content": [
+				{ "type": "Plane", "leftTop": [0, 0], "bottomRight": [0, 0], "size": [0, 30] },
+				{ "type": "Plane", "leftTop": [0, 0], "bottomRight": [0, 0], "grow": 1 }
+			]}
+		],
+		"focusOrder": ["list"]
+	}))
+	.unwrap();
+
+	assert!(page.get("bg").unwrap().get_mut().event_trigger(event_type::EACH_TICK));
+	assert_eq!(TICKS.load(Ordering::Relaxed), 1);
+	let list = page.get("list").unwrap();
+	let mut guard = list.get_mut();
+	let content: &mut dyn UiPageContent = &mut **guard;
+	assert_eq!(content.downcast_mut::<UiLayout>().unwrap().len(), 2);
+
+	// typos and missing handlers are reported
+	let plane = json!({ "name": "p", "type": "Plane", "leftTop": [0, 0], "bottomRight": [1, 1], "colour": "#000000" });
+	assert!(UiMarkup::loadJson(&json!({ "content": [plane] })).is_err());
+	let plane = json!({ "name": "p", "type": "Plane", "leftTop": [0, 0], "bottomRight": [1, 1], "events": { "HOVER": "missingHandler" } });
+	assert!(UiMarkup::loadJson(&json!({ "content": [plane] })).is_err());
+}
+
+#[test]
+fn overlay_stack()
+{
+	let manager = ManagerInterface::singleton();
+	manager.pushOverlay("overlayA", UiOverlay_options::popup());
+	manager.pushOverlay("overlayB", UiOverlay_options::popup());
+	// pushing again move on top
+	manager.pushOverlay("overlayA", UiOverlay_options::popup());
+	assert_eq!(manager.getOverlays(), vec!["overlayB".to_string(), "overlayA".to_string()]);
+
+	assert_eq!(manager.popOverlay(), Some("overlayA".to_string()));
+	assert!(!manager.removeOverlay("overlayA"));
+	assert!(manager.removeOverlay("overlayB"));
+	assert!(manager.getOverlays().is_empty());
+
+	// hiding an overlay remove its focus, and close the text input it had
+	let mut page = UiPage::new();
+	page.add("box", focusBox::new(0.0, 0.0));
+	page.add("input", UiTextInput::new(interfacePosition::new_pixel(0, 50), interfacePosition::new_pixel(200, 90)));
+	assert!(page.focus_set("box"));
+	assert!(page.focus_set("input"));
+	assert!(manager.textInput_isWanted());
+	manager.UiPageAppend("overlayFocus", page);
+	manager.pushOverlay("overlayFocus", UiOverlay_options::popup());
+	std::thread::sleep(Duration::from_millis(50));
+	assert_eq!(manager.popOverlay(), Some("overlayFocus".to_string()));
+	// exit run in another thread
+	std::thread::sleep(Duration::from_millis(100));
+
+	let focused = Arc::new(AtomicU32::new(2));
+	let sub_focused = focused.clone();
+	manager.UiPageUpdate("overlayFocus", move |page| {
+		let state = page.focus_get().is_some() || isFocused(page, "box");
+		sub_focused.store(state as u32, Ordering::Relaxed);
+	});
+	assert_eq!(focused.load(Ordering::Relaxed), 0);
+	assert!(!manager.textInput_isWanted());
+}
+
+#[test]
+fn transition_transforms()
+{
+	let fade = UiTransition::new(UiTransition_type::FADE, Duration::from_millis(300)).withEasing(UiTransition_easing::LINEAR);
+	let [oldpage, newpage] = fade.transforms(0.25);
+	assert_eq!(oldpage.alpha, 0.75);
+	assert_eq!(newpage.alpha, 0.25);
+	assert_eq!(newpage.offset, [0.0, 0.0]);
+
+	let scale = UiTransition::new(UiTransition_type::SCALE, Duration::from_millis(300));
+	let [oldpage, newpage] = scale.transforms(1.0);
+	assert_eq!(oldpage.alpha, 0.0);
+	assert_eq!(newpage.scale, 1.0);
+
+	// pixel vertex scaled around the window center then moved, clip follow
+	let mut vertex = HGE_shader_2Dsimple {
+		position: [100.0, 50.0, 0.1],
+		ispixel: 1,
+		clip: [0.0, 0.0, 200.0, 100.0],
+		..Default::default()
+	};
+	let transform = HGE_shader_2Dsimple_transform {
+		alpha: 0.5,
+		scale: 2.0,
+		offset: [10.0, 0.0],
+	};
+	transform.apply(&mut vertex, [200.0, 100.0]);
+	assert_eq!(vertex.position, [110.0, 50.0, 0.1]);
+	assert_eq!(vertex.color[3], 0.5);
+	assert_eq!(vertex.clip, [-90.0, -50.0, 310.0, 150.0]);
+}
+
+#[test]
+fn textMarkup_parse()
+{
+	let segments = TextMarkup::parse("[b]Gold[/b]: [color=#ffd700][size=1.5]120[/size][/color] [icon=ui#coin] [[x]").unwrap();
+	assert_eq!(segments.len(), 6);
+	assert!(matches!(&segments[0], TextMarkup_segment::TEXT { text, font: Some(font), .. } if text == "Gold" && font == "bold"));
+	assert!(matches!(&segments[1], TextMarkup_segment::TEXT { text, font: None, color: None, .. } if text == ": "));
+	let TextMarkup_segment::TEXT { text, color: Some(gold), size, .. } = &segments[2]
+	else
+	{
+		panic!("colored text expected");
+	};
+	assert_eq!(text, "120");
+	assert_eq!(gold.toArray(), color::from([255u8, 215, 0, 255]).toArray());
+	assert_eq!(*size, Some(TextMarkup_size::FACTOR(1.5)));
+	assert!(matches!(&segments[4], TextMarkup_segment::ICON(icon) if icon == "ui#coin"));
+	assert_eq!(TextMarkup::plainText(&segments), "Gold: 120  [x]");
+
+	assert!(TextMarkup::parse("[b]bold[/i]").is_err());
+	assert!(TextMarkup::parse("[blink]no[/blink]").is_err());
+	assert!(TextMarkup::parse("[size=big]no[/size]").is_err());
+}
+
+#[test]
+fn locale_tables()
+{
+	assert_eq!(ManagerLocale::pluralCategory("en", 1), "one");
+	assert_eq!(ManagerLocale::pluralCategory("fr", 0), "one");
+	assert_eq!(ManagerLocale::pluralCategory("ru", 22), "few");
+	assert_eq!(ManagerLocale::pluralCategory("ru", 11), "many");
+	assert_eq!(ManagerLocale::pluralCategory("pt-BR", 2), "other");
+
+	let csv = "key,en,fr\nhello,\"Hello, {name}\",\"Bonjour, {name}\"\nitems.one,{count} item,{count} objet\nitems.other,{count} items,{count} objets\nonly_en,English only,\n";
+	let locale = ManagerLocale::singleton();
+	locale.loadCsv(std::io::Cursor::new(csv)).unwrap();
+	locale.setLanguage("fr");
+	assert_eq!(locale.getWith("hello", &[("name", "Ana")]), "Bonjour, Ana");
+	assert_eq!(locale.getPlural("items", 0, &[]), "0 objet");
+	assert_eq!(locale.getPlural("items", 3, &[]), "3 objets");
+	assert_eq!(locale.get("only_en"), "English only");
+	assert_eq!(locale.get("missing.key"), "missing.key");
+	locale.setLanguage("en");
+	assert_eq!(locale.getPlural("items", 1, &[]), "1 item");
+}
+
+#[test]
+fn fontSdf_generate()
+{
+	// 8x8 filled square centered in 20x20, edge pixels half covered
+	let coverage = image::GrayImage::from_fn(20, 20, |x, y| {
+		let inside = |v: u32| (6..14).contains(&v);
+		let edge = |v: u32| v == 5 || v == 14;
+		match (inside(x) || edge(x), inside(y) || edge(y))
+		{
+			(true, true) if edge(x) || edge(y) => image::Luma([128u8]),
+			(true, true) => image::Luma([255u8]),
+			_ => image::Luma([0u8]),
+		}
+	});
+	let sdf = FontSdf::generate(&coverage, 4.0);
+	let value = |x: u32, y: u32| sdf.get_pixel(x, y).0[0];
+
+	assert!((127..=129).contains(&value(5, 10)));
+	assert_eq!(value(10, 10), 255);
+	assert_eq!(value(0, 0), 0);
+	assert!(value(7, 10) > value(6, 10));
+	assert!(value(3, 10) < value(4, 10));
+	assert!(value(4, 10) < 128 && value(6, 10) > 128);
+}
+
+#[test]
+fn font_fallbackRuns()
+{
+	let path = format!("{}/../examples/simple_2D/static/fonts/NotoSans-SemiBold.ttf", env!("CARGO_MANIFEST_DIR"));
+	let font = FontArc::try_from_vec(std::fs::read(path).unwrap()).unwrap();
+	let fonts = vec![font.clone(), font];
+	let runs = |chain: &[FontId], text: &str| -> Vec<(usize, String)> {
+		ManagerFont::fallbackRuns(&fonts, chain, text).into_iter().map(|(id, run)| (id.0, run)).collect()
+	};
+
+	// the first font of the chain having the char is used
+	assert_eq!(runs(&[FontId(1), FontId(0)], "héllo"), vec![(1, "héllo".to_string())]);
+	assert_eq!(runs(&[FontId(0), FontId(1)], "héllo"), vec![(0, "héllo".to_string())]);
+	// fonts not loaded (registered later) are skipped
+	assert_eq!(runs(&[FontId(5), FontId(1)], "ab"), vec![(1, "ab".to_string())]);
+
+	// chars missing in every font use the first loaded font, keeping the run
+	assert_eq!(runs(&[FontId(5), FontId(1), FontId(0)], "a中文b"), vec![(1, "a中文b".to_string())]);
+	assert_eq!(runs(&[FontId(0)], ""), vec![]);
+}
+
+// single line, each char is half its scale wide
+fn fixedMeasure(section: &OwnedSection<Extra>) -> [f32; 2]
+{
+	let width = section.text.iter().map(|x| x.text.chars().count() as f32 * x.scale.x / 2.0).sum();
+	let height = section.text.iter().map(|x| x.scale.y).fold(0.0, f32::max);
+	return [width, height];
+}
+
+fn sectionOf(texts: &[&str], scale: f32) -> OwnedSection<Extra>
+{
+	let mut section = OwnedSection::default();
+	for text in texts
+	{
+		section = section.add_text(OwnedText::<Extra>::new(*text).with_scale(scale));
+	}
+	return section;
+}
+
+fn sectionString(section: &OwnedSection<Extra>) -> Vec<String>
+{
+	return section.text.iter().map(|x| x.text.clone()).collect();
+}
+
+#[test]
+fn measure_shrinkToFit()
+{
+	// 11 chars of 10 pixel
+	let section = sectionOf(&["hello world"], 20.0);
+	assert_eq!(fixedMeasure(&section), [110.0, 20.0]);
+
+	// already fitting : never scaled up
+	let (fitted, factor) = ManagerFont::shrinkToFit_with(&section, [200.0, 50.0], fixedMeasure);
+	assert_eq!(factor, 1.0);
+	assert_eq!(fitted.text[0].scale.y, 20.0);
+
+	// shrink until it fits
+	let (fitted, factor) = ManagerFont::shrinkToFit_with(&section, [55.0, 50.0], fixedMeasure);
+	let [width, height] = fixedMeasure(&fitted);
+	assert!(width <= 55.0 && height <= 50.0);
+	assert!(factor <= 0.5 && factor > 0.4);
+	assert!((fitted.text[0].scale.y - 20.0 * factor).abs() < 0.001);
+
+	// never below the minimum size, the text overflow
+	let (fitted, factor) = ManagerFont::shrinkToFit_with(&section, [1.0, 1.0], fixedMeasure);
+	assert!((fitted.text[0].scale.y - MANAGERFONT_SHRINK_MINSCALE).abs() < 0.001);
+	assert!((factor - MANAGERFONT_SHRINK_MINSCALE / 20.0).abs() < 0.001);
+}
+
+#[test]
+fn measure_ellipsis()
+{
+	let section = sectionOf(&["hello world"], 20.0);
+
+	// exact fit is kept whole
+	let cut = ManagerFont::ellipsis_with(section.clone(), 110.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["hello world"]);
+
+	// one pixel less : cut, the space before the ellipsis is removed
+	let cut = ManagerFont::ellipsis_with(section.clone(), 109.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["hello wor…"]);
+	let cut = ManagerFont::ellipsis_with(section.clone(), 60.0, "...", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["hel..."]);
+	let cut = ManagerFont::ellipsis_with(section, 60.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["hello…"]);
+	assert!(fixedMeasure(&cut)[0] <= 60.0);
+
+	// at least the ellipsis alone
+	let cut = ManagerFont::ellipsis_with(sectionOf(&["hello"], 20.0), 5.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["…"]);
+
+	// multibyte chars are never split
+	let cut = ManagerFont::ellipsis_with(sectionOf(&["héllo wörld"], 20.0), 50.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["héll…"]);
+	let cut = ManagerFont::ellipsis_with(sectionOf(&["日本語のテキスト"], 20.0), 40.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["日本語…"]);
+
+	// the ellipsis end the last kept text of the section
+	let cut = ManagerFont::ellipsis_with(sectionOf(&["ab", "cd", "ef"], 20.0), 40.0, "…", fixedMeasure);
+	assert_eq!(sectionString(&cut), vec!["ab", "c…", ""]);
+}
+
+#[test]
+fn textEffect_animations()
+{
+	let typewriter = TextEffect::typewriter(10.0);
+	assert_eq!(typewriter(5, 1.0).color[3], 1.0);
+	assert_eq!(typewriter(10, 1.0).color[3], 0.0);
+	assert!((typewriter(10, 1.05).color[3] - 0.5).abs() < 0.001);
+	assert_eq!(typewriter(0, 1.0).offset, TextEffect_glyph::default().offset);
+
+	let shake = TextEffect::shake(3.0);
+	assert_eq!(shake(4, 0.5), shake(4, 0.5));
+	assert!(shake(4, 0.5).offset.iter().all(|x| x.abs() <= 3.0));
+	let wave = TextEffect::wave(2.0, 1.0);
+	assert!((wave(0, std::f32::consts::FRAC_PI_2).offset[1] - 2.0).abs() < 0.001);
+}
+
+#[test]
+fn spriteAnimator_frames()
+{
+	let frame = |ms: u64| SpriteAnimator_frame::new("sheet", [[0.0; 2]; 4], Duration::from_millis(ms));
+	let mut animator = SpriteAnimator::new(vec![frame(100), frame(200), frame(100)], SpriteAnimator_mode::LOOP);
+	assert_eq!(animator.frameAt(Duration::from_millis(50)), (0, 0, false));
+	assert_eq!(animator.frameAt(Duration::from_millis(250)), (1, 0, false));
+	assert_eq!(animator.frameAt(Duration::from_millis(450)), (0, 1, false));
+	animator.setMode(SpriteAnimator_mode::PINGPONG);
+	assert_eq!(animator.frameAt(Duration::from_millis(450)), (1, 0, false));
+	assert_eq!(animator.frameAt(Duration::from_millis(650)), (0, 1, false));
+	animator.setMode(SpriteAnimator_mode::ONCE);
+	assert_eq!(animator.frameAt(Duration::from_millis(900)), (2, 1, true));
+
+	let sheet = json!({
+		"frames": {
+			"walk 10.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+			"walk 2.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "duration": 150 },
+			"walk 1.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 32 }, "rotated": true, "duration": 50 }
+		},
+		"meta": { "size": { "w": 64, "h": 32 }, "frameTags": [{ "name": "idle", "from": 0, "to": 1, "direction": "pingpong" }] }
+	});
+	let aseprite = SpriteAnimator::fromAsepriteJson("sheet", &sheet, None).unwrap();
+	let durations: Vec<_> = aseprite.frames().iter().map(|x| x.duration.as_millis()).collect();
+	assert_eq!(durations, vec![50, 150, 100]);
+	// rotated : 16x32 sprite stored as 32x16
+	assert_eq!(aseprite.frames()[0].uvcoord, [[1.0, 0.0], [1.0, 0.5], [0.5, 0.0], [0.5, 0.5]]);
+	let idle = SpriteAnimator::fromAsepriteJson("sheet", &sheet, Some("idle")).unwrap();
+	assert_eq!(idle.frames().len(), 2);
+	assert_eq!(idle.getMode(), SpriteAnimator_mode::PINGPONG);
+	assert!(SpriteAnimator::fromAsepriteJson("sheet", &sheet, Some("missing")).is_err());
+	assert!(SpriteAnimator::fromTexturePackerJson("sheet", &json!({ "frames": [] }), 10.0, SpriteAnimator_mode::LOOP).is_err());
+}
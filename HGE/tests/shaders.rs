@@ -50,6 +50,20 @@ pub mod HGE_rawshader_2Dsimple_frag {
 	}
 }
 
+pub mod HGE_rawshader_2Dtext_vert {
+	vulkano_shaders::shader! {
+		ty: "vertex",
+		path: "./tests/shaders_glsl/text2D/vert.glsl",
+	}
+}
+
+pub mod HGE_rawshader_2Dtext_frag {
+	vulkano_shaders::shader! {
+		ty: "fragment",
+		path: "./tests/shaders_glsl/text2D/frag.glsl"
+	}
+}
+
 pub mod HGE_rawshader_3Dsimple_vert {
 	vulkano_shaders::shader! {
 		ty: "vertex",
@@ -87,6 +101,25 @@ pub fn loadShaders()
 		constantFunc: "".to_string(),
 	});
 	
+	let mut shaders = BTreeMap::new();
+	shaders.insert(Shader_type::VERTEX, HGE_rawshader_2Dtext_vert::load(device.clone()).unwrap());
+	shaders.insert(Shader_type::FRAGMENT, HGE_rawshader_2Dtext_frag::load(device.clone()).unwrap());
+	Manager::ManagerShaders::singleton().add(names::text2D, ShaderContent{
+		shader: shaders,
+		pushConstant_Func: Arc::new(|cmdBuilder, pipeline_layout,offset|{
+			let windowdim = HGEMain::singleton().getWindowInfos();
+			let rotation = windowdim.orientation.getDeg();
+			let worldMatrix = Matrix4::from(Matrix3::from_angle_z(Deg(rotation)));
+			let uniform_data = HGE_rawshader_2Dtext_vert::PushConstants {
+				window: windowdim.into(),
+				time: HGEMain::singleton().getDurationFromStart().as_secs_f32().into(),
+				world: worldMatrix.into(),
+			};
+			HTraceError!(cmdBuilder.push_constants(pipeline_layout,	offset,uniform_data));
+		}),
+		constantFunc: "".to_string(),
+	});
+	
 	let func = |cmdBuilder: &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>, pipeline_layout: Arc<PipelineLayout>,offset: u32|{
 		let bindingcameraC = HGEMain::singleton().getCamera();
 		let cameraC = bindingcameraC.get();
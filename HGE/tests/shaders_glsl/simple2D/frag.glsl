@@ -12,12 +12,20 @@ layout(location = 1) in vec4 v_color;
 layout(location = 2) in vec2 v_texcoord;
 layout(location = 3) flat in uint v_nbtexture;
 layout(location = 4) flat in uint v_color_blend_type;
+layout(location = 5) in vec2 v_pixel;
+layout(location = 6) flat in vec4 v_clip;
 
 layout(location = 0) out vec4 f_color;
 
 void main()
 {
 	//float depthCalc = (gl_FragCoord.z / gl_FragCoord.w) / uniforms.window.z;
+	// clip rect in pixel (left, top, right, bottom), disabled if empty
+	if (v_clip.z > v_clip.x && (v_pixel.x < v_clip.x || v_pixel.x > v_clip.z || v_pixel.y < v_clip.y || v_pixel.y > v_clip.w))
+	{
+		discard;
+	}
+
 	vec4 tmp_color = vec4(0.0, 0.0, 0.0, 1.0);
 
 	if (v_nbtexture==0)
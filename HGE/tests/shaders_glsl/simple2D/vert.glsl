@@ -10,12 +10,15 @@ layout(location = 2) in uint texture;
 layout(location = 4) in vec2 uvcoord;
 layout(location = 5) in vec4 color;
 layout(location = 6) in uint color_blend_type;
+layout(location = 7) in vec4 clip;
 
 layout(location = 0) out vec3 v_normal;
 layout(location = 1) out vec4 v_color;
 layout(location = 2) out vec2 v_texcoord;
 layout(location = 3) out uint v_nbtexture;
 layout(location = 4) out uint v_color_blend_type;
+layout(location = 5) out vec2 v_pixel;
+layout(location = 6) out vec4 v_clip;
 
 void main() {
     v_normal = vec3(0.0,0.0,1.0);
@@ -23,14 +26,17 @@ void main() {
     v_texcoord = uvcoord;
     v_nbtexture = texture;
 	v_color_blend_type = color_blend_type;
+	v_clip = clip;
     if(ispixel==1)
     {
         float posx = ((position.x / globals.window.x)*2.0) - 1.0;
         float posy = ((position.y / globals.window.y)*2.0) - 1.0;
+        v_pixel = position.xy;
         gl_Position = globals.world * vec4(posx,posy,position.z, 1.0);
     }
     else
     {
+        v_pixel = ((position.xy + 1.0) / 2.0) * globals.window.xy;
         gl_Position = globals.world * vec4(position, 1.0);
     }
 }
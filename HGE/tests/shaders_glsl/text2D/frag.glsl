@@ -0,0 +1,78 @@
+#version 450
+#extension GL_GOOGLE_include_directive : require
+
+#include "../define.glsl"
+#include "../pc_2D.glsl"
+
+layout(constant_id=0) const uint transparent = 0;
+
+// signed distance field of the glyphs in alpha, 0.5 on the edge
+layout(set = 0, binding = 0) uniform sampler2D font_sdf;
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_texcoord;
+layout(location = 2) in vec2 v_pixel;
+layout(location = 3) flat in vec4 v_clip;
+layout(location = 4) flat in vec4 v_outline_color;
+layout(location = 5) flat in float v_outline_width;
+layout(location = 6) flat in vec4 v_shadow_color;
+layout(location = 7) flat in vec2 v_shadow_offset;
+
+layout(location = 0) out vec4 f_color;
+
+// coverage of the shape "distance >= edge", antialiased on one screen pixel
+float coverage(float distance, float edge)
+{
+	float width = max(fwidth(distance) * 0.5, 0.0001);
+	return smoothstep(edge - width, edge + width, distance);
+}
+
+// "top" over "bottom", non premultiplied
+vec4 blend(vec4 top, vec4 bottom)
+{
+	float alpha = top.a + bottom.a * (1.0 - top.a);
+	if (alpha <= 0.0)
+	{
+		return vec4(0.0);
+	}
+	return vec4((top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / alpha, alpha);
+}
+
+void main()
+{
+	// clip rect in pixel (left, top, right, bottom), disabled if empty
+	if (v_clip.z > v_clip.x && (v_pixel.x < v_clip.x || v_pixel.x > v_clip.z || v_pixel.y < v_clip.y || v_pixel.y > v_clip.w))
+	{
+		discard;
+	}
+
+	float distance = texture(font_sdf, v_texcoord).a;
+	vec4 tmp_color = vec4(v_color.rgb, v_color.a * coverage(distance, 0.5));
+
+	if (v_outline_width > 0.0)
+	{
+		vec4 outline = vec4(v_outline_color.rgb, v_outline_color.a * coverage(distance, 0.5 - v_outline_width));
+		tmp_color = blend(tmp_color, outline);
+	}
+
+	if (v_shadow_color.a > 0.0)
+	{
+		vec2 shadowUv = v_texcoord - v_shadow_offset / vec2(textureSize(font_sdf, 0));
+		float shadowDistance = texture(font_sdf, shadowUv).a;
+		vec4 shadow = vec4(v_shadow_color.rgb, v_shadow_color.a * coverage(shadowDistance, 0.5 - v_outline_width));
+		tmp_color = blend(tmp_color, shadow);
+	}
+
+	if(transparent==0)
+	{
+		if (tmp_color.a<0.99)
+			discard;
+	}
+	else
+	{
+		if(tmp_color.a>=0.99 || tmp_color.a<=0.0)
+			discard;
+	}
+
+	f_color = tmp_color;
+}
@@ -0,0 +1,46 @@
+#version 450
+#extension GL_GOOGLE_include_directive : require
+
+#include "../define.glsl"
+#include "../pc_2D.glsl"
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in uint ispixel;
+layout(location = 2) in vec2 uvcoord;
+layout(location = 3) in vec4 color;
+layout(location = 4) in vec4 clip;
+layout(location = 5) in vec4 outline_color;
+layout(location = 6) in float outline_width;
+layout(location = 7) in vec4 shadow_color;
+layout(location = 8) in vec2 shadow_offset;
+
+layout(location = 0) out vec4 v_color;
+layout(location = 1) out vec2 v_texcoord;
+layout(location = 2) out vec2 v_pixel;
+layout(location = 3) flat out vec4 v_clip;
+layout(location = 4) flat out vec4 v_outline_color;
+layout(location = 5) flat out float v_outline_width;
+layout(location = 6) flat out vec4 v_shadow_color;
+layout(location = 7) flat out vec2 v_shadow_offset;
+
+void main() {
+	v_color = color;
+	v_texcoord = uvcoord;
+	v_clip = clip;
+	v_outline_color = outline_color;
+	v_outline_width = outline_width;
+	v_shadow_color = shadow_color;
+	v_shadow_offset = shadow_offset;
+	if(ispixel==1)
+	{
+		float posx = ((position.x / globals.window.x)*2.0) - 1.0;
+		float posy = ((position.y / globals.window.y)*2.0) - 1.0;
+		v_pixel = position.xy;
+		gl_Position = globals.world * vec4(posx,posy,position.z, 1.0);
+	}
+	else
+	{
+		v_pixel = ((position.xy + 1.0) / 2.0) * globals.window.xy;
+		gl_Position = globals.world * vec4(position, 1.0);
+	}
+}
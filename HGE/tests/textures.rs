@@ -0,0 +1,164 @@
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+use image::{Rgba, RgbaImage};
+use Hconfig::serde_json::json;
+use HGE::Textures::generate;
+use HGE::Textures::textureCompressed::Texture_compressed;
+use HGE::Textures::textureLoader::textureLoader_fromKTX2;
+use HGE::Textures::texturePart::{texturePart, texturePart_FromGrid, texturePart_json};
+use HGE::Textures::TextureAtlas::{TextureAtlas, TextureAtlas_insert};
+use HGE::Textures::Textures::Texture;
+use vulkano::format::Format;
+
+fn overlap(a: [u32; 4], b: [u32; 4]) -> bool
+{
+	a[0] < b[0] + b[2] && b[0] < a[0] + a[2] && a[1] < b[1] + b[3] && b[1] < a[1] + a[3]
+}
+
+#[test]
+fn atlas_packing()
+{
+	let mut atlas = TextureAtlas::new([64, 64]);
+	for i in 0..6
+	{
+		let placed = atlas.insert(format!("icon{}", i), RgbaImage::from_pixel(20, 20, Rgba([i as u8, 0, 0, 255]))).unwrap();
+		assert!(matches!(placed, TextureAtlas_insert::PLACED(_)));
+	}
+	// 7th does not fit in 64x64 with padding : grown and repacked
+	assert_eq!(atlas.insert("big", RgbaImage::new(40, 40)).unwrap(), TextureAtlas_insert::REBUILT);
+	assert!(atlas.size()[0] > 64 || atlas.size()[1] > 64);
+
+	let names: Vec<String> = (0..6).map(|i| format!("icon{}", i)).chain(["big".to_string()]).collect();
+	let rects: Vec<_> = names.iter().map(|x| atlas.rect(x).unwrap()).collect();
+	for (i, a) in rects.iter().enumerate()
+	{
+		assert!(a[0] + a[2] <= atlas.size()[0] && a[1] + a[3] <= atlas.size()[1]);
+		assert!(rects[i + 1..].iter().all(|b| !overlap(*a, *b)));
+	}
+
+	let parts = atlas.parts();
+	let icon2 = atlas.rect("icon2").unwrap();
+	assert_eq!(parts["icon2"].dim, [20, 20]);
+	assert_eq!(parts["icon2"].uvcoord[0][0], icon2[0] as f32 / atlas.size()[0] as f32);
+	assert_eq!(atlas.compose().get_pixel(icon2[0] + 5, icon2[1] + 5).0, [2, 0, 0, 255]);
+
+	// same size replacement stay in place
+	assert_eq!(atlas.insert("icon2", RgbaImage::new(20, 20)).unwrap(), TextureAtlas_insert::PLACED([icon2[0], icon2[1]]));
+	assert!(atlas.remove("big"));
+	assert!(atlas.rect("big").is_none());
+	assert!(atlas.insert("huge", RgbaImage::new(9000, 4)).is_err());
+}
+
+#[test]
+fn texturePart_loaders()
+{
+	let texture = Texture {
+		width: Some(72),
+		height: Some(34),
+		..Default::default()
+	};
+	let grid = texturePart_FromGrid {
+		rows: 2,
+		cols: 4,
+		margin: 1,
+		spacing: 2,
+		prefix: "tile".to_string(),
+	};
+	let parts = grid.load(&texture).unwrap();
+	assert_eq!(parts.len(), 8);
+	// 16x15 cells : the 6th is the second of the second row
+	let tile = parts["tile5"];
+	assert_eq!(tile.dim, [16, 15]);
+	assert_eq!(tile.uvcoord[0], [19.0 / 72.0, 18.0 / 34.0]);
+	assert!(texturePart_FromGrid { rows: 0, ..grid.clone() }.load(&texture).is_err());
+	assert!(texturePart_FromGrid { margin: 40, ..grid }.load(&texture).is_err());
+
+	let sheet = json!({
+		"frames": {
+			"run_2.png": { "frame": { "x": 0, "y": 0, "w": 16, "h": 32 }, "rotated": true, "trimmed": false },
+			"run_10.png": { "frame": { "x": 32, "y": 0, "w": 12, "h": 10 }, "rotated": false, "trimmed": true,
+				"spriteSourceSize": { "x": 2, "y": 3, "w": 12, "h": 10 }, "sourceSize": { "w": 16, "h": 16 } }
+		},
+		"meta": { "size": { "w": 64, "h": 32 } }
+	});
+	let frames = texturePart_json::frames(&sheet, texturePart_json::sheetSize(&sheet).unwrap()).unwrap();
+	let names: Vec<_> = frames.iter().map(|(name, _, _)| name.as_str()).collect();
+	assert_eq!(names, vec!["run_2.png", "run_10.png"]);
+	// rotated : 16x32 sprite stored as 32x16
+	let rotated = frames[0].1;
+	assert_eq!(rotated.dim, [16, 32]);
+	assert_eq!(rotated.corners(), [[0.5, 0.0], [0.5, 0.5], [0.0, 0.0], [0.0, 0.5]]);
+	assert_eq!(frames[1].1.trim, Some([2, 3, 16, 16]));
+	assert_eq!(frames[1].1.trimRect(), Some([0.125, 0.1875, 0.875, 0.8125]));
+	assert_eq!(rotated.trimRect(), None);
+
+	let malformed = json!({ "frames": { "bad": { "frame": { "x": 0, "y": 0, "w": 16 } } }, "meta": { "size": { "w": 64, "h": 32 } } });
+	assert!(texturePart_json::frames(&malformed, [64.0, 32.0]).is_err());
+	let outside = json!({ "frames": [{ "filename": "big", "frame": { "x": 60, "y": 0, "w": 16, "h": 16 } }] });
+	assert!(texturePart_json::frames(&outside, [64.0, 32.0]).is_err());
+}
+
+fn ktx2(vkFormat: u32, width: u32, height: u32, supercompression: u32, levels: &[Vec<u8>]) -> Vec<u8>
+{
+	let mut bytes = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+	for value in [vkFormat, 1, width, height, 0, 0, 1, levels.len() as u32, supercompression]
+	{
+		bytes.extend_from_slice(&value.to_le_bytes());
+	}
+	bytes.resize(80, 0);
+	let mut offset = 80 + levels.len() * 24;
+	for level in levels
+	{
+		for value in [offset, level.len(), level.len()]
+		{
+			bytes.extend_from_slice(&(value as u64).to_le_bytes());
+		}
+		offset += level.len();
+	}
+	for level in levels
+	{
+		bytes.extend_from_slice(level);
+	}
+	return bytes;
+}
+
+#[test]
+fn mipmap_compressed()
+{
+	assert_eq!(generate::mipmapLevels(256, 64, 0), 9);
+	assert_eq!(generate::mipmapLevels(5, 3, 0), 3);
+	assert_eq!(generate::mipmapLevels(256, 256, 4), 4);
+	let chain: Vec<_> = generate::mipmapChain(RgbaImage::from_pixel(4, 2, Rgba([255, 0, 0, 255])), 3).iter().map(|x| x.dimensions()).collect();
+	assert_eq!(chain, vec![(4, 2), (2, 1), (1, 1)]);
+
+	// BC1 blocks : color0 pure red (565), all indexes at 0
+	let block = vec![0x00, 0xF8, 0x00, 0x00, 0, 0, 0, 0];
+	let loaded = textureLoader_fromKTX2::fromBytes(&ktx2(131, 4, 4, 0, &[block.clone(), block.clone()])).unwrap();
+	assert_eq!((loaded.width, loaded.height), (4, 4));
+	let compressed = loaded.compressed.unwrap();
+	assert_eq!(compressed.format, Format::BC1_RGB_UNORM_BLOCK);
+	assert_eq!(compressed.levels.len(), 2);
+	let decompressed = compressed.decompress(4, 4).unwrap();
+	assert_eq!(decompressed.get_pixel(3, 3), &Rgba([255, 0, 0, 255]));
+
+	// BC2 : explicit 4 bits alpha (8 => 0x88) then the BC1 color block
+	let bc2 = [vec![0x88; 8], block.clone()].concat();
+	let compressed = textureLoader_fromKTX2::fromBytes(&ktx2(135, 4, 4, 0, &[bc2])).unwrap().compressed.unwrap();
+	assert_eq!(compressed.format, Format::BC2_UNORM_BLOCK);
+	assert_eq!(compressed.decompress(4, 4).unwrap().get_pixel(0, 0), &Rgba([255, 0, 0, 0x88]));
+
+	// rgba8 is loaded as raw
+	let rgba = textureLoader_fromKTX2::fromBytes(&ktx2(37, 1, 1, 0, &[vec![1, 2, 3, 4]])).unwrap();
+	assert!(rgba.compressed.is_none());
+	assert_eq!(rgba.raw, vec![1, 2, 3, 4]);
+
+	assert!(textureLoader_fromKTX2::fromBytes(&ktx2(131, 4, 4, 2, &[block.clone()])).is_err());
+	assert!(textureLoader_fromKTX2::fromBytes(&ktx2(131, 8, 8, 0, &[block.clone()])).is_err());
+	// crafted dimensions : size overflowing or bigger than the file
+	assert!(textureLoader_fromKTX2::fromBytes(&ktx2(37, u32::MAX, u32::MAX, 0, &[vec![0; 4]])).is_err());
+	assert!(textureLoader_fromKTX2::fromBytes(&ktx2(131, u32::MAX, u32::MAX, 0, &[block.clone()])).is_err());
+	assert_eq!(Texture_compressed::levelSize(Format::R8G8B8A8_UNORM, u32::MAX, u32::MAX), None);
+	assert!(textureLoader_fromKTX2::fromBytes(&ktx2(1000, 4, 4, 0, &[block])).is_err());
+	assert!(textureLoader_fromKTX2::fromBytes(b"not a ktx2 file").is_err());
+}
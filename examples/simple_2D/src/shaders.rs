@@ -56,6 +56,22 @@ pub mod HGE_rawshader_2Dsimple_frag
 	}
 }
 
+pub mod HGE_rawshader_2Dtext_vert
+{
+	vulkano_shaders::shader! {
+		ty: "vertex",
+		path: "./shaders_glsl/text2D/vert.glsl",
+	}
+}
+
+pub mod HGE_rawshader_2Dtext_frag
+{
+	vulkano_shaders::shader! {
+		ty: "fragment",
+		path: "./shaders_glsl/text2D/frag.glsl"
+	}
+}
+
 pub mod HGE_rawshader_3Dsimple_vert
 {
 	vulkano_shaders::shader! {
@@ -97,6 +113,28 @@ pub fn loadShaders()
 		},
 	);
 
+	let mut shaders = BTreeMap::new();
+	shaders.insert(Shader_type::VERTEX, HGE_rawshader_2Dtext_vert::load(device.clone()).unwrap());
+	shaders.insert(Shader_type::FRAGMENT, HGE_rawshader_2Dtext_frag::load(device.clone()).unwrap());
+	Manager::ManagerShaders::singleton().add(
+		names::text2D,
+		ShaderContent {
+			shader: shaders,
+			pushConstant_Func: Arc::new(|cmdBuilder, pipeline_layout, offset| {
+				let windowdim = HGEMain::singleton().getWindowInfos();
+				let rotation = windowdim.orientation.getDeg();
+				let worldMatrix = Matrix4::from(Matrix3::from_angle_z(Deg(rotation)));
+				let uniform_data = HGE_rawshader_2Dtext_vert::PushConstants {
+					world: worldMatrix.into(),
+					window: windowdim.into(),
+					time: HGEMain::singleton().getDurationFromStart().as_secs_f32().into(),
+				};
+				HTraceError!(cmdBuilder.push_constants(pipeline_layout, offset, uniform_data));
+			}),
+			constantFunc: "".to_string(),
+		},
+	);
+
 	let func = |cmdBuilder: &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>, pipeline_layout: Arc<PipelineLayout>, offset: u32| {
 		let bindingcameraC = HGEMain::singleton().getCamera();
 		let cameraC = bindingcameraC.get();